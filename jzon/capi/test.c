@@ -0,0 +1,180 @@
+// Exercises the C API through the shared library; `make` builds and runs it.
+
+#include <math.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "jzon.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                            \
+    do {                                                                       \
+        if (!(cond)) {                                                         \
+            fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #cond);         \
+            failures++;                                                        \
+        }                                                                      \
+    } while (0)
+
+static jzon *parse(const char *text) {
+    jzon *value = NULL;
+    jzon_error err;
+    jzon_status status = jzon_parse(text, strlen(text), &value, &err);
+    if (status != JZON_OK) {
+        fprintf(stderr, "%zu:%zu: %s\n", err.line, err.column, err.message);
+    }
+    CHECK(status == JZON_OK);
+    return value;
+}
+
+// `value` as text, which the caller frees
+static char *write(const jzon *value, unsigned flags) {
+    size_t len = 0;
+    CHECK(jzon_write(value, flags, NULL, 0, &len) == JZON_ERR_BUFFER);
+    char *buf = malloc(len + 1);
+    CHECK(jzon_write(value, flags, buf, len + 1, &len) == JZON_OK);
+    CHECK(strlen(buf) == len);
+    return buf;
+}
+
+static void test_read(void) {
+    jzon *doc = parse("{\"name\": \"jzon\", \"tags\": [\"json\", \"c\"], "
+                      "\"version\": {\"major\": 0, \"minor\": 1.5}, "
+                      "\"stable\": false, \"nul\": \"a\\u0000b\"}");
+    CHECK(jzon_type_of(doc) == JZON_OBJECT);
+    CHECK(jzon_len(doc) == 5);
+
+    const char *s;
+    size_t len;
+    CHECK(jzon_get_string(jzon_member(doc, "name"), &s, &len) == JZON_OK);
+    CHECK(len == 4 && memcmp(s, "jzon", 4) == 0);
+    CHECK(jzon_get_string(jzon_member(doc, "nul"), &s, &len) == JZON_OK);
+    CHECK(len == 3 && memcmp(s, "a\0b", 3) == 0);
+
+    const jzon *tags = jzon_member(doc, "tags");
+    CHECK(jzon_type_of(tags) == JZON_ARRAY && jzon_len(tags) == 2);
+    CHECK(jzon_get_string(jzon_index(tags, 1), &s, &len) == JZON_OK);
+    CHECK(len == 1 && s[0] == 'c');
+    CHECK(jzon_index(tags, 2) == NULL);
+
+    int64_t major;
+    double minor;
+    bool stable = true;
+    CHECK(jzon_get_int(jzon_pointer(doc, "/version/major"), &major) == JZON_OK);
+    CHECK(major == 0);
+    CHECK(jzon_get_double(jzon_pointer(doc, "/version/minor"), &minor) == JZON_OK);
+    CHECK(minor == 1.5);
+    CHECK(jzon_get_int(jzon_pointer(doc, "/version/minor"), &major) == JZON_ERR_TYPE);
+    CHECK(jzon_get_bool(jzon_member(doc, "stable"), &stable) == JZON_OK);
+    CHECK(!stable);
+    CHECK(jzon_pointer(doc, "/tags/0") == jzon_index(tags, 0));
+    CHECK(jzon_pointer(doc, "/missing") == NULL);
+    CHECK(jzon_type_of(NULL) == JZON_NULL);
+
+    jzon_iter *it = jzon_iter_new(doc);
+    const char *key;
+    size_t key_len;
+    const jzon *value;
+    size_t members = 0;
+    bool seen_tags = false;
+    while (jzon_iter_next(it, &key, &key_len, &value)) {
+        members++;
+        if (key_len == 4 && memcmp(key, "tags", 4) == 0) {
+            seen_tags = value == tags;
+        }
+    }
+    jzon_iter_free(it);
+    CHECK(members == 5 && seen_tags);
+
+    it = jzon_iter_new(tags);
+    CHECK(jzon_iter_next(it, &key, NULL, &value));
+    CHECK(key == NULL && value == jzon_index(tags, 0));
+    CHECK(jzon_iter_next(it, NULL, NULL, NULL));
+    CHECK(!jzon_iter_next(it, NULL, NULL, NULL));
+    jzon_iter_free(it);
+
+    jzon_free(doc);
+}
+
+static void test_build(void) {
+    jzon *doc = jzon_new_object();
+    jzon *list = jzon_new_array();
+    CHECK(jzon_push(list, jzon_new_int(-7)) == JZON_OK);
+    CHECK(jzon_push(list, jzon_new_double(0.25)) == JZON_OK);
+    CHECK(jzon_push(list, jzon_new_bool(true)) == JZON_OK);
+    CHECK(jzon_push(list, jzon_new_null()) == JZON_OK);
+    CHECK(jzon_insert(doc, "list", list) == JZON_OK);
+    CHECK(jzon_insert(doc, "text", jzon_new_string("tab\there", 8)) == JZON_OK);
+    CHECK(jzon_insert(doc, "empty", jzon_new_object()) == JZON_OK);
+
+    jzon *stray = jzon_new_int(1);
+    CHECK(jzon_push(doc, stray) == JZON_ERR_TYPE);
+    CHECK(jzon_insert(doc, "bad\xff", stray) == JZON_ERR_UTF8);
+    jzon_free(stray);
+    CHECK(jzon_new_string("\xc3", 1) == NULL);
+    CHECK(jzon_new_double(NAN) == NULL);
+    CHECK(jzon_new_double(INFINITY) == NULL);
+
+    char *text = write(doc, JZON_SORT_KEYS);
+    CHECK(strcmp(text, "{\"empty\":{},\"list\":[-7,0.25,true,null],\"text\":\"tab\\there\"}") == 0);
+
+    // what is written reads back the same
+    jzon *copy = parse(text);
+    char *again = write(copy, JZON_SORT_KEYS);
+    CHECK(strcmp(text, again) == 0);
+    free(again);
+    free(text);
+
+    jzon *clone = jzon_clone(copy);
+    jzon_free(copy);
+    text = write(jzon_member(clone, "list"), JZON_PRETTY);
+    CHECK(strcmp(text, "[\n  -7,\n  0.25,\n  true,\n  null\n]") == 0);
+    free(text);
+
+    char small[4] = "xxx";
+    size_t len = 0;
+    CHECK(jzon_write(clone, 0, small, sizeof small, &len) == JZON_ERR_BUFFER);
+    CHECK(len > sizeof small);
+    jzon_free(clone);
+
+    // out of range for a double
+    jzon *huge = parse("[1e400, 2.0]");
+    text = write(huge, 0);
+    CHECK(strcmp(text, "[null,2.0]") == 0);
+    free(text);
+    jzon_free(huge);
+    jzon_free(doc);
+}
+
+static void test_errors(void) {
+    const char *text = "[1,\n  {\"a\" 2}]";
+    jzon *value = (jzon *)1;
+    jzon_error err;
+    CHECK(jzon_parse(text, strlen(text), &value, &err) == JZON_ERR_SYNTAX);
+    CHECK(value == NULL);
+    CHECK(err.status == JZON_ERR_SYNTAX);
+    CHECK(err.offset == 11 && err.line == 2 && err.column == 8);
+    CHECK(strcmp(err.message, "expected ':'") == 0);
+
+    // a line break inside a string, not the string, is what fails
+    text = "\"a\nb\"";
+    CHECK(jzon_parse(text, strlen(text), &value, &err) == JZON_ERR_SYNTAX);
+    CHECK(err.offset == 2 && err.line == 1 && err.column == 3);
+
+    CHECK(jzon_parse(NULL, 1, &value, NULL) == JZON_ERR_NULL);
+    CHECK(strcmp(jzon_status_str(JZON_ERR_BUFFER), "buffer too small") == 0);
+    CHECK(strcmp(jzon_status_str(42), "unknown status") == 0);
+}
+
+int main(void) {
+    test_read();
+    test_build();
+    test_errors();
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+    printf("all checks passed\n");
+    return 0;
+}
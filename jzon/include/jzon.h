@@ -0,0 +1,120 @@
+// The C API of jzon, implemented in src/ffi.rs, whose tests write this
+// header from the declarations there, so the two always agree.
+//
+// A `jzon` is a JSON value. The ones returned by jzon_parse, jzon_new_* and
+// jzon_clone belong to the caller, who frees them with jzon_free or hands
+// them to jzon_push or jzon_insert. The ones found inside a value, by
+// jzon_index, jzon_member, jzon_pointer and jzon_iter_next, are borrowed:
+// they stay valid until the value they were found in is freed or changed.
+// Strings are UTF-8. Those read from values come with a length rather than
+// a NUL, as JSON strings may hold NULs.
+
+#ifndef JZON_H
+#define JZON_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct jzon jzon;
+typedef struct jzon_iter jzon_iter;
+
+typedef enum {
+    JZON_OK = 0,
+    // the text isn't JSON, the jzon_error tells where
+    JZON_ERR_SYNTAX,
+    // a string or key given isn't UTF-8
+    JZON_ERR_UTF8,
+    // the value isn't of the type asked for
+    JZON_ERR_TYPE,
+    // the output needs more room than given
+    JZON_ERR_BUFFER,
+    // a pointer that mustn't be null is
+    JZON_ERR_NULL,
+} jzon_status;
+
+typedef enum {
+    JZON_NULL = 0,
+    JZON_BOOL,
+    JZON_INTEGER,
+    JZON_DOUBLE,
+    JZON_STRING,
+    JZON_ARRAY,
+    JZON_OBJECT,
+} jzon_type;
+
+// Where parsing stopped. The offset counts bytes from 0, line and column
+// start at 1 and the column counts chars. The message is static.
+typedef struct {
+    jzon_status status;
+    size_t offset;
+    size_t line;
+    size_t column;
+    const char *message;
+} jzon_error;
+
+// flags of jzon_write
+#define JZON_PRETTY 1u
+#define JZON_SORT_KEYS 2u
+
+// Parses `len` bytes of `text`, which needn't end in a NUL. On success *out
+// is the value, otherwise it is NULL and `err`, which may be NULL, says why.
+jzon_status jzon_parse(const char *text, size_t len, jzon **out, jzon_error *err);
+void jzon_free(jzon *value);
+jzon *jzon_clone(const jzon *value);
+// an int, so any value is safe to pass; unknown ones get "unknown status"
+const char *jzon_status_str(int status);
+
+// JZON_NULL for a NULL value too
+jzon_type jzon_type_of(const jzon *value);
+jzon_status jzon_get_bool(const jzon *value, bool *out);
+jzon_status jzon_get_int(const jzon *value, int64_t *out);
+// integers too, rounded where they have to be
+jzon_status jzon_get_double(const jzon *value, double *out);
+jzon_status jzon_get_string(const jzon *value, const char **out, size_t *len);
+
+// members of an object or elements of an array, 0 for anything else
+size_t jzon_len(const jzon *value);
+// These return NULL when there is nothing there. Pointers are JSON
+// Pointers, RFC 6901, like "/a/0/b", and "" is the value itself.
+const jzon *jzon_index(const jzon *array, size_t idx);
+const jzon *jzon_member(const jzon *object, const char *key);
+const jzon *jzon_pointer(const jzon *value, const char *pointer);
+
+// Walks the members of an object, in key order, or the elements of an
+// array; NULL for anything else. jzon_iter_next returns false once there
+// is nothing left. Keys are NULL for array elements, and any of the outputs
+// may be NULL if it isn't wanted.
+jzon_iter *jzon_iter_new(const jzon *value);
+bool jzon_iter_next(jzon_iter *iter, const char **key, size_t *key_len, const jzon **value);
+void jzon_iter_free(jzon_iter *iter);
+
+jzon *jzon_new_null(void);
+jzon *jzon_new_bool(bool v);
+jzon *jzon_new_int(int64_t v);
+// NULL for NaN and the infinities, which JSON has no form for
+jzon *jzon_new_double(double v);
+// NULL unless the bytes are UTF-8
+jzon *jzon_new_string(const char *s, size_t len);
+jzon *jzon_new_array(void);
+jzon *jzon_new_object(void);
+// These take `value` over when they return JZON_OK, and only then.
+// jzon_insert replaces any member with the same key.
+jzon_status jzon_push(jzon *array, jzon *value);
+jzon_status jzon_insert(jzon *object, const char *key, jzon *value);
+
+// Like snprintf: the text is written with a NUL after it when it fits in
+// `cap` bytes, and *len, if `len` isn't NULL, gets its length without the
+// NUL either way, so a NULL buffer of 0 bytes asks for the size. Numbers
+// too large for a double, like a parsed 1e400, are written as null.
+jzon_status jzon_write(const jzon *value, unsigned flags, char *buf, size_t cap, size_t *len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,654 @@
+use jzon::diff::{Path, Segment};
+use jzon::Jzon;
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::raw::c_ulong;
+use std::os::unix::io::AsRawFd;
+
+// A full screen tree view of one document, drawn with ANSI escapes on
+// /dev/tty so the document itself can come from standard input and the
+// chosen subtree can go to standard output.
+
+// larger than `struct termios` anywhere; it is only handed back to libc
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Termios([u64; 32]);
+
+#[repr(C)]
+#[derive(Default)]
+struct Winsize {
+    rows: u16,
+    cols: u16,
+    xpixel: u16,
+    ypixel: u16,
+}
+
+extern "C" {
+    fn tcgetattr(fd: i32, termios: *mut Termios) -> i32;
+    fn tcsetattr(fd: i32, actions: i32, termios: *const Termios) -> i32;
+    fn cfmakeraw(termios: *mut Termios);
+    fn ioctl(fd: i32, request: c_ulong, ...) -> i32;
+}
+
+const TCSAFLUSH: i32 = 2;
+#[cfg(target_os = "linux")]
+const TIOCGWINSZ: c_ulong = 0x5413;
+#[cfg(not(target_os = "linux"))]
+const TIOCGWINSZ: c_ulong = 0x4008_7468;
+
+// the alternate screen with the cursor hidden
+const ENTER: &str = "\x1b[?1049h\x1b[?25l";
+const LEAVE: &str = "\x1b[?25h\x1b[?1049l";
+const REVERSE: &str = "\x1b[7m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+const HELP: &str =
+    "arrows/hjkl move  enter fold  / search  n/N next  y copy  o print and quit  q quit";
+
+// raw mode until dropped, even when unwinding
+struct Terminal {
+    tty: File,
+    saved: Termios,
+}
+
+impl Terminal {
+    fn open() -> io::Result<Terminal> {
+        let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+        let mut saved = Termios([0; 32]);
+        if unsafe { tcgetattr(tty.as_raw_fd(), &mut saved) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut raw = saved;
+        unsafe { cfmakeraw(&mut raw) };
+        if unsafe { tcsetattr(tty.as_raw_fd(), TCSAFLUSH, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut term = Terminal { tty, saved };
+        term.tty.write_all(ENTER.as_bytes())?;
+        Ok(term)
+    }
+
+    // rows and columns
+    fn size(&self) -> (usize, usize) {
+        let mut size = Winsize::default();
+        match unsafe { ioctl(self.tty.as_raw_fd(), TIOCGWINSZ, &mut size) } {
+            0 if size.rows > 0 && size.cols > 0 => (size.rows as usize, size.cols as usize),
+            _ => (24, 80),
+        }
+    }
+
+    fn key(&mut self) -> io::Result<Key> {
+        let mut buf = [0; 16];
+        let n = self.tty.read(&mut buf)?;
+        Ok(Key::parse(&buf[..n]))
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = self.tty.write_all(LEAVE.as_bytes());
+        unsafe { tcsetattr(self.tty.as_raw_fd(), TCSAFLUSH, &self.saved) };
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Esc,
+    Backspace,
+    Char(char),
+    Unknown,
+}
+
+impl Key {
+    // one read, which holds a whole escape sequence as terminals send them
+    fn parse(bytes: &[u8]) -> Key {
+        match bytes {
+            [27, b'[', rest @ ..] | [27, b'O', rest @ ..] => match rest {
+                b"A" => Key::Up,
+                b"B" => Key::Down,
+                b"C" => Key::Right,
+                b"D" => Key::Left,
+                b"H" | b"1~" | b"7~" => Key::Home,
+                b"F" | b"4~" | b"8~" => Key::End,
+                b"5~" => Key::PageUp,
+                b"6~" => Key::PageDown,
+                _ => Key::Unknown,
+            },
+            [27] => Key::Esc,
+            [b'\r'] | [b'\n'] => Key::Enter,
+            [127] | [8] => Key::Backspace,
+            _ => match std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) => Key::Char(c),
+                None => Key::Unknown,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Label<'a> {
+    Root,
+    Key(&'a str),
+    Index(usize),
+}
+
+struct Row<'a> {
+    depth: usize,
+    label: Label<'a>,
+    node: &'a Jzon,
+    parent: Option<usize>,
+}
+
+// objects are shown with their keys in order, as a `Map` keeps them
+fn children(jz: &Jzon) -> Vec<(Label<'_>, &Jzon)> {
+    match jz {
+        Jzon::Object(map) => map.iter().map(|(k, v)| (Label::Key(k), v)).collect(),
+        Jzon::Array(vec) => vec
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (Label::Index(i), v))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn is_container(jz: &Jzon) -> bool {
+    matches!(jz, Jzon::Object(_) | Jzon::Array(_))
+}
+
+// search is case-insensitive and looks at keys and at scalars as written
+fn matches(label: Label<'_>, jz: &Jzon, query: &str) -> bool {
+    let found = |s: &str| s.to_lowercase().contains(query);
+    match (label, jz) {
+        (Label::Key(k), _) if found(k) => true,
+        (_, Jzon::String(s)) => found(s),
+        (_, jz) if !is_container(jz) => found(&jz.stringify()),
+        _ => false,
+    }
+}
+
+struct Explorer<'a> {
+    doc: &'a Jzon,
+    // by address, as the document never changes
+    expanded: HashSet<*const Jzon>,
+    rows: Vec<Row<'a>>,
+    selected: usize,
+    top: usize,
+    query: String,
+    // the query lowercased, for matching
+    lowered: String,
+    // where `/` was pressed, to search from and to go back to on escape
+    origin: Option<*const Jzon>,
+    message: String,
+}
+
+impl<'a> Explorer<'a> {
+    fn new(doc: &'a Jzon) -> Self {
+        let mut explorer = Explorer {
+            doc,
+            expanded: HashSet::new(),
+            rows: vec![],
+            selected: 0,
+            top: 0,
+            query: String::new(),
+            lowered: String::new(),
+            origin: None,
+            message: String::from(HELP),
+        };
+        explorer.expanded.insert(doc);
+        explorer.flatten();
+        explorer
+    }
+
+    fn node(&self) -> &'a Jzon {
+        self.rows[self.selected].node
+    }
+
+    // the visible rows again, keeping the selected node if it still shows
+    fn flatten(&mut self) {
+        let selected = self
+            .rows
+            .get(self.selected)
+            .map(|row| row.node as *const Jzon);
+        self.rows.clear();
+        self.visit(Label::Root, self.doc, 0, None);
+        self.selected = selected
+            .and_then(|node| self.row_of(node))
+            .unwrap_or(self.selected.min(self.rows.len() - 1));
+    }
+
+    fn visit(&mut self, label: Label<'a>, node: &'a Jzon, depth: usize, parent: Option<usize>) {
+        let row = self.rows.len();
+        self.rows.push(Row {
+            depth,
+            label,
+            node,
+            parent,
+        });
+        if self.expanded.contains(&(node as *const Jzon)) {
+            for (label, child) in children(node) {
+                self.visit(label, child, depth + 1, Some(row));
+            }
+        }
+    }
+
+    fn row_of(&self, node: *const Jzon) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| std::ptr::eq(row.node, node))
+    }
+
+    fn pointer(&self, row: usize) -> Path {
+        let mut segments = vec![];
+        let mut row = Some(row);
+        while let Some(r) = row {
+            match self.rows[r].label {
+                Label::Root => (),
+                Label::Key(k) => segments.push(Segment::Key(k.to_string())),
+                Label::Index(i) => segments.push(Segment::Index(i)),
+            }
+            row = self.rows[r].parent;
+        }
+        segments.reverse();
+        Path(segments)
+    }
+
+    fn toggle(&mut self) {
+        let node = self.node();
+        if is_container(node) && !self.expanded.remove(&(node as *const Jzon)) {
+            self.expanded.insert(node);
+        }
+        self.flatten();
+    }
+
+    fn right(&mut self) {
+        let node = self.node();
+        if !is_container(node) {
+            return;
+        }
+        if self.expanded.insert(node) {
+            self.flatten();
+        } else if self.selected + 1 < self.rows.len()
+            && self.rows[self.selected + 1].parent == Some(self.selected)
+        {
+            self.selected += 1;
+        }
+    }
+
+    fn left(&mut self) {
+        let node = self.node();
+        if self.expanded.remove(&(node as *const Jzon)) {
+            self.flatten();
+        } else if let Some(parent) = self.rows[self.selected].parent {
+            self.selected = parent;
+        }
+    }
+
+    fn step(&mut self, delta: isize) {
+        let last = self.rows.len() as isize - 1;
+        self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+    }
+
+    // The next match after `from` in the order the tree is shown, wrapping
+    // around, with the containers it is in before it. `from` itself counts
+    // when `inclusive`, so typing on keeps the current match.
+    fn find(&self, from: *const Jzon, forward: bool, inclusive: bool) -> Option<Vec<&'a Jzon>> {
+        let mut passed = false;
+        let (mut first_before, mut last_before) = (None, None);
+        let (mut first_after, mut last_after) = (None, None);
+        walk(Label::Root, self.doc, &mut vec![], &mut |label, node, _| {
+            if std::ptr::eq(node, from) {
+                passed = true;
+                if !inclusive {
+                    return true;
+                }
+            }
+            if !matches(label, node, &self.lowered) {
+                return true;
+            }
+            if !passed {
+                first_before = first_before.or(Some(node));
+                last_before = Some(node);
+                return true;
+            }
+            first_after = first_after.or(Some(node));
+            last_after = Some(node);
+            // going forward nothing after this one matters
+            !forward
+        });
+        let found = match forward {
+            true => first_after.or(first_before),
+            false => last_before.or(last_after),
+        }?;
+
+        let mut chain = None;
+        walk(Label::Root, self.doc, &mut vec![], &mut |_, node, path| {
+            if std::ptr::eq(node, found) {
+                chain = Some(path.to_vec());
+            }
+            chain.is_none()
+        });
+        chain
+    }
+
+    // shows and selects the end of `chain`
+    fn reveal(&mut self, chain: &[&'a Jzon]) {
+        let (node, above) = chain.split_last().unwrap();
+        for container in above {
+            self.expanded.insert(*container);
+        }
+        self.flatten();
+        self.selected = self.row_of(*node).unwrap();
+    }
+
+    fn search(&mut self, from: *const Jzon, forward: bool, inclusive: bool) {
+        if self.query.is_empty() {
+            return;
+        }
+        match self.find(from, forward, inclusive) {
+            Some(chain) => {
+                self.reveal(&chain);
+                self.message.clear();
+            }
+            None => self.message = format!("no match for {:?}", self.query),
+        }
+    }
+
+    // keys while searching edit the query
+    fn handle(&mut self, key: Key, page: usize, term: &mut impl Write) -> io::Result<Action> {
+        if let Some(origin) = self.origin {
+            match key {
+                Key::Enter => self.origin = None,
+                Key::Esc => {
+                    self.origin = None;
+                    self.query.clear();
+                    self.lowered.clear();
+                    self.flatten();
+                    self.selected = self.row_of(origin).unwrap_or(self.selected);
+                }
+                Key::Backspace => {
+                    self.query.pop();
+                    self.lowered = self.query.to_lowercase();
+                    self.search(origin, true, true);
+                }
+                Key::Char(c) if !c.is_control() => {
+                    self.query.push(c);
+                    self.lowered = self.query.to_lowercase();
+                    self.search(origin, true, true);
+                }
+                _ => (),
+            }
+            return Ok(Action::Continue);
+        }
+
+        self.message.clear();
+        match key {
+            Key::Char('q') | Key::Char('\u{3}') => return Ok(Action::Quit),
+            Key::Char('o') => return Ok(Action::Print(self.node().stringify())),
+            Key::Up | Key::Char('k') => self.step(-1),
+            Key::Down | Key::Char('j') => self.step(1),
+            Key::PageUp => self.step(-(page as isize)),
+            Key::PageDown | Key::Char(' ') => self.step(page as isize),
+            Key::Home | Key::Char('g') => self.selected = 0,
+            Key::End | Key::Char('G') => self.selected = self.rows.len() - 1,
+            Key::Right | Key::Char('l') => self.right(),
+            Key::Left | Key::Char('h') => self.left(),
+            Key::Enter => self.toggle(),
+            Key::Char('/') => {
+                self.origin = Some(self.node());
+                self.query.clear();
+                self.lowered.clear();
+            }
+            Key::Char('n') => self.search(self.node(), true, false),
+            Key::Char('N') => self.search(self.node(), false, false),
+            // OSC 52, which most terminals take as setting the clipboard
+            Key::Char('y') => {
+                let json = self.node().stringify();
+                write!(term, "\x1b]52;c;{}\x07", base64(json.as_bytes()))?;
+                self.message = format!("copied {} bytes", json.len());
+            }
+            Key::Char('?') => self.message = String::from(HELP),
+            _ => (),
+        }
+        Ok(Action::Continue)
+    }
+
+    fn line(&self, row: &Row<'_>, width: usize) -> String {
+        let mut line = "  ".repeat(row.depth);
+        let expanded = self.expanded.contains(&(row.node as *const Jzon));
+        line += match row.node {
+            _ if !is_container(row.node) => "  ",
+            _ if expanded => "▾ ",
+            _ => "▸ ",
+        };
+        match row.label {
+            Label::Root => (),
+            Label::Key(k) => line += &format!("{}: ", Jzon::String(k.to_string()).stringify()),
+            Label::Index(i) => line += &format!("[{}] ", i),
+        }
+        let (open, len) = match row.node {
+            Jzon::Object(map) => ("{", map.len()),
+            Jzon::Array(vec) => ("[", vec.len()),
+            jz => return clip(line + &jz.stringify(), width),
+        };
+        let summary = match expanded {
+            true => open.to_string(),
+            false => format!("{}…{} {}{}", open, DIM, len, RESET),
+        };
+        clip(line, width) + &summary
+    }
+
+    fn draw(&mut self, term: &mut impl Write, rows: usize, cols: usize) -> io::Result<()> {
+        let height = rows.saturating_sub(1).max(1);
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + height {
+            self.top = self.selected + 1 - height;
+        }
+
+        let mut screen = String::from("\x1b[H");
+        for i in self.top..self.top + height {
+            match self.rows.get(i) {
+                Some(row) if i == self.selected => {
+                    let line = self.line(row, cols);
+                    screen += &format!("{}{}{}", REVERSE, line.replace(RESET, ""), RESET);
+                }
+                Some(row) => screen += &self.line(row, cols),
+                None => (),
+            }
+            screen += "\x1b[K\r\n";
+        }
+        let status = match self.origin {
+            Some(_) => format!("/{}", self.query),
+            None => {
+                let pointer = self.pointer(self.selected).to_string();
+                let place = format!("{}/{}", self.selected + 1, self.rows.len());
+                let left = match pointer.as_str() {
+                    "" => format!("(root)  {}", self.message),
+                    _ => format!("{}  {}", pointer, self.message),
+                };
+                let gap = cols
+                    .saturating_sub(left.chars().count() + place.len())
+                    .max(1);
+                format!("{}{}{}", left, " ".repeat(gap), place)
+            }
+        };
+        screen += &format!("{}{}{}\x1b[K", REVERSE, clip(status, cols), RESET);
+        term.write_all(screen.as_bytes())?;
+        term.flush()
+    }
+}
+
+enum Action {
+    Continue,
+    Quit,
+    Print(String),
+}
+
+fn walk<'a>(
+    label: Label<'a>,
+    node: &'a Jzon,
+    chain: &mut Vec<&'a Jzon>,
+    visit: &mut dyn FnMut(Label<'a>, &'a Jzon, &[&'a Jzon]) -> bool,
+) -> bool {
+    chain.push(node);
+    if !visit(label, node, chain) {
+        return false;
+    }
+    for (label, child) in children(node) {
+        if !walk(label, child, chain, visit) {
+            return false;
+        }
+    }
+    chain.pop();
+    true
+}
+
+fn clip(s: String, width: usize) -> String {
+    match s.char_indices().nth(width) {
+        Some((i, _)) => s[..i].to_string(),
+        None => s,
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char),
+                false => out.push('='),
+            }
+        }
+    }
+    out
+}
+
+// Shows `doc` until the user quits, and returns the compact JSON of the
+// subtree they chose to print, if they did.
+pub fn run(doc: &Jzon) -> io::Result<Option<String>> {
+    let mut term = Terminal::open()?;
+    let mut explorer = Explorer::new(doc);
+    loop {
+        let (rows, cols) = term.size();
+        explorer.draw(&mut term.tty, rows, cols)?;
+        let key = term.key()?;
+        match explorer.handle(key, rows.saturating_sub(2).max(1), &mut term.tty)? {
+            Action::Continue => (),
+            Action::Quit => return Ok(None),
+            Action::Print(json) => return Ok(Some(json)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc() -> Jzon {
+        let text = br#"{"b": [1, {"name": "Needle"}], "a": {"x": null, "y": "needle"}, "c": 3}"#;
+        Jzon::parse(text).unwrap()
+    }
+
+    fn shown(explorer: &Explorer<'_>) -> Vec<String> {
+        (0..explorer.rows.len())
+            .map(|i| explorer.pointer(i).to_string())
+            .collect()
+    }
+
+    fn selected(explorer: &Explorer<'_>) -> String {
+        explorer.pointer(explorer.selected).to_string()
+    }
+
+    #[test]
+    fn navigate() {
+        let doc = doc();
+        let mut explorer = Explorer::new(&doc);
+        assert_eq!(["", "/a", "/b", "/c"], shown(&explorer)[..]);
+        explorer.step(2);
+        explorer.right();
+        assert_eq!(["", "/a", "/b", "/b/0", "/b/1", "/c"], shown(&explorer)[..]);
+        explorer.right();
+        assert_eq!("/b/0", selected(&explorer));
+        explorer.left();
+        assert_eq!("/b", selected(&explorer));
+        explorer.left();
+        assert_eq!(4, explorer.rows.len());
+        explorer.step(10);
+        assert_eq!("/c", selected(&explorer));
+
+        let mut sink = vec![];
+        match explorer.handle(Key::Char('o'), 10, &mut sink).unwrap() {
+            Action::Print(json) => assert_eq!("3", json),
+            _ => panic!(),
+        }
+        explorer.handle(Key::Char('y'), 10, &mut sink).unwrap();
+        assert_eq!(b"\x1b]52;c;Mw==\x07", &sink[..]);
+    }
+
+    #[test]
+    fn search() {
+        let doc = doc();
+        let mut explorer = Explorer::new(&doc);
+        let mut sink = vec![];
+        let mut keys = |explorer: &mut Explorer<'_>, keys: &str| {
+            for c in keys.chars() {
+                let key = match c {
+                    '\n' => Key::Enter,
+                    '\x1b' => Key::Esc,
+                    c => Key::Char(c),
+                };
+                explorer.handle(key, 10, &mut sink).unwrap();
+            }
+        };
+        // while searching "n" is typed, and "ne" first matches under "a",
+        // which comes first on screen
+        keys(&mut explorer, "/ne");
+        assert_eq!("/a/y", selected(&explorer));
+        keys(&mut explorer, "ed\nn");
+        assert_eq!("/b/1/name", selected(&explorer));
+        keys(&mut explorer, "n");
+        assert_eq!("/a/y", selected(&explorer));
+        keys(&mut explorer, "N");
+        assert_eq!("/b/1/name", selected(&explorer));
+
+        keys(&mut explorer, "/z");
+        assert!(explorer.message.starts_with("no match"));
+        keys(&mut explorer, "\x1b");
+        assert_eq!("/b/1/name", selected(&explorer));
+        // in any case, scalars as written
+        keys(&mut explorer, "/NuL\n");
+        assert_eq!("/a/x", selected(&explorer));
+        assert!(sink.is_empty());
+    }
+
+    #[test]
+    fn keys() {
+        assert_eq!(Key::Up, Key::parse(b"\x1b[A"));
+        assert_eq!(Key::PageDown, Key::parse(b"\x1b[6~"));
+        assert_eq!(Key::End, Key::parse(b"\x1bOF"));
+        assert_eq!(Key::Esc, Key::parse(b"\x1b"));
+        assert_eq!(Key::Char('é'), Key::parse("é".as_bytes()));
+        assert_eq!("", base64(b""));
+        assert_eq!("YWJjZA==", base64(b"abcd"));
+    }
+}
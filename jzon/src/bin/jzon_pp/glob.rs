@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Expands `*`, `?`, `[...]` and `**` the way a shell does, for patterns that
+// were quoted or come from shells without globbing. A pattern matching
+// nothing is kept as is, so opening it reports the missing file.
+pub fn expand(pattern: &str) -> Vec<PathBuf> {
+    if !has_magic(pattern) {
+        return vec![PathBuf::from(pattern)];
+    }
+
+    let parts: Vec<&str> = pattern.split('/').filter(|p| !p.is_empty()).collect();
+    let root = if pattern.starts_with('/') {
+        PathBuf::from("/")
+    } else {
+        PathBuf::new()
+    };
+    let mut found = vec![];
+    walk(&root, &parts, &mut found);
+    found.sort();
+    found.dedup();
+
+    if found.is_empty() {
+        vec![PathBuf::from(pattern)]
+    } else {
+        found
+    }
+}
+
+fn has_magic(s: &str) -> bool {
+    s.contains(['*', '?', '['])
+}
+
+fn walk(dir: &Path, parts: &[&str], found: &mut Vec<PathBuf>) {
+    let (part, rest) = match parts.split_first() {
+        Some(split) => split,
+        None => return found.push(dir.to_path_buf()),
+    };
+
+    if *part == "**" {
+        walk(dir, rest, found);
+        // as in shells, `**` doesn't follow links, which could loop
+        for (path, real_dir) in children(dir, false) {
+            if real_dir {
+                walk(&path, parts, found);
+            }
+        }
+    } else if !has_magic(part) {
+        let path = dir.join(part);
+        if rest.is_empty() && path.exists() || path.is_dir() {
+            walk(&path, rest, found);
+        }
+    } else {
+        for (path, _) in children(dir, part.starts_with('.')) {
+            let name = path.file_name().unwrap().to_string_lossy();
+            if matches(part, &name) && (rest.is_empty() || path.is_dir()) {
+                walk(&path, rest, found);
+            }
+        }
+    }
+}
+
+// Hidden entries only take part when the pattern asks for a leading dot.
+// Each comes with whether it is a directory itself rather than a link to one.
+fn children(dir: &Path, hidden: bool) -> Vec<(PathBuf, bool)> {
+    let read = if dir.as_os_str().is_empty() {
+        fs::read_dir(".")
+    } else {
+        fs::read_dir(dir)
+    };
+    let entries = match read {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| hidden || !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| {
+            let real_dir = e.file_type().is_ok_and(|t| t.is_dir());
+            (dir.join(e.file_name()), real_dir)
+        })
+        .collect()
+}
+
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    match_from(&pattern, &name)
+}
+
+fn match_from(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|i| match_from(rest, &name[i..])),
+        Some(('?', rest)) => !name.is_empty() && match_from(rest, &name[1..]),
+        Some(('[', rest)) => match (name.split_first(), class_end(rest)) {
+            (Some((ch, name)), Some(end)) => {
+                in_class(&rest[..end], *ch) && match_from(&rest[end + 1..], name)
+            }
+            // an unclosed bracket is an ordinary character
+            (Some((ch, name)), None) => *ch == '[' && match_from(rest, name),
+            (None, _) => false,
+        },
+        Some((p, rest)) => name.first() == Some(p) && match_from(rest, &name[1..]),
+    }
+}
+
+// index of the `]` closing a class; one right after `[`, `[!` or `[^` is a member
+fn class_end(class: &[char]) -> Option<usize> {
+    let start = match class.first() {
+        Some('!') | Some('^') => 2,
+        _ => 1,
+    };
+    class
+        .iter()
+        .skip(start)
+        .position(|c| *c == ']')
+        .map(|i| i + start)
+}
+
+fn in_class(class: &[char], ch: char) -> bool {
+    let (negate, set) = match class.first() {
+        Some('!') | Some('^') => (true, &class[1..]),
+        _ => (false, class),
+    };
+    let mut i = 0;
+    let mut found = false;
+    while i < set.len() && !found {
+        if i + 2 < set.len() && set[i + 1] == '-' {
+            found = set[i] <= ch && ch <= set[i + 2];
+            i += 3;
+        } else {
+            found = set[i] == ch;
+            i += 1;
+        }
+    }
+    found != negate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("*.json", "a.json"));
+        assert!(matches("*.json", ".json"));
+        assert!(!matches("*.json", "a.json5"));
+        assert!(matches("fail??.json", "fail01.json"));
+        assert!(!matches("fail??.json", "fail1.json"));
+        assert!(matches("[pr]*", "pass01.json"));
+        assert!(matches("[a-c][!a-c]", "bz"));
+        assert!(!matches("[a-c][!a-c]", "bb"));
+        assert!(matches("[]]", "]"));
+        assert!(matches("a[", "a["));
+    }
+
+    #[test]
+    fn expand_data() {
+        let found = expand("data/jsonchecker/pass0[12].json");
+        assert_eq!(
+            vec![
+                PathBuf::from("data/jsonchecker/pass01.json"),
+                PathBuf::from("data/jsonchecker/pass02.json")
+            ],
+            found
+        );
+        assert_eq!(3, expand("data/**/pass*.json").len());
+        assert_eq!(vec![PathBuf::from("nope/*.json")], expand("nope/*.json"));
+    }
+
+    #[test]
+    fn symlink_loop() {
+        let dir = std::env::temp_dir().join(format!("jzon_glob_{}", std::process::id()));
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::write(dir.join("a/x.json"), "1").unwrap();
+        std::os::unix::fs::symlink("..", dir.join("a/up")).unwrap();
+
+        let pattern = format!("{}/**/*.json", dir.display());
+        let found = expand(&pattern);
+        // a single star still goes through the link
+        let through = expand(&format!("{}/a/up/*/x.json", dir.display()));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(vec![dir.join("a/x.json")], found);
+        assert_eq!(vec![dir.join("a/up/a/x.json")], through);
+    }
+}
@@ -0,0 +1,604 @@
+extern crate jzon;
+use jzon::cbor::Encoding;
+use jzon::diff::{ArrayMatch, Change};
+use jzon::jzon::{GREEN, HIGHLIGHT, RED, RESET};
+use jzon::*;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+mod explore;
+mod glob;
+mod stats;
+
+// exit codes
+const OK: i32 = 0;
+const INVALID: i32 = 1;
+const TROUBLE: i32 = 2;
+
+extern "C" {
+    fn isatty(fd: i32) -> i32;
+}
+
+enum Input {
+    Stdin,
+    Text(String),
+    File(PathBuf),
+}
+
+enum Output {
+    Pretty,
+    Canonical,
+    Cbor(Encoding),
+}
+
+struct Options {
+    inputs: Vec<Input>,
+    output: Output,
+    from_cbor: bool,
+    compact: bool,
+    indent: usize,
+    sort_keys: bool,
+    escaping: Escaping,
+    check: bool,
+    explore: bool,
+    ndjson: bool,
+    // suffix of the backup kept when rewriting files in place
+    in_place: Option<String>,
+    theme: Option<Theme>,
+}
+
+fn main() {
+    let mut mode = String::from("auto");
+    let args: Vec<String> = env::args()
+        .skip(1)
+        .filter(|arg| match arg.strip_prefix("--color=") {
+            Some(m) => {
+                mode = m.to_string();
+                false
+            }
+            None => true,
+        })
+        .collect();
+    let theme = match use_color(&mode) {
+        Some(true) => Some(Theme::parse(&env::var("JZON_COLORS").unwrap_or_default())),
+        Some(false) => None,
+        None => usage(&format!("unknown color mode {:?}", mode)),
+    };
+
+    let status = match args.first().map(String::as_str) {
+        Some("diff") => diff(&args[1..], theme.is_some()),
+        Some("infer") => infer(args[1..].to_vec(), theme, None),
+        Some("stats") => stats(&args[1..], theme),
+        Some("rust") => {
+            let (root, args) = root_name(&args[1..]);
+            infer(args, theme, Some(root))
+        }
+        Some("-h") | Some("--help") => {
+            print_usage();
+            OK
+        }
+        _ => match parse_args(args, theme) {
+            Ok(opts) if opts.explore => explore(&opts),
+            Ok(opts) => run(&opts),
+            Err(msg) => usage(&msg),
+        },
+    };
+    process::exit(status);
+}
+
+fn parse_args(args: Vec<String>, theme: Option<Theme>) -> Result<Options, String> {
+    let mut opts = Options {
+        inputs: vec![],
+        output: Output::Pretty,
+        from_cbor: false,
+        compact: false,
+        indent: 2,
+        sort_keys: false,
+        escaping: Escaping::default(),
+        check: false,
+        explore: false,
+        ndjson: false,
+        in_place: None,
+        theme,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+        match arg.as_str() {
+            "-" => opts.inputs.push(Input::Stdin),
+            "-e" => opts.inputs.push(Input::Text(value("-e")?)),
+            "-f" => opts.inputs.push(Input::File(value("-f")?.into())),
+            "--compact" | "-c" => opts.compact = true,
+            "--indent" => {
+                let n = value("--indent")?;
+                opts.indent = n.parse().map_err(|_| format!("bad indent {:?}", n))?;
+            }
+            "--sort-keys" | "-S" => opts.sort_keys = true,
+            "--ascii" | "-a" => opts.escaping.ascii = true,
+            "--html-safe" => opts.escaping.html = true,
+            "--keep-slash" => opts.escaping.slash = false,
+            "--check" => opts.check = true,
+            "--explore" => opts.explore = true,
+            "--ndjson" => opts.ndjson = true,
+            "--in-place" | "-i" => opts.in_place = Some(String::from(".bak")),
+            "--canonical" => opts.output = Output::Canonical,
+            "--to-cbor" => opts.output = Output::Cbor(Encoding::Preferred),
+            "--to-cbor-canonical" => opts.output = Output::Cbor(Encoding::Canonical),
+            "--from-cbor" => opts.from_cbor = true,
+            _ if arg.starts_with("--in-place=") => {
+                opts.in_place = Some(arg["--in-place=".len()..].to_string())
+            }
+            _ if arg.starts_with("--indent=") => {
+                let n = &arg["--indent=".len()..];
+                opts.indent = n.parse().map_err(|_| format!("bad indent {:?}", n))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option {:?}", arg)),
+            _ => opts
+                .inputs
+                .extend(glob::expand(&arg).into_iter().map(Input::File)),
+        }
+    }
+
+    if opts.inputs.is_empty() {
+        opts.inputs.push(Input::Stdin);
+    }
+    if opts.in_place.is_some() {
+        if opts.inputs.iter().any(|i| !matches!(i, Input::File(_))) {
+            return Err(String::from("--in-place only rewrites files"));
+        }
+        if opts.in_place.as_deref() == Some("") {
+            return Err(String::from("--in-place needs a non-empty backup suffix"));
+        }
+        // the rewritten files should not contain escape sequences
+        opts.theme = None;
+    }
+    if opts.from_cbor && opts.ndjson {
+        return Err(String::from("--ndjson reads text, not CBOR"));
+    }
+    Ok(opts)
+}
+
+fn run(opts: &Options) -> i32 {
+    let mut status = OK;
+    let stdout = io::stdout();
+    for input in &opts.inputs {
+        let (name, bytes) = match read(input) {
+            Ok(read) => read,
+            Err((name, e)) => {
+                eprintln!("{}: {}", name, e);
+                status = TROUBLE;
+                continue;
+            }
+        };
+
+        let mut out = vec![];
+        let mut valid = true;
+        for (line, doc) in documents(opts, &bytes) {
+            let emitted = doc.and_then(|jz| match opts.check {
+                true => Ok(()),
+                false => emit(opts, &jz, &mut out),
+            });
+            if let Err(e) = emitted {
+                report(&name, line, &e);
+                valid = false;
+            }
+        }
+        if !valid {
+            status = status.max(INVALID);
+        }
+
+        let written = match (input, &opts.in_place) {
+            _ if opts.check => Ok(()),
+            // a file that failed to parse is left alone
+            (Input::File(path), Some(suffix)) if valid => {
+                let mut backup = path.clone().into_os_string();
+                backup.push(suffix);
+                fs::copy(path, &backup).and_then(|_| fs::write(path, &out))
+            }
+            (_, Some(_)) => Ok(()),
+            (_, None) => stdout.lock().write_all(&out),
+        };
+        if let Err(e) = written {
+            eprintln!("{}: {}", name, e);
+            status = TROUBLE;
+        }
+    }
+    status
+}
+
+// One document at a time in the terminal; NDJSON lines make an array.
+fn explore(opts: &Options) -> i32 {
+    if opts.inputs.len() != 1 || opts.check || opts.in_place.is_some() {
+        usage("--explore shows a single input");
+    }
+    let (name, bytes) = match read(&opts.inputs[0]) {
+        Ok(read) => read,
+        Err((name, e)) => {
+            eprintln!("{}: {}", name, e);
+            return TROUBLE;
+        }
+    };
+    let mut docs = vec![];
+    for (line, doc) in documents(opts, &bytes) {
+        match doc {
+            Ok(jz) => docs.push(jz),
+            Err(e) => {
+                report(&name, line, &e);
+                return INVALID;
+            }
+        }
+    }
+    let doc = match opts.ndjson {
+        true => Jzon::Array(docs),
+        false => docs.remove(0),
+    };
+    match explore::run(&doc) {
+        Ok(Some(json)) => match writeln!(io::stdout(), "{}", json) {
+            Ok(()) => OK,
+            Err(_) => TROUBLE,
+        },
+        Ok(None) => OK,
+        Err(e) => {
+            eprintln!("jzon_pp: /dev/tty: {}", e);
+            TROUBLE
+        }
+    }
+}
+
+// `--name <Root>` of the rust subcommand, and the other arguments
+fn root_name(args: &[String]) -> (String, Vec<String>) {
+    let mut root = String::from("Root");
+    let mut rest = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--name=") {
+            Some(name) => root = name.to_string(),
+            None if arg == "--name" => match args.next() {
+                Some(name) => root = name.clone(),
+                None => usage("--name needs a value"),
+            },
+            None => rest.push(arg.clone()),
+        }
+    }
+    (root, rest)
+}
+
+// Every document of every input goes into one schema, printed as is or as
+// Rust types named after `root`.
+fn infer(args: Vec<String>, theme: Option<Theme>, root: Option<String>) -> i32 {
+    let mut opts = match parse_args(args, theme) {
+        Ok(opts) if opts.check || opts.in_place.is_some() => usage("infer only prints the schema"),
+        Ok(opts) => opts,
+        Err(msg) => usage(&msg),
+    };
+
+    let mut status = OK;
+    let mut inferrer = Inferrer::new();
+    for input in &opts.inputs {
+        let (name, bytes) = match read(input) {
+            Ok(read) => read,
+            Err((name, e)) => {
+                eprintln!("{}: {}", name, e);
+                status = TROUBLE;
+                continue;
+            }
+        };
+        for (line, doc) in documents(&opts, &bytes) {
+            match doc {
+                Ok(jz) => inferrer.add(&jz),
+                Err(e) => {
+                    report(&name, line, &e);
+                    status = status.max(INVALID);
+                }
+            }
+        }
+    }
+
+    if let Some(root) = root {
+        let code = codegen::rust_types(&inferrer.schema(), &root);
+        if io::stdout().write_all(code.as_bytes()).is_err() {
+            return TROUBLE;
+        }
+        return status;
+    }
+
+    // the schema is a single document whatever the samples were
+    opts.ndjson = false;
+    opts.sort_keys = true;
+    let mut out = vec![];
+    if let Err(e) = emit(&opts, &inferrer.schema(), &mut out) {
+        eprintln!("jzon_pp: {}", e);
+        return TROUBLE;
+    }
+    if io::stdout().write_all(&out).is_err() {
+        return TROUBLE;
+    }
+    status
+}
+
+// A report on each input, all its documents together, as text or JSON.
+fn stats(args: &[String], theme: Option<Theme>) -> i32 {
+    let mut json = false;
+    let mut top = 10;
+    let mut rest = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let n = match arg.as_str() {
+            "--json" => {
+                json = true;
+                continue;
+            }
+            "--top" => args.next().map(String::as_str),
+            _ => match arg.strip_prefix("--top=") {
+                Some(n) => Some(n),
+                None => {
+                    rest.push(arg.clone());
+                    continue;
+                }
+            },
+        };
+        top = match n.map(str::parse) {
+            Some(Ok(n)) => n,
+            _ => usage("--top needs a number"),
+        };
+    }
+    let mut opts = match parse_args(rest, theme) {
+        Ok(opts) if opts.check || opts.in_place.is_some() => usage("stats only prints a report"),
+        Ok(opts) => opts,
+        Err(msg) => usage(&msg),
+    };
+    opts.sort_keys = true;
+
+    let mut status = OK;
+    let stdout = io::stdout();
+    for input in &opts.inputs {
+        let (name, bytes) = match read(input) {
+            Ok(read) => read,
+            Err((name, e)) => {
+                eprintln!("{}: {}", name, e);
+                status = TROUBLE;
+                continue;
+            }
+        };
+
+        let mut stats = stats::Stats::new(bytes.len(), top);
+        let start = Instant::now();
+        let docs = documents(&opts, &bytes);
+        stats.parse = start.elapsed();
+        for (line, doc) in docs {
+            match doc {
+                Ok(jz) => {
+                    let start = Instant::now();
+                    drop(jz.stringify());
+                    stats.serialize += start.elapsed();
+                    stats.add(&jz);
+                }
+                Err(e) => {
+                    report(&name, line, &e);
+                    status = status.max(INVALID);
+                }
+            }
+        }
+
+        // nothing to tell about input that was all invalid
+        if stats.is_empty() {
+            continue;
+        }
+        let mut out = vec![];
+        if json {
+            if let Err(e) = emit(&opts, &stats.to_jzon(&name), &mut out) {
+                eprintln!("jzon_pp: {}", e);
+                return TROUBLE;
+            }
+        } else {
+            out = stats.human(&name).into_bytes();
+        }
+        if stdout.lock().write_all(&out).is_err() {
+            return TROUBLE;
+        }
+    }
+    status
+}
+
+fn report(name: &str, line: Option<usize>, e: &str) {
+    match line {
+        Some(line) => eprintln!("{}:{}: {}", name, line, e),
+        None => eprintln!("{}: {}", name, e),
+    }
+}
+
+fn read(input: &Input) -> Result<(String, Vec<u8>), (String, io::Error)> {
+    match input {
+        Input::Stdin => {
+            let mut bytes = vec![];
+            match io::stdin().read_to_end(&mut bytes) {
+                Ok(_) => Ok((String::from("<stdin>"), bytes)),
+                Err(e) => Err((String::from("<stdin>"), e)),
+            }
+        }
+        Input::Text(text) => Ok((String::from("<text>"), text.clone().into_bytes())),
+        Input::File(path) => {
+            let name = path.display().to_string();
+            match fs::read(path) {
+                Ok(bytes) => Ok((name, bytes)),
+                Err(e) => Err((name, e)),
+            }
+        }
+    }
+}
+
+// each parsed document with its line number in NDJSON mode
+fn documents(opts: &Options, bytes: &[u8]) -> Vec<(Option<usize>, Result<Jzon, String>)> {
+    if opts.from_cbor {
+        return vec![(None, Jzon::from_cbor(bytes).map_err(|e| format!("{:?}", e)))];
+    }
+    if !opts.ndjson {
+        let doc = Jzon::parse_encoded(bytes, Bom::Accept);
+        return vec![(None, doc.map_err(|e| format!("{:?}", e)))];
+    }
+    bytes
+        .split(|b| *b == b'\n')
+        .enumerate()
+        .filter(|(_, line)| line.iter().any(|b| !b" \t\r".contains(b)))
+        .map(|(i, line)| {
+            let doc = Jzon::parse(line).map_err(|e| format!("{:?}", e));
+            (Some(i + 1), doc)
+        })
+        .collect()
+}
+
+fn emit(opts: &Options, jz: &Jzon, out: &mut Vec<u8>) -> Result<(), String> {
+    match opts.output {
+        Output::Cbor(encoding) => out.extend_from_slice(&jz.to_cbor_with(encoding)),
+        Output::Canonical => {
+            let text = jz.canonicalize().map_err(|e| format!("{:?}", e))?;
+            out.extend_from_slice(text.as_bytes());
+            out.push(b'\n');
+        }
+        Output::Pretty => {
+            let mut styled = jz
+                .styled()
+                .sort_keys(opts.sort_keys)
+                .escaping(opts.escaping);
+            if let Some(theme) = &opts.theme {
+                styled = styled.theme(theme);
+            }
+            // NDJSON keeps one document per line
+            if !opts.compact && !opts.ndjson {
+                styled = styled.indent(opts.indent);
+            }
+            // writing into a Vec never fails
+            styled.write_to(out).unwrap();
+            out.push(b'\n');
+        }
+    }
+    Ok(())
+}
+
+// `None` for an unknown mode; NO_COLOR only silences "auto"
+fn use_color(mode: &str) -> Option<bool> {
+    match mode {
+        "always" => Some(true),
+        "never" => Some(false),
+        "auto" => {
+            let no_color = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+            Some(!no_color && unsafe { isatty(1) } == 1)
+        }
+        _ => None,
+    }
+}
+
+// exits like diff(1): 0 if equal, 1 if different, 2 on trouble
+fn diff(args: &[String], color: bool) -> i32 {
+    let mut arrays = ArrayMatch::default();
+    let mut files = vec![];
+    for arg in args {
+        match arg.as_str() {
+            "--array=index" => arrays = ArrayMatch::Index,
+            "--array=lcs" => arrays = ArrayMatch::Lcs,
+            _ if arg.starts_with("--array=key:") => {
+                arrays = ArrayMatch::Key(arg["--array=key:".len()..].to_string())
+            }
+            _ => files.push(arg),
+        }
+    }
+    if files.len() != 2 {
+        usage("diff takes two files");
+    }
+
+    let mut docs = vec![];
+    for file in &files {
+        let parsed = fs::read(file)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| Jzon::parse(&bytes).map_err(|e| format!("{:?}", e)));
+        match parsed {
+            Ok(jz) => docs.push(jz),
+            Err(e) => {
+                eprintln!("{}: {}", file, e);
+                return TROUBLE;
+            }
+        }
+    }
+
+    let changes = docs[0].diff(&docs[1], &arrays);
+    if changes.is_empty() {
+        return OK;
+    }
+
+    let paint = |code: &'static str| if color { code } else { "" };
+    let mut report = String::new();
+    report += &format!("{}--- {}{}\n", paint(HIGHLIGHT), files[0], paint(RESET));
+    report += &format!("{}+++ {}{}\n", paint(HIGHLIGHT), files[1], paint(RESET));
+    for change in &changes {
+        let path = change.path();
+        let (old, new) = match change {
+            Change::Removed(_, old) => (Some(old), None),
+            Change::Added(_, new) => (None, Some(new)),
+            Change::Changed(_, old, new) => (Some(old), Some(new)),
+        };
+        if let Some(old) = old {
+            report += &format!("{}-{}: {}{}\n", paint(RED), path, old, paint(RESET));
+        }
+        if let Some(new) = new {
+            report += &format!("{}+{}: {}{}\n", paint(GREEN), path, new, paint(RESET));
+        }
+    }
+    if io::stdout().write_all(report.as_bytes()).is_err() {
+        return TROUBLE;
+    }
+    INVALID
+}
+
+fn usage(msg: &str) -> ! {
+    eprintln!("jzon_pp: {}", msg);
+    print_usage();
+    process::exit(TROUBLE);
+}
+
+fn print_usage() {
+    eprint!(
+        r#"usage: jzon_pp [options] [file | glob | - ...]
+       jzon_pp diff [--array=index | --array=lcs | --array=key:<name>] <a> <b>
+       jzon_pp infer [options] [file | glob | - ...]
+       jzon_pp rust [--name <Root>] [options] [file | glob | - ...]
+       jzon_pp stats [--json] [--top <n>] [options] [file | glob | - ...]
+
+Reads standard input when no file is given.
+
+  -e <text>             parse <text> itself
+  -f <file>             read <file>, same as naming it
+  -c, --compact         print each document on one line
+  --indent <n>          indent nested values by <n> spaces (default 2)
+  -S, --sort-keys       print object keys in order
+  -a, --ascii           escape everything outside ASCII
+  --html-safe           escape <, > and & so the output can go in a script element
+  --keep-slash          leave / unescaped
+  --check               only validate; exit 1 if anything is invalid
+  --explore             browse the document as a tree; o prints the selected
+                        subtree on the way out, y copies it to the clipboard
+  -i, --in-place[=suf]  rewrite the files, keeping a backup with suffix suf (default .bak)
+  --ndjson              every input line is a document, printed on one line
+  --canonical           print RFC 8785 canonical JSON
+  --to-cbor             print CBOR, --to-cbor-canonical for deterministic CBOR
+  --from-cbor           inputs are CBOR instead of JSON
+  --color=<when>        always, never or auto (default), which honours NO_COLOR
+
+infer prints a JSON Schema that all the documents read, NDJSON lines included,
+would validate against. rust prints Rust types for them instead, which convert
+to and from Jzon.
+
+stats reports the depth, node counts, keys and where they occur, string length
+and number histograms, the <n> largest subtrees (default 10) and the parse and
+serialize times of each input, or all of it as JSON with --json.
+
+Colors come from JZON_COLORS, e.g. "key=1;34:string=32:number=33:bool=35:null=36".
+Exits 0 on success, 1 on invalid input or differences, 2 on other trouble.
+"#
+    );
+}
@@ -0,0 +1,480 @@
+use jzon::diff::{Path, Segment};
+use jzon::Jzon;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap};
+use std::time::Duration;
+
+// What the documents of one input are made of, for getting to know a
+// payload before writing code against it.
+
+const TYPES: [&str; 7] = [
+    "object", "array", "string", "integer", "double", "bool", "null",
+];
+
+#[derive(Default)]
+pub struct Stats {
+    documents: usize,
+    bytes: usize,
+    pub parse: Duration,
+    pub serialize: Duration,
+    // nested objects and arrays, so a scalar document has depth 0
+    max_depth: usize,
+    // by `TYPES`
+    nodes: [usize; 7],
+    keys: HashMap<String, Key>,
+    // by the bit length of the length in chars, so 0, 1, 2..4, 4..8...
+    string_lengths: BTreeMap<u32, usize>,
+    // by the decimal exponent of numbers other than zero
+    magnitudes: BTreeMap<i32, usize>,
+    zeros: usize,
+    negatives: usize,
+    range: Option<(f64, f64)>,
+    // how many of the largest subtrees to keep
+    top: usize,
+    // the largest objects and arrays below the root so far, the one that
+    // ranks last on top
+    largest: BinaryHeap<Subtree>,
+}
+
+#[derive(PartialEq, Eq)]
+struct Subtree {
+    // serialized compactly
+    size: usize,
+    path: Path,
+}
+
+// the larger first, then by path
+fn rank(size: usize, path: &Path, other: &Subtree) -> Ordering {
+    other
+        .size
+        .cmp(&size)
+        .then_with(|| path.0.cmp(&other.path.0))
+}
+
+impl Ord for Subtree {
+    fn cmp(&self, other: &Self) -> Ordering {
+        rank(self.size, &self.path, other)
+    }
+}
+
+impl PartialOrd for Subtree {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Default)]
+struct Key {
+    count: usize,
+    // where the objects with the key are, every index written as `*`
+    paths: BTreeSet<String>,
+}
+
+impl Stats {
+    pub fn new(bytes: usize, top: usize) -> Self {
+        Stats {
+            bytes,
+            top,
+            ..Stats::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents == 0
+    }
+
+    pub fn add(&mut self, jz: &Jzon) {
+        self.documents += 1;
+        self.walk(jz, &mut Path::default());
+    }
+
+    // the size `jz` takes when serialized compactly
+    fn walk(&mut self, jz: &Jzon, path: &mut Path) -> usize {
+        let size = match jz {
+            Jzon::Object(map) => {
+                self.nodes[0] += 1;
+                let parent = generalized(path);
+                let mut size = 2 + map.len().saturating_sub(1);
+                for (k, v) in map {
+                    let key = self.keys.entry(k.clone()).or_default();
+                    key.count += 1;
+                    if !key.paths.contains(&parent) {
+                        key.paths.insert(parent.clone());
+                    }
+                    path.0.push(Segment::Key(k.clone()));
+                    size += quoted_len(k) + 1 + self.walk(v, path);
+                    path.0.pop();
+                }
+                size
+            }
+            Jzon::Array(vec) => {
+                self.nodes[1] += 1;
+                let mut size = 2 + vec.len().saturating_sub(1);
+                for (i, v) in vec.iter().enumerate() {
+                    path.0.push(Segment::Index(i));
+                    size += self.walk(v, path);
+                    path.0.pop();
+                }
+                size
+            }
+            Jzon::String(s) => {
+                self.nodes[2] += 1;
+                let len = s.chars().count();
+                *self.string_lengths.entry(bit_len(len)).or_default() += 1;
+                quoted_len(s)
+            }
+            Jzon::Integer(v) => {
+                self.nodes[3] += 1;
+                self.number(*v as f64);
+                v.to_string().len()
+            }
+            Jzon::Double(v) => {
+                self.nodes[4] += 1;
+                self.number(*v);
+                jz.stringify().len()
+            }
+            Jzon::Bool(v) => {
+                self.nodes[5] += 1;
+                if *v {
+                    4
+                } else {
+                    5
+                }
+            }
+            Jzon::Null | Jzon::Error => {
+                self.nodes[6] += 1;
+                4
+            }
+        };
+        if let Jzon::Object(_) | Jzon::Array(_) = jz {
+            self.max_depth = self.max_depth.max(path.0.len() + 1);
+            if !path.0.is_empty() {
+                self.keep(size, path);
+            }
+        }
+        size
+    }
+
+    // the path is only cloned for subtrees that make it into the top ones
+    fn keep(&mut self, size: usize, path: &Path) {
+        if self.largest.len() == self.top {
+            match self.largest.peek() {
+                Some(last) if rank(size, path, last) == Ordering::Less => {
+                    self.largest.pop();
+                }
+                _ => return,
+            }
+        }
+        self.largest.push(Subtree {
+            size,
+            path: path.clone(),
+        });
+    }
+
+    fn number(&mut self, v: f64) {
+        if v < 0.0 {
+            self.negatives += 1;
+        }
+        match v {
+            _ if v == 0.0 => self.zeros += 1,
+            _ => {
+                let exp = v.abs().log10().floor() as i32;
+                *self.magnitudes.entry(exp).or_default() += 1;
+            }
+        }
+        self.range = match self.range {
+            Some((min, max)) => Some((min.min(v), max.max(v))),
+            None => Some((v, v)),
+        };
+    }
+
+    // most frequent first, then by name
+    fn ranked_keys(&self) -> Vec<(&String, &Key)> {
+        let mut keys: Vec<_> = self.keys.iter().collect();
+        keys.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(a.0.cmp(b.0)));
+        keys
+    }
+
+    fn largest(&self) -> Vec<&Subtree> {
+        let mut largest: Vec<_> = self.largest.iter().collect();
+        largest.sort();
+        largest
+    }
+
+    pub fn human(&self, name: &str) -> String {
+        let mut out = format!(
+            "{}: {} document{}, {}\n",
+            name,
+            self.documents,
+            if self.documents == 1 { "" } else { "s" },
+            size_str(self.bytes)
+        );
+        out += &format!(
+            "  parse {:.3?}, serialize {:.3?}\n",
+            self.parse, self.serialize
+        );
+        out += &format!("  max depth {}\n", self.max_depth);
+
+        out += "\nnodes\n";
+        for (name, count) in TYPES.iter().zip(self.nodes) {
+            out += &format!("  {:<10} {:>9}\n", name, count);
+        }
+
+        let keys = self.ranked_keys();
+        out += &format!("\nkeys, {} of {}\n", keys.len().min(self.top), keys.len());
+        for (k, key) in keys.iter().take(self.top) {
+            let mut paths: Vec<_> = key.paths.iter().map(|p| shown(p)).take(3).collect();
+            if key.paths.len() > 3 {
+                paths.push(format!("{} more", key.paths.len() - 3));
+            }
+            out += &format!("  {:<20} {:>9}  {}\n", k, key.count, paths.join(", "));
+        }
+
+        out += "\nstring lengths\n";
+        for (bits, count) in &self.string_lengths {
+            let range = match bits {
+                0 => String::from("0"),
+                _ => format!("{}..{}", 1usize << (bits - 1), 1usize << bits),
+            };
+            out += &format!("  {:<20} {:>9}\n", range, count);
+        }
+
+        match self.range {
+            Some((min, max)) => {
+                out += &format!(
+                    "\nnumbers from {} to {}, {} negative\n",
+                    min, max, self.negatives
+                )
+            }
+            None => out += "\nnumbers\n",
+        }
+        if self.zeros > 0 {
+            out += &format!("  {:<20} {:>9}\n", "0", self.zeros);
+        }
+        for (exp, count) in &self.magnitudes {
+            let range = format!("1e{}..1e{}", exp, exp + 1);
+            out += &format!("  |x| {:<16} {:>9}\n", range, count);
+        }
+
+        out += "\nlargest subtrees\n";
+        for subtree in self.largest() {
+            out += &format!("  {:>9}  {}\n", size_str(subtree.size), subtree.path);
+        }
+        out
+    }
+
+    // keys are all there, only the largest subtrees are cut to the top ones
+    pub fn to_jzon(&self, name: &str) -> Jzon {
+        let int = |n: usize| Jzon::Integer(n as i64);
+        let mut doc = HashMap::new();
+        doc.insert("input", Jzon::String(name.to_string()));
+        doc.insert("documents", int(self.documents));
+        doc.insert("bytes", int(self.bytes));
+        doc.insert("parse_ms", Jzon::Double(self.parse.as_secs_f64() * 1e3));
+        doc.insert(
+            "serialize_ms",
+            Jzon::Double(self.serialize.as_secs_f64() * 1e3),
+        );
+        doc.insert("max_depth", int(self.max_depth));
+        let nodes = TYPES.iter().zip(self.nodes);
+        doc.insert("nodes", object(nodes.map(|(t, n)| (*t, int(n))).collect()));
+
+        let keys = self.ranked_keys().into_iter().map(|(k, key)| {
+            let paths = key.paths.iter().map(|p| Jzon::String(p.clone()));
+            object(vec![
+                ("key", Jzon::String(k.clone())),
+                ("count", int(key.count)),
+                ("paths", Jzon::Array(paths.collect())),
+            ])
+        });
+        doc.insert("keys", Jzon::Array(keys.collect()));
+
+        let lengths = self.string_lengths.iter().map(|(bits, count)| {
+            let (min, max) = match bits {
+                0 => (0, 0),
+                _ => (1 << (bits - 1), (1 << bits) - 1),
+            };
+            object(vec![
+                ("min", int(min)),
+                ("max", int(max)),
+                ("count", int(*count)),
+            ])
+        });
+        doc.insert("string_lengths", Jzon::Array(lengths.collect()));
+
+        let magnitudes = self.magnitudes.iter().map(|(exp, count)| {
+            object(vec![
+                ("exponent", Jzon::Integer(*exp as i64)),
+                ("count", int(*count)),
+            ])
+        });
+        let (min, max) = match self.range {
+            Some((min, max)) => (Jzon::Double(min), Jzon::Double(max)),
+            None => (Jzon::Null, Jzon::Null),
+        };
+        doc.insert(
+            "numbers",
+            object(vec![
+                ("min", min),
+                ("max", max),
+                ("negative", int(self.negatives)),
+                ("zero", int(self.zeros)),
+                ("magnitudes", Jzon::Array(magnitudes.collect())),
+            ]),
+        );
+
+        let largest = self.largest().into_iter().map(|subtree| {
+            object(vec![
+                ("path", Jzon::String(subtree.path.to_string())),
+                ("bytes", int(subtree.size)),
+            ])
+        });
+        doc.insert("largest", Jzon::Array(largest.collect()));
+        object(doc.into_iter().collect())
+    }
+}
+
+fn object(members: Vec<(&str, Jzon)>) -> Jzon {
+    Jzon::Object(
+        members
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+// a JSON Pointer with `*` for every index
+fn generalized(path: &Path) -> String {
+    let generalized = path.0.iter().map(|seg| match seg {
+        Segment::Index(_) => Segment::Key(String::from("*")),
+        seg => seg.clone(),
+    });
+    Path(generalized.collect()).to_string()
+}
+
+// the root pointer is empty, which reads badly in a table
+fn shown(pointer: &str) -> String {
+    match pointer {
+        "" => String::from("(root)"),
+        _ => pointer.to_string(),
+    }
+}
+
+fn quoted_len(s: &str) -> usize {
+    Jzon::String(s.to_string()).stringify().len()
+}
+
+fn bit_len(n: usize) -> u32 {
+    usize::BITS - n.leading_zeros()
+}
+
+fn size_str(len: usize) -> String {
+    const UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+    let mut size = len as f64;
+    let mut e = 0;
+    while size > 1024.0 {
+        size /= 1024.0;
+        e += 1;
+    }
+    format!("{:.1}{}", size, UNITS[e])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"{"name": "a", "tags": ["x", "yz"], "ok": true, "none": null,
+        "items": [{"id": 1, "price": -2.5}, {"id": 0, "price": 1000}]}"#;
+
+    fn sample(top: usize) -> Stats {
+        let mut stats = Stats::new(100, top);
+        stats.add(&Jzon::parse(DOC.as_bytes()).unwrap());
+        stats
+    }
+
+    #[test]
+    fn counts() {
+        let stats = sample(2);
+        assert_eq!(1, stats.documents);
+        assert_eq!(3, stats.max_depth);
+        assert_eq!([3, 2, 3, 3, 1, 1, 1], stats.nodes);
+
+        let keys: Vec<_> = stats
+            .ranked_keys()
+            .into_iter()
+            .map(|(k, key)| (k.as_str(), key.count, key.paths.iter().cloned().collect()))
+            .collect();
+        let paths = |p: &str| vec![p.to_string()];
+        assert_eq!(
+            vec![
+                ("id", 2, paths("/items/*")),
+                ("price", 2, paths("/items/*")),
+                ("items", 1, paths("")),
+                ("name", 1, paths("")),
+                ("none", 1, paths("")),
+                ("ok", 1, paths("")),
+                ("tags", 1, paths("")),
+            ],
+            keys
+        );
+
+        // "a" and "x" are 1 char, "yz" 2..4
+        let lengths: Vec<_> = stats.string_lengths.into_iter().collect();
+        assert_eq!(vec![(1, 2), (2, 1)], lengths);
+
+        assert_eq!(Some((-2.5, 1000.0)), stats.range);
+        assert_eq!(1, stats.zeros);
+        assert_eq!(1, stats.negatives);
+        let magnitudes: Vec<_> = stats.magnitudes.into_iter().collect();
+        assert_eq!(vec![(0, 2), (3, 1)], magnitudes);
+    }
+
+    #[test]
+    fn largest() {
+        // both items are 21 bytes, the first path wins the tie
+        let stats = sample(2);
+        let largest: Vec<_> = stats
+            .largest()
+            .iter()
+            .map(|s| (s.size, s.path.to_string()))
+            .collect();
+        assert_eq!(
+            vec![(45, String::from("/items")), (21, String::from("/items/0"))],
+            largest
+        );
+
+        let largest: Vec<_> = sample(10).largest().iter().map(|s| s.size).collect();
+        assert_eq!(vec![45, 21, 21, 10], largest);
+        assert!(sample(0).largest().is_empty());
+    }
+
+    #[test]
+    fn to_jzon() {
+        let expected = Jzon::parse(
+            br#"{
+                "input": "doc.json", "documents": 1, "bytes": 100,
+                "parse_ms": 0.0, "serialize_ms": 0.0, "max_depth": 3,
+                "nodes": {"object": 3, "array": 2, "string": 3, "integer": 3,
+                    "double": 1, "bool": 1, "null": 1},
+                "keys": [
+                    {"key": "id", "count": 2, "paths": ["/items/*"]},
+                    {"key": "price", "count": 2, "paths": ["/items/*"]},
+                    {"key": "items", "count": 1, "paths": [""]},
+                    {"key": "name", "count": 1, "paths": [""]},
+                    {"key": "none", "count": 1, "paths": [""]},
+                    {"key": "ok", "count": 1, "paths": [""]},
+                    {"key": "tags", "count": 1, "paths": [""]}
+                ],
+                "string_lengths": [
+                    {"min": 1, "max": 1, "count": 2},
+                    {"min": 2, "max": 3, "count": 1}
+                ],
+                "numbers": {"min": -2.5, "max": 1000.0, "negative": 1, "zero": 1,
+                    "magnitudes": [{"exponent": 0, "count": 2}, {"exponent": 3, "count": 1}]},
+                "largest": [{"path": "/items", "bytes": 45}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(expected, sample(1).to_jzon("doc.json"));
+    }
+}
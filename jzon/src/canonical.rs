@@ -0,0 +1,188 @@
+use crate::jzon::{self, Jzon};
+use std::fmt::Write;
+use std::result;
+
+// JSON Canonicalization Scheme, RFC 8785.
+
+#[derive(Debug)]
+pub enum CanonicalErr {
+    // NaN and the infinities have no JSON representation
+    ExpectFinite,
+    // a document with parse errors has nothing to sign
+    ExpectNoneError,
+}
+
+type Result = result::Result<String, CanonicalErr>;
+
+impl Jzon {
+    // Integers are serialized as the doubles they round to, as I-JSON
+    // numbers are, so values beyond 2^53 may change.
+    pub fn canonicalize(&self) -> Result {
+        let mut out = String::new();
+        write_value(&mut out, self)?;
+        Ok(out)
+    }
+}
+
+fn write_value(out: &mut String, jz: &Jzon) -> result::Result<(), CanonicalErr> {
+    match jz {
+        Jzon::Null => out.push_str("null"),
+        Jzon::Error => return Err(CanonicalErr::ExpectNoneError),
+        Jzon::Bool(true) => out.push_str("true"),
+        Jzon::Bool(false) => out.push_str("false"),
+        Jzon::Integer(v) => write_number(out, *v as f64)?,
+        Jzon::Double(v) => write_number(out, *v)?,
+        Jzon::String(s) => write_string(out, s),
+        Jzon::Array(vec) => {
+            out.push('[');
+            for (i, v) in vec.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, v)?;
+            }
+            out.push(']');
+        }
+        Jzon::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            // keys compare as arrays of UTF-16 code units (§3.2.3)
+            entries.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16()));
+            out.push('{');
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, k);
+                out.push(':');
+                write_value(out, v)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+// only '"', '\\' and the control characters are escaped (§3.2.2.2)
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{00}'..='\u{1F}' => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            _ => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+// Number::toString of ECMA-262 §7.1.12.1, which JCS adopts (§3.2.2.3)
+fn write_number(out: &mut String, v: f64) -> result::Result<(), CanonicalErr> {
+    if !v.is_finite() {
+        return Err(CanonicalErr::ExpectFinite);
+    }
+    // writing into a String never fails
+    let _ = jzon::write_number(out, v);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 8785 Appendix B
+    #[test]
+    fn numbers() {
+        let cases = [
+            (0x0000000000000000u64, "0"),
+            (0x8000000000000000, "0"),
+            (0x0000000000000001, "5e-324"),
+            (0x8000000000000001, "-5e-324"),
+            (0x7fefffffffffffff, "1.7976931348623157e+308"),
+            (0xffefffffffffffff, "-1.7976931348623157e+308"),
+            (0x4340000000000000, "9007199254740992"),
+            (0xc340000000000000, "-9007199254740992"),
+            (0x4430000000000000, "295147905179352830000"),
+            (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+            (0x44b52d02c7e14af6, "1e+23"),
+            (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+            (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+            (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+            (0x444b1ae4d6e2ef50, "1e+21"),
+            (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+            (0x3eb0c6f7a0b5ed8d, "0.000001"),
+            (0x41b3de4355555553, "333333333.3333332"),
+            (0x41b3de4355555554, "333333333.33333325"),
+            (0x41b3de4355555555, "333333333.3333333"),
+            (0x41b3de4355555556, "333333333.3333334"),
+            (0x41b3de4355555557, "333333333.33333343"),
+            (0xbecbf647612f3696, "-0.0000033333333333333333"),
+            (0x43143ff3c1cb0959, "1424953923781206.2"),
+        ];
+        for (bits, expected) in cases.iter() {
+            let jz = Jzon::Double(f64::from_bits(*bits));
+            assert_eq!(*expected, jz.canonicalize().unwrap(), "{:016x}", bits);
+        }
+
+        for bits in [0x7fffffffffffffffu64, 0x7ff0000000000000].iter() {
+            assert!(Jzon::Double(f64::from_bits(*bits)).canonicalize().is_err());
+        }
+    }
+
+    // RFC 8785 §3.2.2
+    #[test]
+    fn serialization() {
+        let json = r#"{
+            "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+            "literals": [null, true, false]
+        }"#;
+        let jz = Jzon::parse(json.as_bytes()).unwrap();
+        assert_eq!(
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#,
+            jz.canonicalize().unwrap()
+        );
+    }
+
+    // RFC 8785 §3.2.3
+    #[test]
+    fn sorting() {
+        let json = r#"{
+            "€": "Euro Sign",
+            "\r": "Carriage Return",
+            "\ufb33": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "😀": "Emoji: Grinning Face",
+            "\u0080": "Control",
+            "ö": "Latin Small Letter O With Diaeresis"
+        }"#;
+        let jz = Jzon::parse(json.as_bytes()).unwrap();
+        let canonical = jz.canonicalize().unwrap();
+        let order: Vec<_> = [
+            "Carriage Return",
+            "One",
+            "Control",
+            "Latin Small Letter O With Diaeresis",
+            "Euro Sign",
+            "Emoji: Grinning Face",
+            "Hebrew Letter Dalet With Dagesh",
+        ]
+        .iter()
+        .map(|v| canonical.find(v).unwrap())
+        .collect();
+        assert!(order.windows(2).all(|w| w[0] < w[1]), "{}", canonical);
+    }
+
+    #[test]
+    fn integers() {
+        let jz = Jzon::parse(b"[0, -142, 9007199254740993]").unwrap();
+        assert_eq!("[0,-142,9007199254740992]", jz.canonicalize().unwrap());
+    }
+}
@@ -0,0 +1,698 @@
+use crate::base64;
+use crate::jzon::Jzon;
+use std::convert::TryFrom;
+use std::result;
+
+// Concise Binary Object Representation, RFC 8949.
+
+#[derive(Debug)]
+pub enum CborErr {
+    ExpectUtf8,
+    ExpectChunk,
+    ExpectNoMore,
+    ExpectNoneEOF,
+    ExpectTagContent,
+    ExpectKnownSimple,
+    ExpectDefiniteInfo,
+    UnexpectedBreak,
+    UnexpectedDepth,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Encoding {
+    // definite lengths, shortest heads and floats (§4.1)
+    #[default]
+    Preferred,
+    // arrays, maps and strings use indefinite lengths (§3.2)
+    Indefinite,
+    // preferred serialization with map keys sorted bytewise (§4.2.1)
+    Canonical,
+}
+
+type Result = result::Result<Jzon, CborErr>;
+
+use CborErr::*;
+
+const UINT: u8 = 0;
+const NINT: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+
+const INDEFINITE: u8 = 31;
+const BREAK: u8 = 0xFF;
+
+const FALSE: u8 = 0xF4;
+const TRUE: u8 = 0xF5;
+const NULL: u8 = 0xF6;
+const UNDEFINED: u8 = 0xF7;
+const HALF: u8 = 0xF9;
+const SINGLE: u8 = 0xFA;
+const DOUBLE: u8 = 0xFB;
+
+const TAG_POS_BIGNUM: u64 = 2;
+const TAG_NEG_BIGNUM: u64 = 3;
+const TAG_DECIMAL: u64 = 4;
+const TAG_BIGFLOAT: u64 = 5;
+const TAG_BASE64URL: u64 = 21;
+const TAG_BASE64: u64 = 22;
+const TAG_BASE16: u64 = 23;
+
+// arrays, maps and tags nested deeper than this are refused rather than
+// decoded by recursing until the stack runs out
+const MAX_DEPTH: usize = 128;
+
+impl Jzon {
+    pub fn to_cbor(&self) -> Vec<u8> {
+        self.to_cbor_with(Encoding::Preferred)
+    }
+
+    pub fn to_cbor_with(&self, encoding: Encoding) -> Vec<u8> {
+        let mut out = vec![];
+        encode(self, encoding, &mut out);
+        out
+    }
+
+    // Byte strings become base64url text and non-text map keys their JSON
+    // text, following the CBOR-to-JSON advice of RFC 8949 §6.1. Bignums
+    // that do not fit an i64 decode as doubles.
+    pub fn from_cbor(bytes: &[u8]) -> Result {
+        let mut de = Decoder {
+            bytes,
+            pos: 0,
+            depth: 0,
+        };
+        let value = de.value()?;
+        if de.pos == bytes.len() {
+            Ok(value)
+        } else {
+            Err(ExpectNoMore)
+        }
+    }
+}
+
+fn encode(jz: &Jzon, encoding: Encoding, out: &mut Vec<u8>) {
+    let indefinite = encoding == Encoding::Indefinite;
+    match jz {
+        Jzon::Null => out.push(NULL),
+        Jzon::Error => out.push(UNDEFINED),
+        Jzon::Bool(false) => out.push(FALSE),
+        Jzon::Bool(true) => out.push(TRUE),
+        Jzon::Integer(v) if *v >= 0 => write_head(out, UINT, *v as u64),
+        Jzon::Integer(v) => write_head(out, NINT, !*v as u64),
+        Jzon::Double(v) => write_float(out, *v),
+        Jzon::String(s) => write_text(out, s, indefinite),
+        Jzon::Array(vec) => {
+            write_len(out, ARRAY, vec.len(), indefinite);
+            for v in vec {
+                encode(v, encoding, out);
+            }
+            if indefinite {
+                out.push(BREAK);
+            }
+        }
+        Jzon::Object(map) if encoding == Encoding::Canonical => {
+            let mut entries: Vec<(Vec<u8>, &Jzon)> = map
+                .iter()
+                .map(|(k, v)| {
+                    let mut key = vec![];
+                    write_text(&mut key, k, false);
+                    (key, v)
+                })
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            write_head(out, MAP, entries.len() as u64);
+            for (k, v) in entries {
+                out.extend_from_slice(&k);
+                encode(v, encoding, out);
+            }
+        }
+        Jzon::Object(map) => {
+            write_len(out, MAP, map.len(), indefinite);
+            for (k, v) in map {
+                write_text(out, k, indefinite);
+                encode(v, encoding, out);
+            }
+            if indefinite {
+                out.push(BREAK);
+            }
+        }
+    }
+}
+
+fn write_head(out: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    if n < 24 {
+        out.push(major | n as u8);
+    } else if n <= 0xFF {
+        out.push(major | 24);
+        out.push(n as u8);
+    } else if n <= 0xFFFF {
+        out.push(major | 25);
+        out.extend_from_slice(&(n as u16).to_be_bytes());
+    } else if n <= 0xFFFF_FFFF {
+        out.push(major | 26);
+        out.extend_from_slice(&(n as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+fn write_len(out: &mut Vec<u8>, major: u8, len: usize, indefinite: bool) {
+    if indefinite {
+        out.push(major << 5 | INDEFINITE);
+    } else {
+        write_head(out, major, len as u64);
+    }
+}
+
+fn write_text(out: &mut Vec<u8>, s: &str, indefinite: bool) {
+    if indefinite {
+        out.push(TEXT << 5 | INDEFINITE);
+        if !s.is_empty() {
+            write_head(out, TEXT, s.len() as u64);
+            out.extend_from_slice(s.as_bytes());
+        }
+        out.push(BREAK);
+    } else {
+        write_head(out, TEXT, s.len() as u64);
+        out.extend_from_slice(s.as_bytes());
+    }
+}
+
+// the shortest of half, single and double precision that keeps the value
+fn write_float(out: &mut Vec<u8>, v: f64) {
+    if v.is_nan() {
+        out.extend_from_slice(&[HALF, 0x7E, 0x00]);
+        return;
+    }
+    let single = v as f32;
+    if single as f64 != v {
+        out.push(DOUBLE);
+        out.extend_from_slice(&v.to_bits().to_be_bytes());
+    } else if let Some(half) = f32_to_f16(single) {
+        out.push(HALF);
+        out.extend_from_slice(&half.to_be_bytes());
+    } else {
+        out.push(SINGLE);
+        out.extend_from_slice(&single.to_bits().to_be_bytes());
+    }
+}
+
+// `None` if `v` has no exact binary16 representation
+fn f32_to_f16(v: f32) -> Option<u16> {
+    let bits = v.to_bits();
+    let sign = (bits >> 16) as u16 & 0x8000;
+    let exp = (bits >> 23 & 0xFF) as i32;
+    let mant = bits & 0x7F_FFFF;
+
+    match exp {
+        0xFF if mant == 0 => return Some(sign | 0x7C00),
+        0xFF => return None,
+        0 if mant == 0 => return Some(sign),
+        // binary32 subnormals are far below the binary16 range
+        0 => return None,
+        _ => (),
+    }
+
+    let e = exp - 127;
+    if !(-24..=15).contains(&e) {
+        None
+    } else if e >= -14 {
+        if mant & 0x1FFF != 0 {
+            return None;
+        }
+        Some(sign | ((e + 15) as u16) << 10 | (mant >> 13) as u16)
+    } else {
+        // subnormal: value = m * 2^-24 with the implicit bit made explicit
+        let full = mant | 0x80_0000;
+        let shift = -(e + 1);
+        if full & ((1 << shift) - 1) != 0 {
+            return None;
+        }
+        Some(sign | (full >> shift) as u16)
+    }
+}
+
+// RFC 8949 Appendix D
+fn f16_to_f64(half: u16) -> f64 {
+    let exp = (half >> 10) & 0x1F;
+    let mant = (half & 0x3FF) as f64;
+    let v = match exp {
+        0 => mant * 2f64.powi(-24),
+        31 if mant == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (mant + 1024.0) * 2f64.powi(exp as i32 - 25),
+    };
+    if half & 0x8000 != 0 {
+        -v
+    } else {
+        v
+    }
+}
+
+fn uint(n: u64) -> Jzon {
+    match i64::try_from(n) {
+        Ok(v) => Jzon::Integer(v),
+        Err(_) => Jzon::Double(n as f64),
+    }
+}
+
+fn nint(n: u64) -> Jzon {
+    match i64::try_from(n) {
+        Ok(v) => Jzon::Integer(-1 - v),
+        Err(_) => Jzon::Double(-1.0 - n as f64),
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    // values being decoded, this one included
+    depth: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn byte(&mut self) -> result::Result<u8, CborErr> {
+        let b = *self.bytes.get(self.pos).ok_or(ExpectNoneEOF)?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn peek(&self) -> result::Result<u8, CborErr> {
+        self.bytes.get(self.pos).copied().ok_or(ExpectNoneEOF)
+    }
+
+    fn take(&mut self, n: u64) -> result::Result<&'a [u8], CborErr> {
+        let rest = self.bytes.len() - self.pos;
+        match usize::try_from(n) {
+            Ok(n) if n <= rest => {
+                let s = &self.bytes[self.pos..self.pos + n];
+                self.pos += n;
+                Ok(s)
+            }
+            _ => Err(ExpectNoneEOF),
+        }
+    }
+
+    fn argument(&mut self, info: u8) -> result::Result<u64, CborErr> {
+        let arg = match info {
+            0..=23 => info as u64,
+            24 => self.byte()? as u64,
+            25 => u16::from_be_bytes([self.byte()?, self.byte()?]) as u64,
+            26 => {
+                let mut buf = [0; 4];
+                buf.copy_from_slice(self.take(4)?);
+                u32::from_be_bytes(buf) as u64
+            }
+            27 => {
+                let mut buf = [0; 8];
+                buf.copy_from_slice(self.take(8)?);
+                u64::from_be_bytes(buf)
+            }
+            _ => return Err(ExpectDefiniteInfo),
+        };
+        Ok(arg)
+    }
+
+    fn at_break(&mut self) -> result::Result<bool, CborErr> {
+        if self.peek()? == BREAK {
+            self.pos += 1;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn value(&mut self) -> Result {
+        if self.depth == MAX_DEPTH {
+            return Err(UnexpectedDepth);
+        }
+        self.depth += 1;
+        let value = self.item();
+        self.depth -= 1;
+        value
+    }
+
+    fn item(&mut self) -> Result {
+        let initial = self.byte()?;
+        let (major, info) = (initial >> 5, initial & 0x1F);
+
+        match major {
+            UINT => Ok(uint(self.argument(info)?)),
+            NINT => Ok(nint(self.argument(info)?)),
+            BYTES => Ok(Jzon::String(base64::encode_url(&self.string(BYTES, info)?))),
+            TEXT => self.text(info).map(Jzon::String),
+            ARRAY => {
+                let mut vec = vec![];
+                if info == INDEFINITE {
+                    while !self.at_break()? {
+                        vec.push(self.value()?);
+                    }
+                } else {
+                    for _ in 0..self.argument(info)? {
+                        vec.push(self.value()?);
+                    }
+                }
+                Ok(Jzon::Array(vec))
+            }
+            MAP => {
+                let mut members = vec![];
+                if info == INDEFINITE {
+                    while !self.at_break()? {
+                        let key = self.key()?;
+                        members.push((key, self.value()?));
+                    }
+                } else {
+                    for _ in 0..self.argument(info)? {
+                        let key = self.key()?;
+                        members.push((key, self.value()?));
+                    }
+                }
+                Ok(Jzon::Object(members.into_iter().collect()))
+            }
+            TAG => {
+                let tag = self.argument(info)?;
+                self.tagged(tag)
+            }
+            _ => self.simple(info),
+        }
+    }
+
+    fn key(&mut self) -> result::Result<String, CborErr> {
+        match self.value()? {
+            Jzon::String(s) => Ok(s),
+            other => Ok(other.stringify()),
+        }
+    }
+
+    fn text(&mut self, info: u8) -> result::Result<String, CborErr> {
+        String::from_utf8(self.string(TEXT, info)?).map_err(|_| ExpectUtf8)
+    }
+
+    // byte or text string content, joining the chunks of an indefinite one
+    fn string(&mut self, major: u8, info: u8) -> result::Result<Vec<u8>, CborErr> {
+        if info != INDEFINITE {
+            let len = self.argument(info)?;
+            return Ok(self.take(len)?.to_vec());
+        }
+
+        let mut buf = vec![];
+        while !self.at_break()? {
+            let initial = self.byte()?;
+            if initial >> 5 != major || initial & 0x1F == INDEFINITE {
+                return Err(ExpectChunk);
+            }
+            let len = self.argument(initial & 0x1F)?;
+            buf.extend_from_slice(self.take(len)?);
+        }
+        Ok(buf)
+    }
+
+    fn raw_bytes(&mut self) -> result::Result<Vec<u8>, CborErr> {
+        let initial = self.byte()?;
+        if initial >> 5 != BYTES {
+            return Err(ExpectTagContent);
+        }
+        self.string(BYTES, initial & 0x1F)
+    }
+
+    fn tagged(&mut self, tag: u64) -> Result {
+        match tag {
+            TAG_POS_BIGNUM | TAG_NEG_BIGNUM => {
+                let digits = self.raw_bytes()?;
+                let digits = match digits.iter().position(|b| *b != 0) {
+                    Some(i) => &digits[i..],
+                    None => &[],
+                };
+                let value = if digits.len() <= 8 {
+                    let n = digits.iter().fold(0u64, |n, b| n << 8 | *b as u64);
+                    if tag == TAG_POS_BIGNUM {
+                        uint(n)
+                    } else {
+                        nint(n)
+                    }
+                } else {
+                    let n = digits.iter().fold(0f64, |n, b| n * 256.0 + *b as f64);
+                    Jzon::Double(if tag == TAG_POS_BIGNUM { n } else { -1.0 - n })
+                };
+                Ok(value)
+            }
+            TAG_DECIMAL | TAG_BIGFLOAT => {
+                let (exp, mant) = match self.value()? {
+                    Jzon::Array(ref v) if v.len() == 2 => match (&v[0], &v[1]) {
+                        (Jzon::Integer(e), Jzon::Integer(m)) => (*e, *m as f64),
+                        (Jzon::Integer(e), Jzon::Double(m)) => (*e, *m),
+                        _ => return Err(ExpectTagContent),
+                    },
+                    _ => return Err(ExpectTagContent),
+                };
+                let exp = i32::try_from(exp).map_err(|_| ExpectTagContent)?;
+                let value = if tag == TAG_BIGFLOAT {
+                    mant * 2f64.powi(exp)
+                } else {
+                    // let the float parser round `mant`e`exp` correctly
+                    format!("{}e{}", mant, exp)
+                        .parse()
+                        .unwrap_or(mant * 10f64.powi(exp))
+                };
+                Ok(Jzon::Double(value))
+            }
+            TAG_BASE64URL | TAG_BASE64 | TAG_BASE16 if self.peek()? >> 5 == BYTES => {
+                let bytes = self.raw_bytes()?;
+                Ok(Jzon::String(match tag {
+                    TAG_BASE64URL => base64::encode_url(&bytes),
+                    TAG_BASE64 => base64::encode(&bytes),
+                    _ => base64::encode_hex(&bytes),
+                }))
+            }
+            _ => self.value(),
+        }
+    }
+
+    fn simple(&mut self, info: u8) -> Result {
+        let value = match info {
+            20 => Jzon::Bool(false),
+            21 => Jzon::Bool(true),
+            // undefined has no JSON counterpart, null is the closest
+            22 | 23 => Jzon::Null,
+            25 => {
+                let half = u16::from_be_bytes([self.byte()?, self.byte()?]);
+                Jzon::Double(f16_to_f64(half))
+            }
+            26 => {
+                let mut buf = [0; 4];
+                buf.copy_from_slice(self.take(4)?);
+                Jzon::Double(f32::from_be_bytes(buf) as f64)
+            }
+            27 => {
+                let mut buf = [0; 8];
+                buf.copy_from_slice(self.take(8)?);
+                Jzon::Double(f64::from_be_bytes(buf))
+            }
+            INDEFINITE => return Err(UnexpectedBreak),
+            _ => return Err(ExpectKnownSimple),
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn encode_json(json: &str) -> Vec<u8> {
+        Jzon::parse(json.as_bytes()).unwrap().to_cbor()
+    }
+
+    // RFC 8949 Appendix A
+    #[test]
+    fn encode_preferred() {
+        let cases = [
+            ("0", "00"),
+            ("23", "17"),
+            ("24", "1818"),
+            ("100", "1864"),
+            ("1000", "1903e8"),
+            ("1000000", "1a000f4240"),
+            ("1000000000000", "1b000000e8d4a51000"),
+            ("-1", "20"),
+            ("-10", "29"),
+            ("-100", "3863"),
+            ("-1000", "3903e7"),
+            ("-9223372036854775808", "3b7fffffffffffffff"),
+            ("0.0", "f90000"),
+            ("1.0", "f93c00"),
+            ("1.5", "f93e00"),
+            ("-4.1", "fbc010666666666666"),
+            ("false", "f4"),
+            ("true", "f5"),
+            ("null", "f6"),
+            (r#""""#, "60"),
+            (r#""IETF""#, "6449455446"),
+            (r#""\"\\""#, "62225c"),
+            (r#""ü""#, "62c3bc"),
+            (r#""水""#, "63e6b0b4"),
+            ("[]", "80"),
+            ("[1,[2,3],[4,5]]", "8301820203820405"),
+            ("{}", "a0"),
+            (r#"{"a":"A"}"#, "a161616141"),
+        ];
+        for (json, cbor) in cases.iter() {
+            assert_eq!(hex(cbor), encode_json(json), "{}", json);
+        }
+
+        let floats = [
+            (-0.0, "f98000"),
+            (1.1, "fb3ff199999999999a"),
+            (65504.0, "f97bff"),
+            (100000.0, "fa47c35000"),
+            (3.4028234663852886e+38, "fa7f7fffff"),
+            (1.0e+300, "fb7e37e43c8800759c"),
+            (5.960464477539063e-8, "f90001"),
+            (0.00006103515625, "f90400"),
+            (-4.0, "f9c400"),
+            (f64::INFINITY, "f97c00"),
+            (f64::NAN, "f97e00"),
+            (f64::NEG_INFINITY, "f9fc00"),
+        ];
+        for (v, cbor) in floats.iter() {
+            assert_eq!(hex(cbor), Jzon::Double(*v).to_cbor(), "{}", v);
+        }
+    }
+
+    #[test]
+    fn encode_indefinite() {
+        let jz = Jzon::parse(r#"["a", [1]]"#.as_bytes()).unwrap();
+        assert_eq!(
+            hex("9f7f6161ff9f01ffff"),
+            jz.to_cbor_with(Encoding::Indefinite)
+        );
+        assert_eq!(
+            "[\"a\",[1]]",
+            Jzon::from_cbor(&jz.to_cbor_with(Encoding::Indefinite))
+                .unwrap()
+                .stringify()
+        );
+    }
+
+    #[test]
+    fn encode_canonical() {
+        let jz =
+            Jzon::parse(r#"{"b": 1, "aa": 3, "a": 2, "c": {"z": 0, "y": 1}}"#.as_bytes()).unwrap();
+        assert_eq!(
+            hex("a46161026162016163a2617901617a0062616103"),
+            jz.to_cbor_with(Encoding::Canonical)
+        );
+    }
+
+    #[test]
+    fn decode() {
+        let cases = [
+            ("00", "0"),
+            ("3b7fffffffffffffff", "-9223372036854775808"),
+            ("1bffffffffffffffff", "18446744073709552000.0"),
+            ("c249010000000000000000", "18446744073709552000.0"),
+            ("c349010000000000000000", "-18446744073709552000.0"),
+            ("c2430100ff", "65791"),
+            ("c48221196ab3", "273.15"),
+            ("c5822003", "1.5"),
+            ("f90001", "5.960464477539062e-8"),
+            ("fa47c35000", "100000.0"),
+            ("f7", "null"),
+            ("4401020304", r#""AQIDBA""#),
+            ("d6420102", r#""AQI=""#),
+            ("d7420102", r#""0102""#),
+            ("5f42010243030405ff", r#""AQIDBAU""#),
+            ("7f657374726561646d696e67ff", r#""streaming""#),
+            (
+                "c074323031332d30332d32315432303a30343a30305a",
+                r#""2013-03-21T20:04:00Z""#,
+            ),
+            ("9fff", "[]"),
+            ("9f018202039f0405ffff", "[1,[2,3],[4,5]]"),
+            ("83019f0203ff820405", "[1,[2,3],[4,5]]"),
+        ];
+        for (cbor, json) in cases.iter() {
+            let jz = Jzon::from_cbor(&hex(cbor)).unwrap();
+            assert_eq!(*json, jz.stringify(), "{}", cbor);
+        }
+
+        let jz = Jzon::from_cbor(&hex("bf61610161629f0203ffff")).unwrap();
+        assert_eq!(jz["a"], 1);
+        assert_eq!(jz["b"][1], 3);
+        let jz = Jzon::from_cbor(&hex("a201020304")).unwrap();
+        assert_eq!(jz["1"], 2);
+        assert_eq!(jz["3"], 4);
+    }
+
+    #[test]
+    fn decode_malformed() {
+        assert!(matches!(Jzon::from_cbor(&hex("")), Err(ExpectNoneEOF)));
+        assert!(matches!(
+            Jzon::from_cbor(&hex("1a0000")),
+            Err(ExpectNoneEOF)
+        ));
+        assert!(matches!(Jzon::from_cbor(&hex("8201")), Err(ExpectNoneEOF)));
+        assert!(matches!(Jzon::from_cbor(&hex("0000")), Err(ExpectNoMore)));
+        assert!(matches!(Jzon::from_cbor(&hex("ff")), Err(UnexpectedBreak)));
+        assert!(matches!(
+            Jzon::from_cbor(&hex("1c")),
+            Err(ExpectDefiniteInfo)
+        ));
+        assert!(matches!(Jzon::from_cbor(&hex("62c328")), Err(ExpectUtf8)));
+        assert!(matches!(
+            Jzon::from_cbor(&hex("5f6161ff")),
+            Err(ExpectChunk)
+        ));
+        assert!(matches!(
+            Jzon::from_cbor(&hex("f0")),
+            Err(ExpectKnownSimple)
+        ));
+        assert!(matches!(
+            Jzon::from_cbor(&hex("c201")),
+            Err(ExpectTagContent)
+        ));
+
+        let mut nested = vec![0x81; 127];
+        nested.push(0);
+        assert!(Jzon::from_cbor(&nested).is_ok());
+        nested.insert(0, 0x81);
+        assert!(matches!(Jzon::from_cbor(&nested), Err(UnexpectedDepth)));
+        let deep = vec![0x81; 200_000];
+        assert!(matches!(Jzon::from_cbor(&deep), Err(UnexpectedDepth)));
+        let tags = vec![0xc6; 200_000];
+        assert!(matches!(Jzon::from_cbor(&tags), Err(UnexpectedDepth)));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let json = r#"{"name":"jzon","tags":["a","b"],"n":-142,"half":0.5,"ok":true,"none":null}"#;
+        let jz = Jzon::parse(json.as_bytes()).unwrap();
+        for encoding in [
+            Encoding::Preferred,
+            Encoding::Indefinite,
+            Encoding::Canonical,
+        ]
+        .iter()
+        {
+            let back = Jzon::from_cbor(&jz.to_cbor_with(*encoding)).unwrap();
+            assert_eq!(back["name"], "jzon");
+            assert_eq!(back["tags"][1], "b");
+            assert_eq!(back["n"], -142);
+            assert_eq!(back["half"], 0.5);
+            assert_eq!(back["ok"], true);
+            assert_eq!(back["none"].stringify(), "null");
+        }
+    }
+}
@@ -0,0 +1,516 @@
+use crate::jzon::Jzon;
+use crate::map::Map;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+// Generates Rust types for the documents a JSON Schema describes, as
+// `Jzon::infer_schema` writes them. Objects become structs, strings with
+// an enum become enums, and values of several types become enums with a
+// variant per type. Everything converts to and from `Jzon` through the
+// traits in `convert`.
+
+// names the generated code uses itself, and prelude ones a type of the
+// same name would shadow
+const TAKEN: [&str; 17] = [
+    "Self",
+    "String",
+    "Vec",
+    "Option",
+    "Some",
+    "None",
+    "Result",
+    "Ok",
+    "Err",
+    "Box",
+    "Default",
+    "From",
+    "Map",
+    "Jzon",
+    "ConvertErr",
+    "FromJzon",
+    "ToJzon",
+];
+
+const KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+pub fn rust_types(schema: &Jzon, root: &str) -> String {
+    let mut gen = Generator {
+        names: TAKEN.iter().map(|n| n.to_string()).collect(),
+        defs: vec![],
+    };
+    let ty = gen.type_of(schema, &pascal(root));
+    let named = gen.names.contains(&ty) && !TAKEN.contains(&ty.as_str());
+    if !named {
+        let name = gen.name(&pascal(root));
+        gen.defs.insert(0, format!("pub type {} = {};\n", name, ty));
+    }
+
+    let mut out = String::from("// Generated from sample documents by `jzon_pp rust`.\n");
+    let converts = gen.defs.iter().any(|def| !def.starts_with("pub type"));
+    if converts {
+        out += "use jzon::convert::{ConvertErr, FromJzon, ToJzon};\n";
+    }
+    if converts || gen.defs[0].contains("Jzon") {
+        out += "use jzon::Jzon;\n";
+    }
+    let idents = |def: &String| -> bool {
+        def.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| word == "Map")
+    };
+    if gen.defs.iter().any(idents) {
+        out += "use jzon::Map;\n";
+    }
+    for def in gen.defs {
+        out.push('\n');
+        out += &def;
+    }
+    out
+}
+
+struct Generator {
+    // type names in use
+    names: HashSet<String>,
+    // definitions, each parent before its children
+    defs: Vec<String>,
+}
+
+impl Generator {
+    fn name(&mut self, base: &str) -> String {
+        unique(&mut self.names, base)
+    }
+
+    // `hint` names the type if one has to be defined
+    fn type_of(&mut self, schema: &Jzon, hint: &str) -> String {
+        let types = types(schema);
+        let nullable = types.contains(&"null");
+        let rest: Vec<&str> = types.into_iter().filter(|t| *t != "null").collect();
+        match rest.as_slice() {
+            // null or anything
+            [] => String::from("Jzon"),
+            [t] if nullable => format!("Option<{}>", self.single(schema, t, hint)),
+            [t] => self.single(schema, t, hint),
+            _ if nullable => format!("Option<{}>", self.union(schema, &rest, hint)),
+            _ => self.union(schema, &rest, hint),
+        }
+    }
+
+    fn single(&mut self, schema: &Jzon, t: &str, hint: &str) -> String {
+        match t {
+            "object" => match get(schema, "properties") {
+                Some(Jzon::Object(props)) if !props.is_empty() => {
+                    self.structure(props, schema, hint)
+                }
+                _ => String::from("Map"),
+            },
+            "array" => match get(schema, "items") {
+                Some(items) => format!("Vec<{}>", self.type_of(items, &singular(hint))),
+                None => String::from("Vec<Jzon>"),
+            },
+            "string" => match get(schema, "enum") {
+                Some(Jzon::Array(values)) => self.strings(values, hint),
+                _ => String::from("String"),
+            },
+            "integer" => String::from("i64"),
+            "number" => String::from("f64"),
+            "boolean" => String::from("bool"),
+            _ => String::from("Jzon"),
+        }
+    }
+
+    fn structure(&mut self, props: &Map, schema: &Jzon, hint: &str) -> String {
+        let name = self.name(hint);
+        let slot = self.defs.len();
+        self.defs.push(String::new());
+
+        let required: Vec<&Jzon> = match get(schema, "required") {
+            Some(Jzon::Array(keys)) => keys.iter().collect(),
+            _ => vec![],
+        };
+        let mut keys: Vec<&String> = props.keys().collect();
+        keys.sort();
+
+        let mut idents = HashSet::new();
+        let mut fields = String::new();
+        let mut from = String::new();
+        let mut to = String::new();
+        for key in keys {
+            let ident = unique(&mut idents, &snake(key));
+            let mut ty = self.type_of(&props[key], &pascal(key));
+            let lit = format!("{:?}", key);
+            if unraw(&ident) != key {
+                let _ = writeln!(fields, "    // {}", lit);
+            }
+            if required.iter().any(|k| **k == key.as_str()) {
+                let _ = writeln!(
+                    from,
+                    "            {}: jzon::convert::field(map, {})?,",
+                    ident, lit
+                );
+                let _ = writeln!(
+                    to,
+                    "        map.insert({}.to_string(), self.{}.to_jzon());",
+                    lit, ident
+                );
+            } else {
+                ty = format!("Option<{}>", ty);
+                let _ = writeln!(
+                    from,
+                    "            {}: jzon::convert::optional(map, {})?,",
+                    ident, lit
+                );
+                let _ = writeln!(
+                    to,
+                    "        if let Some(v) = &self.{} {{\n            map.insert({}.to_string(), v.to_jzon());\n        }}",
+                    ident, lit
+                );
+            }
+            let _ = writeln!(fields, "    pub {}: {},", ident, ty);
+        }
+
+        self.defs[slot] = format!(
+            "#[derive(Debug)]
+pub struct {name} {{
+{fields}}}
+
+impl FromJzon for {name} {{
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {{
+        let map = match jz {{
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        }};
+        Ok({name} {{
+{from}        }})
+    }}
+}}
+
+impl ToJzon for {name} {{
+    fn to_jzon(&self) -> Jzon {{
+        let mut map = Map::new();
+{to}        Jzon::Object(map)
+    }}
+}}
+",
+            name = name,
+            fields = fields,
+            from = from,
+            to = to
+        );
+        name
+    }
+
+    fn strings(&mut self, values: &[Jzon], hint: &str) -> String {
+        let name = self.name(hint);
+        let mut variants = HashSet::new();
+        let mut decl = String::new();
+        let mut from = String::new();
+        let mut to = String::new();
+        for value in values {
+            let value = match value {
+                Jzon::String(s) => s,
+                _ => continue,
+            };
+            let variant = unique(&mut variants, &pascal(value));
+            let lit = format!("{:?}", value);
+            let _ = writeln!(decl, "    {},", variant);
+            let _ = writeln!(from, "            {} => Ok({}::{}),", lit, name, variant);
+            let _ = writeln!(to, "            {}::{} => {},", name, variant, lit);
+        }
+
+        self.defs.push(format!(
+            "#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum {name} {{
+{decl}}}
+
+impl FromJzon for {name} {{
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {{
+        let s = match jz {{
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        }};
+        match s.as_str() {{
+{from}            _ => Err(ConvertErr::ExpectVariant),
+        }}
+    }}
+}}
+
+impl ToJzon for {name} {{
+    fn to_jzon(&self) -> Jzon {{
+        let s = match self {{
+{to}        }};
+        Jzon::String(s.to_string())
+    }}
+}}
+",
+            name = name,
+            decl = decl,
+            from = from,
+            to = to
+        ));
+        name
+    }
+
+    // one variant per type, told apart by the kind of `Jzon`
+    fn union(&mut self, schema: &Jzon, types: &[&str], hint: &str) -> String {
+        let name = self.name(hint);
+        let slot = self.defs.len();
+        self.defs.push(String::new());
+
+        let mut decl = String::new();
+        let mut from = String::new();
+        let mut to = String::new();
+        for t in types {
+            let (variant, pattern) = match *t {
+                "object" => ("Object", "Jzon::Object(_)"),
+                "array" => ("Array", "Jzon::Array(_)"),
+                "string" => ("String", "Jzon::String(_)"),
+                "integer" => ("Integer", "Jzon::Integer(_)"),
+                "number" => ("Number", "Jzon::Integer(_) | Jzon::Double(_)"),
+                "boolean" => ("Bool", "Jzon::Bool(_)"),
+                _ => continue,
+            };
+            let ty = match *t {
+                // an enum of strings would leave out the other types
+                "string" => String::from("String"),
+                t => self.single(schema, t, &format!("{}{}", name, variant)),
+            };
+            let _ = writeln!(decl, "    {}({}),", variant, ty);
+            let _ = writeln!(
+                from,
+                "            {} => FromJzon::from_jzon(jz).map({}::{}),",
+                pattern, name, variant
+            );
+            let _ = writeln!(to, "            {}::{}(v) => v.to_jzon(),", name, variant);
+        }
+
+        self.defs[slot] = format!(
+            "#[derive(Debug)]
+pub enum {name} {{
+{decl}}}
+
+impl FromJzon for {name} {{
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {{
+        match jz {{
+{from}            _ => Err(ConvertErr::ExpectVariant),
+        }}
+    }}
+}}
+
+impl ToJzon for {name} {{
+    fn to_jzon(&self) -> Jzon {{
+        match self {{
+{to}        }}
+    }}
+}}
+",
+            name = name,
+            decl = decl,
+            from = from,
+            to = to
+        );
+        name
+    }
+}
+
+fn get<'a>(schema: &'a Jzon, key: &str) -> Option<&'a Jzon> {
+    match schema {
+        Jzon::Object(map) => map.get(key),
+        _ => None,
+    }
+}
+
+fn types(schema: &Jzon) -> Vec<&str> {
+    match get(schema, "type") {
+        Some(Jzon::String(t)) => vec![t.as_str()],
+        Some(Jzon::Array(ts)) => ts
+            .iter()
+            .filter_map(|t| match t {
+                Jzon::String(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn unique(names: &mut HashSet<String>, base: &str) -> String {
+    let mut name = base.to_string();
+    let mut n = 1;
+    while names.contains(&name) {
+        n += 1;
+        name = format!("{}{}", base, n);
+    }
+    names.insert(name.clone());
+    name
+}
+
+// alphanumeric runs, split again where camelCase goes up
+fn words(s: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut lower = false;
+    for c in s.chars() {
+        if !c.is_alphanumeric() {
+            lower = false;
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        if c.is_uppercase() && lower {
+            words.push(std::mem::take(&mut word));
+        }
+        lower = c.is_lowercase() || c.is_numeric();
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn pascal(s: &str) -> String {
+    let mut name: String = words(s)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            let first = chars.next().unwrap();
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect::<String>()
+        })
+        .collect();
+    if name.is_empty() {
+        name = String::from("Value");
+    }
+    if name.starts_with(|c: char| c.is_numeric()) {
+        name.insert(0, 'V');
+    }
+    name
+}
+
+fn snake(s: &str) -> String {
+    let mut name = words(s)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if name.is_empty() {
+        name = String::from("field");
+    }
+    if name.starts_with(|c: char| c.is_numeric()) {
+        name.insert_str(0, "field_");
+    }
+    match name.as_str() {
+        // these can't be raw identifiers
+        "self" | "super" | "crate" => name + "_",
+        n if KEYWORDS.contains(&n) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+fn unraw(ident: &str) -> &str {
+    ident.strip_prefix("r#").unwrap_or(ident)
+}
+
+// the element type of a list named in the plural
+fn singular(name: &str) -> String {
+    let single = if let Some(stem) = name.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if let Some(stem) = name.strip_suffix("ses") {
+        format!("{}s", stem)
+    } else if name.ends_with('s') && !name.ends_with("ss") {
+        name[..name.len() - 1].to_string()
+    } else {
+        String::new()
+    };
+    match single.as_str() {
+        "" => format!("{}Item", name),
+        _ => single,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(samples: &[&str], root: &str) -> String {
+        let samples: Vec<Jzon> = samples
+            .iter()
+            .map(|s| Jzon::parse(s.as_bytes()).unwrap())
+            .collect();
+        rust_types(&Jzon::infer_schema(&samples), root)
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!("user_id", snake("userId"));
+        assert_eq!("user_id", snake("user-id"));
+        assert_eq!("r#type", snake("type"));
+        assert_eq!("self_", snake("self"));
+        assert_eq!("field_1st", snake("1st"));
+        assert_eq!("field", snake("@"));
+        assert_eq!("HttpUrl", pascal("http_URL"));
+        assert_eq!("V2fa", pascal("2fa"));
+        assert_eq!("Status", singular("Statuses"));
+        assert_eq!("Entry", singular("Entries"));
+        assert_eq!("Url", singular("Urls"));
+        assert_eq!("DataItem", singular("Data"));
+    }
+
+    #[test]
+    fn structs() {
+        let code = generate(
+            &[
+                r#"{"id": 1, "@type": "a", "user-name": null, "tags": ["x"]}"#,
+                r#"{"id": 2, "@type": "a", "user-name": "b", "score": 1.5}"#,
+                r#"{"id": 3, "@type": "a", "user-name": "c", "score": 1}"#,
+            ],
+            "feed",
+        );
+        assert!(code.contains(
+            "pub struct Feed {\n    // \"@type\"\n    pub r#type: Type,\n    pub id: i64,\n"
+        ));
+        assert!(code.contains("    pub score: Option<f64>,\n"));
+        assert!(code.contains("    pub tags: Option<Vec<String>>,\n"));
+        assert!(code.contains("    // \"user-name\"\n    pub user_name: Option<String>,\n"));
+        assert!(code.contains("            r#type: jzon::convert::field(map, \"@type\")?,\n"));
+        assert!(code.contains("            score: jzon::convert::optional(map, \"score\")?,\n"));
+        assert!(code.contains("pub enum Type {\n    A,\n}"));
+        assert!(code.contains("            \"a\" => Ok(Type::A),\n"));
+        assert!(code.contains("use jzon::Map;\n"));
+
+        // no type may shadow what the impls use
+        let code = generate(&[r#"{"result": {"ok": true}, "box": {"err": 1}}"#], "From");
+        assert!(
+            code.contains("pub struct From2 {\n    pub r#box: Box2,\n    pub result: Result2,\n}")
+        );
+        assert!(code.contains("fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {"));
+    }
+
+    #[test]
+    fn unions() {
+        let code = generate(&[r#"[1, "a", {"string": true}, null]"#], "values");
+        assert!(code.starts_with("// Generated"));
+        assert!(code.contains("pub type Values = Vec<Option<Value>>;\n"));
+        assert!(code.contains(
+            "pub enum Value {\n    Object(ValueObject),\n    String(String),\n    Integer(i64),\n}"
+        ));
+        assert!(code.contains(
+            "            Jzon::Integer(_) => FromJzon::from_jzon(jz).map(Value::Integer),\n"
+        ));
+        assert!(code.contains("pub struct ValueObject {\n    pub string: bool,\n}"));
+
+        let code = generate(&["[[1.5]]"], "Matrix");
+        assert_eq!(
+            "// Generated from sample documents by `jzon_pp rust`.\n\npub type Matrix = Vec<Vec<f64>>;\n",
+            code
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use crate::jzon::{Jzon, ParseErr};
+use crate::lazy::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::result;
+use std::sync::Arc;
+
+// A read-only tree that takes less memory than `Jzon` for documents like
+// citm_catalog.json, where arrays of objects repeat a few keys: every node
+// is 24 bytes rather than 32, strings, arrays and objects are boxed slices
+// without spare capacity, and keys may be interned so each distinct key
+// exists only once.
+
+// objects with more members than this keep a hash map
+const SMALL: usize = 16;
+
+pub type Key = Arc<str>;
+
+#[derive(Debug)]
+pub enum Compact {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Double(f64),
+    String(Box<str>),
+    Array(Box<[Compact]>),
+    // sorted by key
+    Object(Box<[(Key, Compact)]>),
+    Map(Box<HashMap<Key, Compact>>),
+}
+
+// the shared symbol table of interned keys
+#[derive(Debug, Default)]
+pub struct Symbols {
+    keys: HashSet<Key>,
+}
+
+impl Symbols {
+    pub fn new() -> Self {
+        Symbols::default()
+    }
+
+    pub fn intern(&mut self, key: &str) -> Key {
+        if let Some(key) = self.keys.get(key) {
+            return Arc::clone(key);
+        }
+        let key: Key = Arc::from(key);
+        self.keys.insert(Arc::clone(&key));
+        key
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl Compact {
+    // every key a separate allocation
+    pub fn parse(bytes: &[u8]) -> result::Result<Compact, ParseErr> {
+        Ok(Compact::build(Jzon::lazy(bytes)?, &mut None))
+    }
+
+    // keys shared through `symbols`, which can serve many documents
+    pub fn parse_interned(
+        bytes: &[u8],
+        symbols: &mut Symbols,
+    ) -> result::Result<Compact, ParseErr> {
+        Ok(Compact::build(Jzon::lazy(bytes)?, &mut Some(symbols)))
+    }
+
+    // straight from the text, so no `Jzon` tree exists on the way
+    fn build(lazy: Lazy<'_>, symbols: &mut Option<&mut Symbols>) -> Compact {
+        if lazy.is_object() {
+            let mut members: Vec<(Key, Compact)> = vec![];
+            for (k, v) in lazy.members() {
+                let key = match symbols {
+                    Some(symbols) => symbols.intern(&k),
+                    None => Arc::from(k.as_ref()),
+                };
+                members.push((key, Compact::build(v, symbols)));
+            }
+            return Compact::object(members);
+        }
+        if lazy.is_array() {
+            let vec: Vec<_> = lazy
+                .elements()
+                .map(|v| Compact::build(v, symbols))
+                .collect();
+            return Compact::Array(vec.into_boxed_slice());
+        }
+        if let Some(s) = lazy.as_str() {
+            return Compact::String(s.into());
+        }
+        if let Some(v) = lazy.as_bool() {
+            return Compact::Bool(v);
+        }
+        match lazy.as_i64() {
+            Some(v) => Compact::Integer(v),
+            None => lazy.as_f64().map_or(Compact::Null, Compact::Double),
+        }
+    }
+
+    // the last of duplicate keys wins, as in `Jzon::parse`
+    fn object(mut members: Vec<(Key, Compact)>) -> Compact {
+        if members.len() > SMALL {
+            return Compact::Map(Box::new(members.into_iter().collect()));
+        }
+        // stable, so duplicates stay in document order
+        members.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut deduped: Vec<(Key, Compact)> = Vec::with_capacity(members.len());
+        for (k, v) in members {
+            match deduped.last_mut() {
+                Some(last) if last.0 == k => last.1 = v,
+                _ => deduped.push((k, v)),
+            }
+        }
+        Compact::Object(deduped.into_boxed_slice())
+    }
+
+    pub fn from_jzon(jz: &Jzon, symbols: Option<&mut Symbols>) -> Compact {
+        let mut symbols = symbols;
+        Compact::convert(jz, &mut symbols)
+    }
+
+    fn convert(jz: &Jzon, symbols: &mut Option<&mut Symbols>) -> Compact {
+        match jz {
+            Jzon::Null | Jzon::Error => Compact::Null,
+            Jzon::Bool(v) => Compact::Bool(*v),
+            Jzon::Integer(v) => Compact::Integer(*v),
+            Jzon::Double(v) => Compact::Double(*v),
+            Jzon::String(s) => Compact::String(s.as_str().into()),
+            Jzon::Array(vec) => {
+                Compact::Array(vec.iter().map(|v| Compact::convert(v, symbols)).collect())
+            }
+            Jzon::Object(map) => {
+                let members = map
+                    .iter()
+                    .map(|(k, v)| {
+                        let key = match symbols {
+                            Some(symbols) => symbols.intern(k),
+                            None => Arc::from(k.as_str()),
+                        };
+                        (key, Compact::convert(v, symbols))
+                    })
+                    .collect();
+                Compact::object(members)
+            }
+        }
+    }
+
+    pub fn to_jzon(&self) -> Jzon {
+        match self {
+            Compact::Null => Jzon::Null,
+            Compact::Bool(v) => Jzon::Bool(*v),
+            Compact::Integer(v) => Jzon::Integer(*v),
+            Compact::Double(v) => Jzon::Double(*v),
+            Compact::String(s) => Jzon::String(s.to_string()),
+            Compact::Array(vec) => Jzon::Array(vec.iter().map(Compact::to_jzon).collect()),
+            Compact::Object(members) => Jzon::Object(
+                members
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_jzon()))
+                    .collect(),
+            ),
+            Compact::Map(map) => Jzon::Object(
+                map.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_jzon()))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Compact> {
+        match self {
+            Compact::Object(members) => members
+                .binary_search_by(|(k, _)| k.as_ref().cmp(key))
+                .ok()
+                .map(|i| &members[i].1),
+            Compact::Map(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn at(&self, idx: usize) -> Option<&Compact> {
+        match self {
+            Compact::Array(vec) => vec.get(idx),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Compact::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+
+    #[test]
+    fn compact() {
+        assert_eq!(24, mem::size_of::<Compact>());
+
+        let text = br#"[{"b": 1, "a": [true, null], "b": 2.5}, {"a": "x", "b": {}}]"#;
+        let mut symbols = Symbols::new();
+        let doc = Compact::parse_interned(text, &mut symbols).unwrap();
+        assert_eq!(2, symbols.len());
+        assert!(matches!(doc.at(0).unwrap().get("b"), Some(Compact::Double(v)) if *v == 2.5));
+        assert_eq!(Some("x"), doc.at(1).unwrap().get("a").unwrap().as_str());
+        assert!(doc.at(1).unwrap().get("c").is_none());
+        // both objects point at the same "a"
+        let key = |i: usize| match doc.at(i).unwrap() {
+            Compact::Object(members) => Arc::clone(&members[0].0),
+            _ => panic!(),
+        };
+        assert!(Arc::ptr_eq(&key(0), &key(1)));
+
+        let jz = Jzon::parse(text).unwrap();
+        let sorted = |jz: &Jzon| format!("{}", jz.styled().sort_keys(true));
+        assert_eq!(sorted(&jz), sorted(&doc.to_jzon()));
+        assert_eq!(
+            sorted(&jz),
+            sorted(&Compact::from_jzon(&jz, None).to_jzon())
+        );
+
+        let content = std::fs::read("data/citm_catalog.json").unwrap();
+        let jz = Jzon::parse(&content).unwrap();
+        let doc = Compact::parse_interned(&content, &mut symbols).unwrap();
+        assert_eq!(sorted(&jz), sorted(&doc.to_jzon()));
+        assert!(matches!(doc.get("events"), Some(Compact::Map(_))));
+        assert_eq!(
+            jz["venueNames"]["PLEYEL_PLEYEL"],
+            doc.get("venueNames")
+                .unwrap()
+                .get("PLEYEL_PLEYEL")
+                .unwrap()
+                .as_str()
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::jzon::Jzon;
+use crate::map::Map;
+use std::collections::HashMap;
+use std::result;
+
+// Conversions between Rust values and `Jzon`, which the types generated by
+// `codegen` implement.
+
+#[derive(Debug)]
+pub enum ConvertErr {
+    ExpectObject,
+    ExpectArray,
+    ExpectString,
+    ExpectNumber,
+    ExpectInteger,
+    ExpectBool,
+    // a required key is missing
+    ExpectKey(String),
+    // the value matches no variant of an enum
+    ExpectVariant,
+}
+
+type Result<T> = result::Result<T, ConvertErr>;
+
+pub trait FromJzon: Sized {
+    fn from_jzon(jz: &Jzon) -> Result<Self>;
+}
+
+pub trait ToJzon {
+    fn to_jzon(&self) -> Jzon;
+}
+
+use ConvertErr::*;
+
+// the value under a key that must be there
+pub fn field<T: FromJzon>(map: &Map, key: &str) -> Result<T> {
+    match map.get(key) {
+        Some(jz) => T::from_jzon(jz),
+        None => Err(ExpectKey(key.to_string())),
+    }
+}
+
+// `None` when the key is missing, so `Option<Option<T>>` tells a missing
+// key from a null
+pub fn optional<T: FromJzon>(map: &Map, key: &str) -> Result<Option<T>> {
+    map.get(key).map(T::from_jzon).transpose()
+}
+
+impl FromJzon for bool {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        match jz {
+            Jzon::Bool(v) => Ok(*v),
+            _ => Err(ExpectBool),
+        }
+    }
+}
+
+impl FromJzon for i64 {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        match jz {
+            Jzon::Integer(v) => Ok(*v),
+            _ => Err(ExpectInteger),
+        }
+    }
+}
+
+impl FromJzon for f64 {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        match jz {
+            Jzon::Integer(v) => Ok(*v as f64),
+            Jzon::Double(v) => Ok(*v),
+            _ => Err(ExpectNumber),
+        }
+    }
+}
+
+impl FromJzon for String {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        match jz {
+            Jzon::String(s) => Ok(s.clone()),
+            _ => Err(ExpectString),
+        }
+    }
+}
+
+// anything goes, for values the samples didn't pin down
+impl FromJzon for Jzon {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        Ok(jz.clone())
+    }
+}
+
+// null is `None`
+impl<T: FromJzon> FromJzon for Option<T> {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        match jz {
+            Jzon::Null => Ok(None),
+            jz => T::from_jzon(jz).map(Some),
+        }
+    }
+}
+
+impl<T: FromJzon> FromJzon for Vec<T> {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        match jz {
+            Jzon::Array(vec) => vec.iter().map(T::from_jzon).collect(),
+            _ => Err(ExpectArray),
+        }
+    }
+}
+
+impl<T: FromJzon> FromJzon for HashMap<String, T> {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        match jz {
+            Jzon::Object(map) => map
+                .iter()
+                .map(|(k, v)| T::from_jzon(v).map(|v| (k.clone(), v)))
+                .collect(),
+            _ => Err(ExpectObject),
+        }
+    }
+}
+
+impl FromJzon for Map {
+    fn from_jzon(jz: &Jzon) -> Result<Self> {
+        match jz {
+            Jzon::Object(map) => Ok(map.clone()),
+            _ => Err(ExpectObject),
+        }
+    }
+}
+
+impl ToJzon for bool {
+    fn to_jzon(&self) -> Jzon {
+        Jzon::Bool(*self)
+    }
+}
+
+impl ToJzon for i64 {
+    fn to_jzon(&self) -> Jzon {
+        Jzon::Integer(*self)
+    }
+}
+
+impl ToJzon for f64 {
+    fn to_jzon(&self) -> Jzon {
+        Jzon::Double(*self)
+    }
+}
+
+impl ToJzon for String {
+    fn to_jzon(&self) -> Jzon {
+        Jzon::String(self.clone())
+    }
+}
+
+impl ToJzon for Jzon {
+    fn to_jzon(&self) -> Jzon {
+        self.clone()
+    }
+}
+
+impl<T: ToJzon> ToJzon for Option<T> {
+    fn to_jzon(&self) -> Jzon {
+        match self {
+            Some(v) => v.to_jzon(),
+            None => Jzon::Null,
+        }
+    }
+}
+
+impl<T: ToJzon> ToJzon for Vec<T> {
+    fn to_jzon(&self) -> Jzon {
+        Jzon::Array(self.iter().map(T::to_jzon).collect())
+    }
+}
+
+impl<T: ToJzon> ToJzon for HashMap<String, T> {
+    fn to_jzon(&self) -> Jzon {
+        Jzon::Object(self.iter().map(|(k, v)| (k.clone(), v.to_jzon())).collect())
+    }
+}
+
+impl ToJzon for Map {
+    fn to_jzon(&self) -> Jzon {
+        Jzon::Object(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let jz = Jzon::parse(br#"{"a": [1, null, 3], "b": {"x": 1.5, "y": 2}}"#).unwrap();
+        let map = match &jz {
+            Jzon::Object(map) => map,
+            _ => panic!(),
+        };
+        let a: Vec<Option<i64>> = field(map, "a").unwrap();
+        assert_eq!(vec![Some(1), None, Some(3)], a);
+        let b: HashMap<String, f64> = field(map, "b").unwrap();
+        assert_eq!(2.0, b["y"]);
+        let c: Option<Option<bool>> = optional(map, "c").unwrap();
+        assert_eq!(None, c);
+        assert_eq!(r#"[1,null,3]"#, a.to_jzon().stringify());
+
+        assert!(matches!(field::<i64>(map, "c"), Err(ExpectKey(k)) if k == "c"));
+        assert!(matches!(field::<Vec<i64>>(map, "b"), Err(ExpectArray)));
+        assert!(matches!(
+            field::<HashMap<String, i64>>(map, "b"),
+            Err(ExpectInteger)
+        ));
+    }
+}
@@ -0,0 +1,737 @@
+use crate::diff::{Path, Segment};
+use crate::jzon::{Jzon, ParseErr};
+use std::fmt;
+use std::ops::Range;
+use std::result;
+
+// A lossless syntax tree: the text is kept as written, down to whitespace
+// and comments, and edits only rewrite the spans they affect.
+
+#[derive(Debug)]
+pub enum CstErr {
+    // the text isn't JSON, the error is at this byte offset
+    Syntax(ParseErr, usize),
+    // nothing at the path, or its parent can't hold the value
+    ExpectPath,
+    // the key is already taken
+    ExpectNewKey,
+    // the root has no parent to insert into, remove from or rename in
+    ExpectNoneRoot,
+    // only object members have keys to rename
+    ExpectMember,
+}
+
+type Result<T> = result::Result<T, CstErr>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Space,
+    // `//` to the end of the line, or `/* */`
+    Comment,
+    // one of {}[]:,
+    Punct,
+    String,
+    Number,
+    // true, false or null
+    Literal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug)]
+pub struct Cst {
+    text: String,
+    tokens: Vec<Token>,
+    root: Node,
+    // one level of indentation, as the text does it
+    unit: String,
+}
+
+#[derive(Debug)]
+struct Node {
+    span: Range<usize>,
+    kind: Kind,
+}
+
+#[derive(Debug)]
+enum Kind {
+    Scalar,
+    Array(Vec<Item>),
+    Object(Vec<Item>),
+}
+
+#[derive(Debug)]
+struct Item {
+    // span and decoded text of an object member's key
+    key: Option<(Range<usize>, String)>,
+    value: Node,
+    comma: Option<usize>,
+}
+
+use CstErr::*;
+
+impl Item {
+    fn start(&self) -> usize {
+        match &self.key {
+            Some((span, _)) => span.start,
+            None => self.value.span.start,
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.value.span.end
+    }
+}
+
+impl Node {
+    fn items(&self) -> &[Item] {
+        match &self.kind {
+            Kind::Array(items) | Kind::Object(items) => items,
+            Kind::Scalar => &[],
+        }
+    }
+
+    // the last of duplicate keys wins, as in `Jzon::parse`
+    fn position(&self, seg: &Segment) -> Option<usize> {
+        match (&self.kind, seg) {
+            (Kind::Array(items), Segment::Index(i)) if *i < items.len() => Some(*i),
+            (Kind::Object(items), seg) => {
+                let key = segment_key(seg);
+                items
+                    .iter()
+                    .rposition(|item| item.key.as_ref().is_some_and(|k| k.1 == key))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn segment_key(seg: &Segment) -> String {
+    match seg {
+        Segment::Key(k) => k.clone(),
+        Segment::Index(i) => i.to_string(),
+    }
+}
+
+impl Cst {
+    // Accepts JSON with comments.
+    pub fn parse(text: &str) -> Result<Cst> {
+        let tokens = lex(text)?;
+        let significant: Vec<&Token> = tokens
+            .iter()
+            .filter(|t| t.kind != TokenKind::Space && t.kind != TokenKind::Comment)
+            .collect();
+        let mut parser = Parser {
+            text,
+            tokens: &significant,
+            next: 0,
+        };
+        let root = parser.node()?;
+        if let Some(token) = significant.get(parser.next) {
+            return Err(Syntax(ParseErr::ExpectNoMore, token.span.start));
+        }
+        let unit = indent_unit(text);
+        Ok(Cst {
+            text: text.to_string(),
+            tokens,
+            root,
+            unit,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    // the value at `path`, without its comments
+    pub fn get(&self, path: &Path) -> Option<Jzon> {
+        self.node_at(&path.0).map(|node| self.value(node))
+    }
+
+    pub fn to_jzon(&self) -> Jzon {
+        self.value(&self.root)
+    }
+
+    // Replaces the value at `path`, or inserts it if the path doesn't exist
+    // yet but its parent does.
+    pub fn set(&mut self, path: &Path, value: &Jzon) -> Result<()> {
+        let node = match self.node_at(&path.0) {
+            Some(node) => node,
+            None => return self.insert(path, value),
+        };
+        let pretty = match path.0.split_last() {
+            Some((_, parent)) => self.multiline(self.node_at(parent).unwrap().span.clone()),
+            None => self.multiline(0..self.text.len()),
+        };
+        let start = node.span.start;
+        let text = self.render(value, self.indent_at(start), pretty);
+        self.splice(vec![(node.span.clone(), text)])
+    }
+
+    // Adds an object member, or an array element before the one at the
+    // index; an index equal to the length appends.
+    pub fn insert(&mut self, path: &Path, value: &Jzon) -> Result<()> {
+        let (last, parent_path) = path.0.split_last().ok_or(ExpectNoneRoot)?;
+        let parent = self.node_at(parent_path).ok_or(ExpectPath)?;
+        let items = parent.items();
+        let (index, key) = match (&parent.kind, last) {
+            (Kind::Object(_), seg) if parent.position(seg).is_some() => return Err(ExpectNewKey),
+            (Kind::Object(_), seg) => (items.len(), Some(segment_key(seg))),
+            (Kind::Array(_), Segment::Index(i)) if *i <= items.len() => (*i, None),
+            _ => return Err(ExpectPath),
+        };
+
+        let open = parent.span.start;
+        let close = parent.span.end - 1;
+        let pretty = self.multiline(parent.span.clone());
+        let indent = match items.get(index).or(items.last()) {
+            Some(item) => self.indent_at(item.start()).to_string(),
+            None => format!("{}{}", self.indent_at(close), self.unit),
+        };
+        let mut entry = String::new();
+        if let Some(key) = key {
+            entry += &Jzon::String(key).stringify();
+            entry += match items.first() {
+                Some(Item {
+                    key: Some((span, _)),
+                    value,
+                    ..
+                }) => &self.text[span.end..value.span.start],
+                _ => ": ",
+            };
+        }
+        entry += &self.render(value, &indent, pretty);
+
+        // elements on a line of their own get a new line, and comments stay
+        // with the element they were written next to
+        let splices = if items.is_empty() {
+            match self.newline_in(open + 1..close) {
+                Some(_) => vec![(open + 1..open + 1, format!("\n{}{}", indent, entry))],
+                None => vec![(open + 1..open + 1, entry)],
+            }
+        } else if index < items.len() {
+            let item = &items[index];
+            let prev = match index {
+                0 => open + 1,
+                _ => items[index - 1].comma.unwrap() + 1,
+            };
+            match self.newline_in(prev..item.start()) {
+                Some(nl) => vec![(nl + 1..nl + 1, format!("{}{},\n", indent, entry))],
+                None => {
+                    let at = item.start();
+                    vec![(at..at, format!("{},{}", entry, self.separator(parent)))]
+                }
+            }
+        } else {
+            let end = items[items.len() - 1].end();
+            match self.newline_in(end..close) {
+                Some(nl) => vec![
+                    (end..end, String::from(",")),
+                    (nl..nl, format!("\n{}{}", indent, entry)),
+                ],
+                None => vec![(end..end, format!(",{}{}", self.separator(parent), entry))],
+            }
+        };
+        self.splice(splices)
+    }
+
+    pub fn remove(&mut self, path: &Path) -> Result<()> {
+        let (last, parent_path) = path.0.split_last().ok_or(ExpectNoneRoot)?;
+        let parent = self.node_at(parent_path).ok_or(ExpectPath)?;
+        let i = parent.position(last).ok_or(ExpectPath)?;
+        let items = parent.items();
+        let item = &items[i];
+        let after = item.comma.map_or(item.end(), |c| c + 1);
+        let next = items
+            .get(i + 1)
+            .map_or(parent.span.end - 1, |next| next.start());
+
+        let line = line_start(&self.text, item.start());
+        let own_line = self.text[line..item.start()].trim().is_empty();
+        let mut splices = vec![];
+        match self.newline_in(after..next) {
+            // the whole line goes, with any comment at its end
+            Some(nl) if own_line => {
+                splices.push((line..nl + 1, String::new()));
+                if let (None, Some(prev)) = (item.comma, i.checked_sub(1)) {
+                    let comma = items[prev].comma.unwrap();
+                    splices.push((comma..comma + 1, String::new()));
+                }
+            }
+            _ if item.comma.is_some() => splices.push((item.start()..next, String::new())),
+            _ if i > 0 => splices.push((items[i - 1].end()..item.end(), String::new())),
+            _ => splices.push((item.start()..item.end(), String::new())),
+        }
+        self.splice(splices)
+    }
+
+    pub fn rename_key(&mut self, path: &Path, key: &str) -> Result<()> {
+        let (last, parent_path) = path.0.split_last().ok_or(ExpectNoneRoot)?;
+        let parent = self.node_at(parent_path).ok_or(ExpectPath)?;
+        if let Kind::Array(_) | Kind::Scalar = parent.kind {
+            return Err(ExpectMember);
+        }
+        let i = parent.position(last).ok_or(ExpectPath)?;
+        let (span, old) = parent.items()[i].key.clone().unwrap();
+        if old != key && parent.position(&Segment::Key(key.to_string())).is_some() {
+            return Err(ExpectNewKey);
+        }
+        self.splice(vec![(span, Jzon::String(key.to_string()).stringify())])
+    }
+
+    fn node_at(&self, path: &[Segment]) -> Option<&Node> {
+        path.iter().try_fold(&self.root, |node, seg| {
+            node.position(seg).map(|i| &node.items()[i].value)
+        })
+    }
+
+    fn value(&self, node: &Node) -> Jzon {
+        match &node.kind {
+            Kind::Scalar => Jzon::parse(self.text[node.span.clone()].as_bytes()).unwrap(),
+            Kind::Array(items) => {
+                Jzon::Array(items.iter().map(|it| self.value(&it.value)).collect())
+            }
+            Kind::Object(items) => {
+                let members = items.iter().map(|item| {
+                    let key = item.key.as_ref().unwrap().1.clone();
+                    (key, self.value(&item.value))
+                });
+                Jzon::Object(members.collect())
+            }
+        }
+    }
+
+    // applies non-overlapping replacements, then parses the result again
+    fn splice(&mut self, mut splices: Vec<(Range<usize>, String)>) -> Result<()> {
+        let mut text = self.text.clone();
+        splices.sort_by_key(|(span, _)| span.start);
+        for (span, replacement) in splices.into_iter().rev() {
+            text.replace_range(span, &replacement);
+        }
+        *self = Cst::parse(&text)?;
+        Ok(())
+    }
+
+    // a line break in whitespace, so never one inside a comment
+    fn newline_in(&self, range: Range<usize>) -> Option<usize> {
+        self.tokens
+            .iter()
+            .filter(|t| t.kind == TokenKind::Space)
+            .filter(|t| range.start <= t.span.start && t.span.end <= range.end)
+            .find_map(|t| {
+                self.text[t.span.clone()]
+                    .find('\n')
+                    .map(|i| t.span.start + i)
+            })
+    }
+
+    fn multiline(&self, span: Range<usize>) -> bool {
+        self.newline_in(span).is_some()
+    }
+
+    fn indent_at(&self, pos: usize) -> &str {
+        let line = &self.text[line_start(&self.text, pos)..];
+        let width = line.len() - line.trim_start_matches([' ', '\t']).len();
+        &line[..width]
+    }
+
+    // whitespace after the commas of an inline container
+    fn separator(&self, container: &Node) -> &str {
+        match container.items() {
+            [first, second, ..] => {
+                let gap = &self.text[first.comma.unwrap() + 1..second.start()];
+                &gap[gap.trim_end().len()..]
+            }
+            _ if self.text[container.span.start + 1..].starts_with(' ') => " ",
+            _ => "",
+        }
+    }
+
+    // Pretty values are indented with the text's unit, continuing at the
+    // indentation of the line they start on.
+    fn render(&self, value: &Jzon, indent: &str, pretty: bool) -> String {
+        if !pretty {
+            return value.stringify();
+        }
+        let text = format!("{}", value.styled().sort_keys(true).indent(1));
+        let mut out = String::new();
+        for (i, line) in text.lines().enumerate() {
+            if i > 0 {
+                let depth = line.len() - line.trim_start_matches(' ').len();
+                out.push('\n');
+                out += indent;
+                out += &self.unit.repeat(depth);
+                out += &line[depth..];
+            } else {
+                out += line;
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for Cst {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+// the indentation of the first indented line, two spaces if there is none
+fn indent_unit(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+        .find(|indent| !indent.is_empty())
+        .map_or(String::from("  "), |indent| {
+            match indent.starts_with('\t') {
+                true => String::from("\t"),
+                false => indent.to_string(),
+            }
+        })
+}
+
+fn lex(text: &str) -> Result<Vec<Token>> {
+    let bytes = text.as_bytes();
+    let mut tokens = vec![];
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let run = |pos: usize, f: fn(&u8) -> bool| {
+            bytes[pos..]
+                .iter()
+                .position(|b| !f(b))
+                .map_or(bytes.len(), |n| pos + n)
+        };
+        let kind = match bytes[pos] {
+            b' ' | b'\t' | b'\n' | b'\r' => {
+                pos = run(pos, |b| b" \t\n\r".contains(b));
+                TokenKind::Space
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                pos = run(pos, |b| *b != b'\n');
+                TokenKind::Comment
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                match text[pos + 2..].find("*/") {
+                    Some(n) => pos += n + 4,
+                    None => return Err(Syntax(ParseErr::ExpectNoneEOF, start)),
+                }
+                TokenKind::Comment
+            }
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => {
+                pos += 1;
+                TokenKind::Punct
+            }
+            b'"' => {
+                let mut escaped = false;
+                let close = bytes[pos + 1..].iter().position(|b| {
+                    let close = *b == b'"' && !escaped;
+                    escaped = *b == b'\\' && !escaped;
+                    close
+                });
+                match close {
+                    Some(n) => pos += n + 2,
+                    None => return Err(Syntax(ParseErr::ExpectNoneEOF, start)),
+                }
+                TokenKind::String
+            }
+            b'-' | b'0'..=b'9' => {
+                pos = run(pos, |b| b"+-.eE".contains(b) || b.is_ascii_digit());
+                TokenKind::Number
+            }
+            b'a'..=b'z' => {
+                pos = run(pos, u8::is_ascii_alphanumeric);
+                match &text[start..pos] {
+                    "true" | "false" | "null" => TokenKind::Literal,
+                    _ => return Err(Syntax(ParseErr::ExpectPrefix, start)),
+                }
+            }
+            _ => return Err(Syntax(ParseErr::ExpectPrefix, start)),
+        };
+        // scalars are checked by the real parser
+        if let TokenKind::String | TokenKind::Number = kind {
+            Jzon::parse(&bytes[start..pos]).map_err(|e| Syntax(e, start))?;
+        }
+        tokens.push(Token {
+            kind,
+            span: start..pos,
+        });
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: &'a [&'a Token],
+    next: usize,
+}
+
+impl Parser<'_> {
+    // the next token and its text
+    fn bump(&mut self) -> Result<(&Token, &str)> {
+        match self.tokens.get(self.next) {
+            Some(token) => {
+                self.next += 1;
+                Ok((token, &self.text[token.span.clone()]))
+            }
+            None => Err(Syntax(ParseErr::ExpectNoneEOF, self.text.len())),
+        }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens
+            .get(self.next)
+            .map(|t| &self.text[t.span.clone()])
+    }
+
+    fn node(&mut self) -> Result<Node> {
+        let (token, text) = self.bump()?;
+        let span = token.span.clone();
+        match (token.kind, text) {
+            (TokenKind::Punct, "[") => self.container(span.start, false),
+            (TokenKind::Punct, "{") => self.container(span.start, true),
+            (TokenKind::Punct, _) => Err(Syntax(ParseErr::ExpectValue, span.start)),
+            _ => Ok(Node {
+                span,
+                kind: Kind::Scalar,
+            }),
+        }
+    }
+
+    fn container(&mut self, open: usize, object: bool) -> Result<Node> {
+        let (closer, expect_comma) = match object {
+            true => ("}", ParseErr::ExpectCommaBrace),
+            false => ("]", ParseErr::ExpectCommaBracket),
+        };
+        let mut items = vec![];
+        if self.peek() == Some(closer) {
+            self.next += 1;
+        } else {
+            loop {
+                let key = if object {
+                    let (token, text) = self.bump()?;
+                    if token.kind != TokenKind::String {
+                        return Err(Syntax(ParseErr::ExpectPair, token.span.start));
+                    }
+                    let span = token.span.clone();
+                    let key = match Jzon::parse(text.as_bytes()) {
+                        Ok(Jzon::String(key)) => key,
+                        _ => unreachable!(),
+                    };
+                    let (token, text) = self.bump()?;
+                    if text != ":" {
+                        return Err(Syntax(ParseErr::ExpectColon, token.span.start));
+                    }
+                    Some((span, key))
+                } else {
+                    None
+                };
+                let value = self.node()?;
+                let (token, text) = self.bump()?;
+                let comma = match text {
+                    "," => Some(token.span.start),
+                    _ if text == closer => None,
+                    _ => return Err(Syntax(expect_comma, token.span.start)),
+                };
+                items.push(Item { key, value, comma });
+                if comma.is_none() {
+                    break;
+                }
+            }
+        }
+
+        let end = self.tokens[self.next - 1].span.end;
+        let kind = match object {
+            true => Kind::Object(items),
+            false => Kind::Array(items),
+        };
+        Ok(Node {
+            span: open..end,
+            kind,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"{
+  // service
+  "name": "web",
+  "ports": [80, 443], /* both */
+  "env": {
+    "debug": false
+  }
+}
+"#;
+
+    fn edit(f: impl FnOnce(&mut Cst) -> Result<()>) -> String {
+        let mut cst = Cst::parse(CONFIG).unwrap();
+        f(&mut cst).unwrap();
+        cst.to_string()
+    }
+
+    fn path(pointer: &str) -> Path {
+        Path::from_pointer(pointer).unwrap()
+    }
+
+    #[test]
+    fn lossless() {
+        let cst = Cst::parse(CONFIG).unwrap();
+        assert_eq!(CONFIG, cst.to_string());
+        let comments = cst.tokens().iter().filter(|t| t.kind == TokenKind::Comment);
+        assert_eq!(2, comments.count());
+        assert_eq!(cst.get(&path("/ports/1")).unwrap(), 443);
+        assert_eq!(cst.to_jzon()["env"]["debug"], false);
+
+        let text = "\t[ 1,2 , {\"a\" :[]} ]  ";
+        assert_eq!(text, Cst::parse(text).unwrap().to_string());
+    }
+
+    #[test]
+    fn syntax() {
+        assert!(matches!(
+            Cst::parse("[1 2]"),
+            Err(CstErr::Syntax(ParseErr::ExpectCommaBracket, 3))
+        ));
+        assert!(matches!(
+            Cst::parse("{\"a\": 1,}"),
+            Err(CstErr::Syntax(ParseErr::ExpectPair, 8))
+        ));
+        assert!(matches!(
+            Cst::parse("[1] /* open"),
+            Err(CstErr::Syntax(ParseErr::ExpectNoneEOF, 4))
+        ));
+        assert!(matches!(
+            Cst::parse("[\"\\x\"]"),
+            Err(CstErr::Syntax(ParseErr::ExpectEscaped, 1))
+        ));
+    }
+
+    #[test]
+    fn set() {
+        assert_eq!(
+            CONFIG.replace("443", "8443"),
+            edit(|cst| cst.set(&path("/ports/1"), &Jzon::Integer(8443)))
+        );
+        assert_eq!(
+            CONFIG.replace("false", "true"),
+            edit(|cst| cst.set(&path("/env/debug"), &Jzon::Bool(true)))
+        );
+        let tags = Jzon::Array(vec![Jzon::String("a".into())]);
+        assert_eq!(
+            CONFIG.replace("\"web\"", "[\n    \"a\"\n  ]"),
+            edit(|cst| cst.set(&path("/name"), &tags))
+        );
+    }
+
+    #[test]
+    fn insert() {
+        assert_eq!(
+            CONFIG.replace("[80", "[8080, 80"),
+            edit(|cst| cst.insert(&path("/ports/0"), &Jzon::Integer(8080)))
+        );
+        assert_eq!(
+            CONFIG.replace("443]", "443, 8443]"),
+            edit(|cst| cst.insert(&path("/ports/2"), &Jzon::Integer(8443)))
+        );
+        assert_eq!(
+            CONFIG.replace("false\n", "false,\n    \"level\": \"info\"\n"),
+            edit(|cst| cst.set(&path("/env/level"), &Jzon::String("info".into())))
+        );
+        assert_eq!(
+            CONFIG.replace("  }\n}", "  },\n  \"id\": 7\n}"),
+            edit(|cst| cst.insert(&path("/id"), &Jzon::Integer(7)))
+        );
+        let extra = Jzon::parse(br#"{"a": [1]}"#).unwrap();
+        assert_eq!(
+            CONFIG.replace(
+                "  }\n}",
+                "  },\n  \"extra\": {\n    \"a\": [\n      1\n    ]\n  }\n}"
+            ),
+            edit(|cst| cst.insert(&path("/extra"), &extra))
+        );
+
+        let mut cst = Cst::parse("[\n  1,\n  // two\n  2\n]").unwrap();
+        cst.insert(&path("/1"), &Jzon::Integer(5)).unwrap();
+        assert_eq!("[\n  1,\n  5,\n  // two\n  2\n]", cst.to_string());
+
+        let mut cst = Cst::parse("{\"a\": [], \"b\": [\n  ]}").unwrap();
+        cst.insert(&path("/a/0"), &Jzon::Integer(1)).unwrap();
+        cst.insert(&path("/b/0"), &Jzon::Integer(2)).unwrap();
+        assert_eq!("{\"a\": [1], \"b\": [\n    2\n  ]}", cst.to_string());
+    }
+
+    #[test]
+    fn remove() {
+        assert_eq!(
+            CONFIG.replace("  \"name\": \"web\",\n", ""),
+            edit(|cst| cst.remove(&path("/name")))
+        );
+        assert_eq!(
+            CONFIG.replace("[80, ", "["),
+            edit(|cst| cst.remove(&path("/ports/0")))
+        );
+        assert_eq!(
+            CONFIG.replace(", 443", ""),
+            edit(|cst| cst.remove(&path("/ports/1")))
+        );
+        assert_eq!(
+            "{\n  // service\n  \"name\": \"web\",\n  \"ports\": [80, 443] /* both */\n}\n",
+            edit(|cst| cst.remove(&path("/env")))
+        );
+        assert_eq!(
+            CONFIG.replace("\n    \"debug\": false\n", "\n"),
+            edit(|cst| cst.remove(&path("/env/debug")))
+        );
+    }
+
+    #[test]
+    fn rename() {
+        assert_eq!(
+            CONFIG.replace("\"ports\"", "\"listen\""),
+            edit(|cst| cst.rename_key(&path("/ports"), "listen"))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut cst = Cst::parse(CONFIG).unwrap();
+        assert!(matches!(cst.remove(&path("")), Err(ExpectNoneRoot)));
+        assert!(matches!(cst.remove(&path("/nope")), Err(ExpectPath)));
+        assert!(matches!(
+            cst.set(&path("/nope/x"), &Jzon::Null),
+            Err(ExpectPath)
+        ));
+        assert!(matches!(
+            cst.insert(&path("/ports/3"), &Jzon::Null),
+            Err(ExpectPath)
+        ));
+        assert!(matches!(
+            cst.insert(&path("/name"), &Jzon::Null),
+            Err(ExpectNewKey)
+        ));
+        assert!(matches!(
+            cst.rename_key(&path("/name"), "env"),
+            Err(ExpectNewKey)
+        ));
+        assert!(matches!(
+            cst.rename_key(&path("/ports/0"), "x"),
+            Err(ExpectMember)
+        ));
+        assert_eq!(CONFIG, cst.to_string());
+    }
+}
@@ -0,0 +1,381 @@
+use crate::jzon::Jzon;
+use std::collections::HashMap;
+use std::fmt;
+
+// How elements of two arrays are paired up before being compared.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ArrayMatch {
+    // position by position
+    Index,
+    // longest common subsequence, so insertions don't shift everything after
+    #[default]
+    Lcs,
+    // objects with equal values under this key, e.g. "id"
+    Key(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Path(pub Vec<Segment>);
+
+#[derive(Debug)]
+pub enum Change<'a> {
+    Added(Path, &'a Jzon),
+    Removed(Path, &'a Jzon),
+    Changed(Path, &'a Jzon, &'a Jzon),
+}
+
+// Beyond this many LCS table cells arrays are matched by index instead.
+const LCS_LIMIT: usize = 1 << 22;
+
+impl Jzon {
+    // Removed and changed paths index into `self`, added ones into `other`.
+    pub fn diff<'a>(&'a self, other: &'a Jzon, arrays: &ArrayMatch) -> Vec<Change<'a>> {
+        let mut changes = vec![];
+        let mut path = Path::default();
+        diff_value(self, other, arrays, &mut path, &mut changes);
+        changes
+    }
+}
+
+impl Change<'_> {
+    pub fn path(&self) -> &Path {
+        match self {
+            Change::Added(path, _) | Change::Removed(path, _) | Change::Changed(path, _, _) => path,
+        }
+    }
+}
+
+impl Path {
+    // Parses a JSON Pointer. Tokens made of digits become indices, which
+    // still match object keys wherever paths are looked up.
+    pub fn from_pointer(pointer: &str) -> Option<Path> {
+        if pointer.is_empty() {
+            return Some(Path::default());
+        }
+        let tokens = pointer.strip_prefix('/')?.split('/');
+        let segments = tokens.map(|t| {
+            let t = t.replace("~1", "/").replace("~0", "~");
+            let digits = !t.is_empty() && t.bytes().all(|b| b.is_ascii_digit());
+            match t.parse() {
+                Ok(i) if digits && (t == "0" || !t.starts_with('0')) => Segment::Index(i),
+                _ => Segment::Key(t),
+            }
+        });
+        Some(Path(segments.collect()))
+    }
+}
+
+impl Jzon {
+    // indices also match object keys, as everywhere else paths are used
+    pub fn get_path(&self, path: &Path) -> Option<&Jzon> {
+        path.0.iter().try_fold(self, |jz, seg| match (jz, seg) {
+            (Jzon::Object(map), Segment::Key(k)) => map.get(k),
+            (Jzon::Object(map), Segment::Index(i)) => map.get(&i.to_string()),
+            (Jzon::Array(vec), Segment::Index(i)) => vec.get(*i),
+            _ => None,
+        })
+    }
+
+    pub fn pointer(&self, pointer: &str) -> Option<&Jzon> {
+        self.get_path(&Path::from_pointer(pointer)?)
+    }
+}
+
+// formats as a JSON Pointer, RFC 6901, so the root is ""
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for seg in &self.0 {
+            match seg {
+                Segment::Key(k) => write!(f, "/{}", k.replace('~', "~0").replace('/', "~1"))?,
+                Segment::Index(i) => write!(f, "/{}", i)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn diff_value<'a>(
+    a: &'a Jzon,
+    b: &'a Jzon,
+    arrays: &ArrayMatch,
+    path: &mut Path,
+    changes: &mut Vec<Change<'a>>,
+) {
+    match (a, b) {
+        (Jzon::Object(x), Jzon::Object(y)) => {
+            let mut keys: Vec<&String> = x
+                .keys()
+                .chain(y.keys().filter(|k| !x.contains_key(k)))
+                .collect();
+            keys.sort();
+            for k in keys {
+                path.0.push(Segment::Key(k.clone()));
+                match (x.get(k), y.get(k)) {
+                    (Some(v), Some(w)) => diff_value(v, w, arrays, path, changes),
+                    (Some(v), None) => changes.push(Change::Removed(path.clone(), v)),
+                    (None, Some(w)) => changes.push(Change::Added(path.clone(), w)),
+                    (None, None) => unreachable!(),
+                }
+                path.0.pop();
+            }
+        }
+        (Jzon::Array(x), Jzon::Array(y)) => match arrays {
+            ArrayMatch::Index => diff_by_index(x, y, 0, 0, arrays, path, changes),
+            ArrayMatch::Lcs => diff_by_lcs(x, y, arrays, path, changes),
+            ArrayMatch::Key(key) => diff_by_key(x, y, key, arrays, path, changes),
+        },
+        _ if a == b => (),
+        _ => changes.push(Change::Changed(path.clone(), a, b)),
+    }
+}
+
+// `x` and `y` start at `i` and `j` of their arrays
+fn diff_by_index<'a>(
+    x: &'a [Jzon],
+    y: &'a [Jzon],
+    i: usize,
+    j: usize,
+    arrays: &ArrayMatch,
+    path: &mut Path,
+    changes: &mut Vec<Change<'a>>,
+) {
+    for (k, (v, w)) in x.iter().zip(y).enumerate() {
+        path.0.push(Segment::Index(i + k));
+        diff_value(v, w, arrays, path, changes);
+        path.0.pop();
+    }
+    for (k, v) in x.iter().enumerate().skip(y.len()) {
+        path.0.push(Segment::Index(i + k));
+        changes.push(Change::Removed(path.clone(), v));
+        path.0.pop();
+    }
+    for (k, w) in y.iter().enumerate().skip(x.len()) {
+        path.0.push(Segment::Index(j + k));
+        changes.push(Change::Added(path.clone(), w));
+        path.0.pop();
+    }
+}
+
+fn diff_by_lcs<'a>(
+    x: &'a [Jzon],
+    y: &'a [Jzon],
+    arrays: &ArrayMatch,
+    path: &mut Path,
+    changes: &mut Vec<Change<'a>>,
+) {
+    let prefix = x.iter().zip(y).take_while(|(v, w)| v == w).count();
+    let (x, y) = (&x[prefix..], &y[prefix..]);
+    let suffix = x
+        .iter()
+        .rev()
+        .zip(y.iter().rev())
+        .take_while(|(v, w)| v == w)
+        .count();
+    let (x, y) = (&x[..x.len() - suffix], &y[..y.len() - suffix]);
+    let (n, m) = (x.len(), y.len());
+
+    if n == 0 || m == 0 || n * m > LCS_LIMIT {
+        return diff_by_index(x, y, prefix, prefix, arrays, path, changes);
+    }
+
+    // lcs[i][j] is the LCS length of x[i..] and y[j..]
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if x[i] == y[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // unmatched runs between common elements are compared position by
+    // position, so an edited object reports its inner changes
+    let (mut i, mut j) = (0, 0);
+    let (mut gap_i, mut gap_j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && x[i] == y[j] {
+            diff_by_index(
+                &x[gap_i..i],
+                &y[gap_j..j],
+                prefix + gap_i,
+                prefix + gap_j,
+                arrays,
+                path,
+                changes,
+            );
+            i += 1;
+            j += 1;
+            gap_i = i;
+            gap_j = j;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            j += 1;
+        } else {
+            i += 1;
+        }
+    }
+    diff_by_index(
+        &x[gap_i..],
+        &y[gap_j..],
+        prefix + gap_i,
+        prefix + gap_j,
+        arrays,
+        path,
+        changes,
+    );
+}
+
+fn diff_by_key<'a>(
+    x: &'a [Jzon],
+    y: &'a [Jzon],
+    key: &str,
+    arrays: &ArrayMatch,
+    path: &mut Path,
+    changes: &mut Vec<Change<'a>>,
+) {
+    fn id_of(v: &Jzon, key: &str) -> Option<String> {
+        match v {
+            Jzon::Object(map) => map.get(key).map(|id| id.stringify()),
+            _ => None,
+        }
+    }
+
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    for (j, w) in y.iter().enumerate() {
+        if let Some(id) = id_of(w, key) {
+            ids.entry(id).or_insert(j);
+        }
+    }
+
+    // elements without the key are paired up in order of appearance
+    let mut matched = vec![false; y.len()];
+    let mut keyless = (0..y.len()).filter(|j| id_of(&y[*j], key).is_none());
+    for (i, v) in x.iter().enumerate() {
+        let j = match id_of(v, key) {
+            Some(id) => ids.get(&id).copied().filter(|j| !matched[*j]),
+            None => keyless.next(),
+        };
+        path.0.push(Segment::Index(i));
+        match j {
+            Some(j) => {
+                matched[j] = true;
+                diff_value(v, &y[j], arrays, path, changes);
+            }
+            None => changes.push(Change::Removed(path.clone(), v)),
+        }
+        path.0.pop();
+    }
+    for (j, w) in y.iter().enumerate().filter(|(j, _)| !matched[*j]) {
+        path.0.push(Segment::Index(j));
+        changes.push(Change::Added(path.clone(), w));
+        path.0.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(a: &str, b: &str, arrays: ArrayMatch) -> Vec<String> {
+        let a = Jzon::parse(a.as_bytes()).unwrap();
+        let b = Jzon::parse(b.as_bytes()).unwrap();
+        a.diff(&b, &arrays)
+            .iter()
+            .map(|c| match c {
+                Change::Added(p, v) => format!("+{} {}", p, v),
+                Change::Removed(p, v) => format!("-{} {}", p, v),
+                Change::Changed(p, v, w) => format!("~{} {} {}", p, v, w),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pointers() {
+        for pointer in ["", "/a~1b/m~0n/0/10/01/", "/-1"] {
+            assert_eq!(pointer, Path::from_pointer(pointer).unwrap().to_string());
+        }
+        assert_eq!(
+            vec![Segment::Key("01".into()), Segment::Index(2)],
+            Path::from_pointer("/01/2").unwrap().0
+        );
+        assert!(Path::from_pointer("a").is_none());
+
+        let doc = Jzon::parse(br#"{"a": [{"b/c": 1}], "0": {"1": 2}}"#).unwrap();
+        assert_eq!(Some(&Jzon::Integer(1)), doc.pointer("/a/0/b~1c"));
+        assert_eq!(Some(&Jzon::Integer(2)), doc.pointer("/0/1"));
+        assert_eq!(Some(&doc), doc.pointer(""));
+        assert_eq!(None, doc.pointer("/a/1"));
+        assert_eq!(None, doc.pointer("/a/b"));
+        assert_eq!(None, doc.pointer("a"));
+    }
+
+    #[test]
+    fn objects() {
+        let a = r#"{"name": "web", "port": 80, "tls": false, "env": {"a/b": 1, "m~n": 2}}"#;
+        let b = r#"{"name": "web", "port": 8080, "replicas": 3, "env": {"a/b": 1, "m~n": "2"}}"#;
+        assert_eq!(
+            vec![
+                "~/env/m~0n 2 \"2\"",
+                "~/port 80 8080",
+                "+/replicas 3",
+                "-/tls false"
+            ],
+            report(a, b, ArrayMatch::Lcs)
+        );
+        assert!(report(a, a, ArrayMatch::Lcs).is_empty());
+        assert_eq!(
+            vec!["~/a 1 [1]"],
+            report(r#"{"a": 1}"#, r#"{"a": [1]}"#, ArrayMatch::Lcs)
+        );
+    }
+
+    #[test]
+    fn arrays_by_index() {
+        assert_eq!(
+            vec!["~/0 1 0", "~/1 2 1", "~/2 3 2", "+/3 3"],
+            report("[1, 2, 3]", "[0, 1, 2, 3]", ArrayMatch::Index)
+        );
+        assert_eq!(
+            vec!["-/2 3"],
+            report("[1, 2, 3]", "[1, 2]", ArrayMatch::Index)
+        );
+    }
+
+    #[test]
+    fn arrays_by_lcs() {
+        assert_eq!(
+            vec!["+/0 0"],
+            report("[1, 2, 3]", "[0, 1, 2, 3]", ArrayMatch::Lcs)
+        );
+        assert_eq!(
+            vec!["-/1 2", "+/2 4"],
+            report("[1, 2, 3]", "[1, 3, 4]", ArrayMatch::Lcs)
+        );
+        assert_eq!(
+            vec!["~/1/v 2 20"],
+            report(
+                r#"[{"v": 1}, {"v": 2}, {"v": 3}]"#,
+                r#"[{"v": 1}, {"v": 20}, {"v": 3}]"#,
+                ArrayMatch::Lcs
+            )
+        );
+    }
+
+    #[test]
+    fn arrays_by_key() {
+        let a = r#"[{"id": 1, "v": "a"}, {"id": 2, "v": "b"}, {"id": 3, "v": "c"}]"#;
+        let b = r#"[{"id": 3, "v": "c"}, {"id": 1, "v": "A"}, {"id": 4, "v": "d"}]"#;
+        let changes = report(a, b, ArrayMatch::Key("id".into()));
+        assert_eq!(3, changes.len());
+        assert_eq!("~/0/v \"a\" \"A\"", changes[0]);
+        assert!(changes[1].starts_with("-/1 "));
+        assert!(changes[2].starts_with("+/2 "));
+    }
+}
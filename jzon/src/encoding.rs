@@ -0,0 +1,217 @@
+use crate::jzon::{Jzon, ParseErr};
+use std::borrow::Cow;
+use std::char;
+use std::result;
+use std::str;
+
+// Text in any of the encodings RFC 4627 §3 allows. The encoding comes from
+// a byte order mark if there is one, and otherwise from where the first
+// four bytes are zero, since JSON text starts with ASCII.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+// what to do with a UTF-8 byte order mark; the UTF-16 and UTF-32 ones are
+// always fine, as they are how those get told apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Bom {
+    #[default]
+    Accept,
+    Reject,
+}
+
+// offsets count bytes of the input, byte order mark included
+#[derive(Debug)]
+pub enum EncodingErr {
+    // a UTF-8 byte order mark with `Bom::Reject`
+    ExpectNoneBom,
+    // an invalid sequence, unpaired surrogate or out of range code point
+    ExpectCodePoint(usize),
+    // the input ends inside a code unit or sequence
+    ExpectNoneEOF(usize),
+    // the decoded text isn't JSON
+    Syntax(ParseErr),
+}
+
+type Result<T> = result::Result<T, EncodingErr>;
+
+use EncodingErr::*;
+
+impl Jzon {
+    pub fn parse_encoded(bytes: &[u8], bom: Bom) -> Result<Jzon> {
+        Jzon::parse(decode(bytes, bom)?.as_bytes()).map_err(Syntax)
+    }
+}
+
+// the encoding and the length of its byte order mark, 0 if there is none
+pub fn detect(bytes: &[u8]) -> (Encoding, usize) {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => (Encoding::Utf8, 3),
+        // before UTF-16LE, whose mark starts the same
+        [0xFF, 0xFE, 0, 0, ..] => (Encoding::Utf32Le, 4),
+        [0, 0, 0xFE, 0xFF, ..] => (Encoding::Utf32Be, 4),
+        [0xFF, 0xFE, ..] => (Encoding::Utf16Le, 2),
+        [0xFE, 0xFF, ..] => (Encoding::Utf16Be, 2),
+        [0, 0, 0, _, ..] => (Encoding::Utf32Be, 0),
+        [_, 0, 0, 0, ..] => (Encoding::Utf32Le, 0),
+        [0, _, ..] => (Encoding::Utf16Be, 0),
+        [_, 0, ..] => (Encoding::Utf16Le, 0),
+        _ => (Encoding::Utf8, 0),
+    }
+}
+
+// UTF-8 input without a byte order mark is borrowed
+pub fn decode(bytes: &[u8], bom: Bom) -> Result<Cow<'_, str>> {
+    let (encoding, start) = detect(bytes);
+    if encoding == Encoding::Utf8 && start > 0 && bom == Bom::Reject {
+        return Err(ExpectNoneBom);
+    }
+    let body = &bytes[start..];
+    match encoding {
+        Encoding::Utf8 => match str::from_utf8(body) {
+            Ok(s) => Ok(Cow::Borrowed(s)),
+            Err(e) if e.error_len().is_none() => Err(ExpectNoneEOF(start + e.valid_up_to())),
+            Err(e) => Err(ExpectCodePoint(start + e.valid_up_to())),
+        },
+        Encoding::Utf16Le => utf16(body, start, u16::from_le_bytes),
+        Encoding::Utf16Be => utf16(body, start, u16::from_be_bytes),
+        Encoding::Utf32Le => utf32(body, start, u32::from_le_bytes),
+        Encoding::Utf32Be => utf32(body, start, u32::from_be_bytes),
+    }
+}
+
+fn utf16(body: &[u8], start: usize, unit: fn([u8; 2]) -> u16) -> Result<Cow<'static, str>> {
+    let n = body.len() / 2;
+    let at = |i: usize| unit([body[2 * i], body[2 * i + 1]]) as u32;
+    let mut s = String::with_capacity(n);
+    let mut i = 0;
+    while i < n {
+        let pos = start + 2 * i;
+        let c = match at(i) {
+            0xD800..=0xDBFF if i + 1 == n => return Err(ExpectNoneEOF(pos)),
+            hi @ 0xD800..=0xDBFF => match at(i + 1) {
+                lo @ 0xDC00..=0xDFFF => {
+                    i += 1;
+                    0x10000 + ((hi - 0xD800) << 10 | (lo - 0xDC00))
+                }
+                _ => return Err(ExpectCodePoint(pos)),
+            },
+            0xDC00..=0xDFFF => return Err(ExpectCodePoint(pos)),
+            c => c,
+        };
+        s.push(char::from_u32(c).unwrap());
+        i += 1;
+    }
+    if !body.len().is_multiple_of(2) {
+        return Err(ExpectNoneEOF(start + 2 * n));
+    }
+    Ok(Cow::Owned(s))
+}
+
+fn utf32(body: &[u8], start: usize, unit: fn([u8; 4]) -> u32) -> Result<Cow<'static, str>> {
+    let chunks = body.chunks_exact(4);
+    let rest = chunks.remainder().len();
+    let mut s = String::with_capacity(body.len() / 4);
+    for (i, c) in chunks.enumerate() {
+        match char::from_u32(unit([c[0], c[1], c[2], c[3]])) {
+            Some(c) => s.push(c),
+            None => return Err(ExpectCodePoint(start + 4 * i)),
+        }
+    }
+    if rest > 0 {
+        return Err(ExpectNoneEOF(start + body.len() - rest));
+    }
+    Ok(Cow::Owned(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(s: &str) -> Vec<u8> {
+        s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    fn utf32be(s: &str) -> Vec<u8> {
+        s.chars().flat_map(|c| (c as u32).to_be_bytes()).collect()
+    }
+
+    #[test]
+    fn detect_and_decode() {
+        let text = r#"{"k": ["é", "𐐷"]}"#;
+        let expected = Jzon::parse(text.as_bytes()).unwrap();
+        let bom = |mark: &[u8], body: Vec<u8>| [mark, &body].concat();
+        let inputs = [
+            (text.as_bytes().to_vec(), Encoding::Utf8, 0),
+            (bom(&[0xEF, 0xBB, 0xBF], text.into()), Encoding::Utf8, 3),
+            (utf16le(text), Encoding::Utf16Le, 0),
+            (bom(&[0xFF, 0xFE], utf16le(text)), Encoding::Utf16Le, 2),
+            (
+                text.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+                Encoding::Utf16Be,
+                0,
+            ),
+            (utf32be(text), Encoding::Utf32Be, 0),
+            (
+                bom(&[0, 0, 0xFE, 0xFF], utf32be(text)),
+                Encoding::Utf32Be,
+                4,
+            ),
+            (
+                text.chars()
+                    .flat_map(|c| (c as u32).to_le_bytes())
+                    .collect(),
+                Encoding::Utf32Le,
+                0,
+            ),
+        ];
+        for (bytes, encoding, mark) in &inputs {
+            assert_eq!((*encoding, *mark), detect(bytes));
+            assert_eq!(expected, Jzon::parse_encoded(bytes, Bom::Accept).unwrap());
+        }
+        // a scalar is shorter than four bytes in UTF-16
+        assert_eq!(
+            Jzon::Integer(7),
+            Jzon::parse_encoded(&utf16le("7"), Bom::Accept).unwrap()
+        );
+        assert!(matches!(
+            decode(text.as_bytes(), Bom::Reject),
+            Ok(Cow::Borrowed(_))
+        ));
+        assert!(matches!(
+            decode(&inputs[1].0, Bom::Reject),
+            Err(ExpectNoneBom)
+        ));
+        assert!(decode(&inputs[3].0, Bom::Reject).is_ok());
+    }
+
+    #[test]
+    fn errors() {
+        let err = |bytes: &[u8]| Jzon::parse_encoded(bytes, Bom::Accept).err().unwrap();
+
+        assert!(matches!(err(b"[\"\xFF\"]"), ExpectCodePoint(2)));
+        assert!(matches!(err(b"\xEF\xBB\xBF[\"\xC3"), ExpectNoneEOF(5)));
+        assert!(matches!(err(b"[1,]"), Syntax(ParseErr::ExpectPrefix)));
+        // a lone low surrogate, a high one followed by no low one, and one
+        // cut off at the end
+        let mut bytes = utf16le("[\"");
+        bytes.extend([0x00, 0xDC, 0x22, 0x00]);
+        assert!(matches!(err(&bytes), ExpectCodePoint(4)));
+        bytes.splice(4..6, [0x00, 0xD8]);
+        assert!(matches!(err(&bytes), ExpectCodePoint(4)));
+        bytes.truncate(6);
+        assert!(matches!(err(&bytes), ExpectNoneEOF(4)));
+        bytes.truncate(5);
+        assert!(matches!(err(&bytes), ExpectNoneEOF(4)));
+        let mut bytes = utf32be("[1]");
+        bytes[5] = 0x11;
+        assert!(matches!(err(&bytes), ExpectCodePoint(4)));
+        assert!(matches!(err(&utf32be("[1]")[..11]), ExpectNoneEOF(8)));
+    }
+}
@@ -0,0 +1,955 @@
+#![allow(clippy::missing_safety_doc)]
+
+use crate::jzon::{Jzon, ParseErr};
+use crate::map::Map;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::slice;
+use std::str;
+
+// The C API, declared in include/jzon.h, which the tests below write and
+// which also says who owns what. Values are handed out as pointers to
+// `Jzon`: the ones returned by `jzon_parse`, `jzon_new_*` and `jzon_clone`
+// are owned by the caller, the ones found inside them are borrowed and stay
+// valid until their root is freed or changed. Build the shared library with
+// `cargo rustc --release --lib --crate-type cdylib`, see capi/Makefile.
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Ok = 0,
+    // the text isn't JSON, `Error` tells where
+    Syntax,
+    // a string or key given isn't UTF-8
+    Utf8,
+    // the value isn't of the type asked for
+    Type,
+    // the output needs more room than given
+    Buffer,
+    // a pointer that mustn't be null is
+    Null,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Type {
+    Null,
+    Bool,
+    Integer,
+    Double,
+    String,
+    Array,
+    Object,
+}
+
+// Where parsing stopped. The offset counts bytes from 0, line and column
+// start at 1 and the column counts chars.
+#[repr(C)]
+pub struct Error {
+    pub status: Status,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    // static and NUL terminated
+    pub message: *const c_char,
+}
+
+pub const PRETTY: u32 = 1;
+pub const SORT_KEYS: u32 = 2;
+
+// what `jzon_iter` walks; keys are null for array elements
+pub struct Members {
+    entries: Vec<(*const String, *const Jzon)>,
+    next: usize,
+}
+
+fn text(bytes: &'static [u8]) -> *const c_char {
+    bytes.as_ptr() as *const c_char
+}
+
+fn message(err: ParseErr) -> *const c_char {
+    use ParseErr::*;
+    text(match err {
+        ExpectPair => b"expected a member\0",
+        ExpectBool => b"expected true or false\0",
+        ExpectNull => b"expected null\0",
+        ExpectColon => b"expected ':'\0",
+        ExpectValue => b"expected a value\0",
+        ExpectQuote => b"expected '\"'\0",
+        ExpectDigit => b"expected a digit\0",
+        ExpectNoMore => b"expected the end of the input\0",
+        ExpectPrefix => b"expected a value\0",
+        ExpectNoneEOF => b"unexpected end of the input\0",
+        ExpectEscaped => b"invalid escape\0",
+        ExpectHexDigit => b"expected a hex digit\0",
+        ExpectCodePoint => b"invalid code point\0",
+        ExpectCommaBrace => b"expected ',' or '}'\0",
+        ExpectNoneControl => b"unescaped control character\0",
+        ExpectCommaBracket => b"expected ',' or ']'\0",
+        ExpectShallow => b"nested too deeply\0",
+    })
+}
+
+unsafe fn key<'a>(key: *const c_char) -> Result<&'a str, Status> {
+    if key.is_null() {
+        return Err(Status::Null);
+    }
+    CStr::from_ptr(key).to_str().map_err(|_| Status::Utf8)
+}
+
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Result<&'a [u8], Status> {
+    match ptr {
+        _ if len == 0 => Ok(&[]),
+        p if p.is_null() => Err(Status::Null),
+        p => Ok(slice::from_raw_parts(p, len)),
+    }
+}
+
+// the line and column of the byte at `offset`
+fn position(bytes: &[u8], offset: usize) -> (usize, usize) {
+    let before = &bytes[..offset];
+    let start = before
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |i| i + 1);
+    let line = before.iter().filter(|b| **b == b'\n').count() + 1;
+    let column = String::from_utf8_lossy(&before[start..]).chars().count() + 1;
+    (line, column)
+}
+
+fn owned(jz: Jzon) -> *mut Jzon {
+    Box::into_raw(Box::new(jz))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn jzon_parse(
+    text: *const u8,
+    len: usize,
+    out: *mut *mut Jzon,
+    err: *mut Error,
+) -> Status {
+    let bytes = match bytes(text, len) {
+        Ok(bytes) if !out.is_null() => bytes,
+        _ => return Status::Null,
+    };
+    match Jzon::parse_with_offset(bytes) {
+        Ok(jz) => {
+            *out = owned(jz);
+            Status::Ok
+        }
+        Err((e, offset)) => {
+            *out = ptr::null_mut();
+            if let Some(err) = err.as_mut() {
+                let (line, column) = position(bytes, offset);
+                *err = Error {
+                    status: Status::Syntax,
+                    offset,
+                    line,
+                    column,
+                    message: message(e),
+                };
+            }
+            Status::Syntax
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn jzon_free(jz: *mut Jzon) {
+    if !jz.is_null() {
+        drop(Box::from_raw(jz));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn jzon_clone(jz: *const Jzon) -> *mut Jzon {
+    match jz.as_ref() {
+        Some(jz) => owned(jz.clone()),
+        None => ptr::null_mut(),
+    }
+}
+
+const STATUSES: [Status; 6] = [
+    Status::Ok,
+    Status::Syntax,
+    Status::Utf8,
+    Status::Type,
+    Status::Buffer,
+    Status::Null,
+];
+
+// an int rather than a `Status`, which C may hand over with any value
+#[no_mangle]
+pub extern "C" fn jzon_status_str(status: c_int) -> *const c_char {
+    text(match STATUSES.iter().find(|s| **s as c_int == status) {
+        Some(Status::Ok) => b"ok\0",
+        Some(Status::Syntax) => b"syntax error\0",
+        Some(Status::Utf8) => b"invalid UTF-8\0",
+        Some(Status::Type) => b"wrong type\0",
+        Some(Status::Buffer) => b"buffer too small\0",
+        Some(Status::Null) => b"null pointer\0",
+        None => b"unknown status\0",
+    })
+}
+
+// values `parse_tolerant` couldn't parse print as null, and read as one
+#[no_mangle]
+pub unsafe extern "C" fn jzon_type_of(jz: *const Jzon) -> Type {
+    match jz.as_ref() {
+        Some(Jzon::Bool(_)) => Type::Bool,
+        Some(Jzon::Integer(_)) => Type::Integer,
+        Some(Jzon::Double(_)) => Type::Double,
+        Some(Jzon::String(_)) => Type::String,
+        Some(Jzon::Array(_)) => Type::Array,
+        Some(Jzon::Object(_)) => Type::Object,
+        Some(Jzon::Null) | Some(Jzon::Error) | None => Type::Null,
+    }
+}
+
+unsafe fn get<T>(jz: *const Jzon, out: *mut T, read: impl FnOnce(&Jzon) -> Option<T>) -> Status {
+    match (jz.as_ref(), out.is_null()) {
+        (Some(jz), false) => match read(jz) {
+            Some(v) => {
+                *out = v;
+                Status::Ok
+            }
+            None => Status::Type,
+        },
+        _ => Status::Null,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn jzon_get_bool(jz: *const Jzon, out: *mut bool) -> Status {
+    get(jz, out, |jz| match jz {
+        Jzon::Bool(v) => Some(*v),
+        _ => None,
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn jzon_get_int(jz: *const Jzon, out: *mut i64) -> Status {
+    get(jz, out, |jz| match jz {
+        Jzon::Integer(v) => Some(*v),
+        _ => None,
+    })
+}
+
+// integers too, rounded where they have to be
+#[no_mangle]
+pub unsafe extern "C" fn jzon_get_double(jz: *const Jzon, out: *mut f64) -> Status {
+    get(jz, out, |jz| match jz {
+        Jzon::Integer(v) => Some(*v as f64),
+        Jzon::Double(v) => Some(*v),
+        _ => None,
+    })
+}
+
+// UTF-8 without a terminating NUL, as strings may hold NULs
+#[no_mangle]
+pub unsafe extern "C" fn jzon_get_string(
+    jz: *const Jzon,
+    out: *mut *const c_char,
+    len: *mut usize,
+) -> Status {
+    if len.is_null() {
+        return Status::Null;
+    }
+    get(jz, out, |jz| match jz {
+        Jzon::String(s) => {
+            *len = s.len();
+            Some(s.as_ptr() as *const c_char)
+        }
+        _ => None,
+    })
+}
+
+// members of an object or elements of an array, 0 for anything else
+#[no_mangle]
+pub unsafe extern "C" fn jzon_len(jz: *const Jzon) -> usize {
+    match jz.as_ref() {
+        Some(Jzon::Object(map)) => map.len(),
+        Some(Jzon::Array(vec)) => vec.len(),
+        _ => 0,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn jzon_index(jz: *const Jzon, idx: usize) -> *const Jzon {
+    match jz.as_ref() {
+        Some(Jzon::Array(vec)) => vec.get(idx).map_or(ptr::null(), |v| v as *const Jzon),
+        _ => ptr::null(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn jzon_member(jz: *const Jzon, name: *const c_char) -> *const Jzon {
+    match (jz.as_ref(), key(name)) {
+        (Some(Jzon::Object(map)), Ok(name)) => {
+            map.get(name).map_or(ptr::null(), |v| v as *const Jzon)
+        }
+        _ => ptr::null(),
+    }
+}
+
+// a JSON Pointer like "/a/0/b", null if it points at nothing
+#[no_mangle]
+pub unsafe extern "C" fn jzon_pointer(jz: *const Jzon, pointer: *const c_char) -> *const Jzon {
+    let found = match (jz.as_ref(), key(pointer)) {
+        (Some(jz), Ok(pointer)) => jz.pointer(pointer),
+        _ => None,
+    };
+    found.map_or(ptr::null(), |v| v as *const Jzon)
+}
+
+// objects are walked in key order, null for anything but objects and
+// arrays
+#[no_mangle]
+pub unsafe extern "C" fn jzon_iter_new(jz: *const Jzon) -> *mut Members {
+    let entries = match jz.as_ref() {
+        Some(Jzon::Object(map)) => map
+            .iter()
+            .map(|(k, v)| (k as *const String, v as *const Jzon))
+            .collect(),
+        Some(Jzon::Array(vec)) => vec
+            .iter()
+            .map(|v| (ptr::null(), v as *const Jzon))
+            .collect(),
+        _ => return ptr::null_mut(),
+    };
+    Box::into_raw(Box::new(Members { entries, next: 0 }))
+}
+
+// False once there is nothing left. Keys aren't NUL terminated, so they
+// come with their length, and are null for array elements. Any of the
+// outputs may be null if it isn't wanted.
+#[no_mangle]
+pub unsafe extern "C" fn jzon_iter_next(
+    it: *mut Members,
+    key: *mut *const c_char,
+    key_len: *mut usize,
+    value: *mut *const Jzon,
+) -> bool {
+    let it = match it.as_mut() {
+        Some(it) => it,
+        None => return false,
+    };
+    let (k, v) = match it.entries.get(it.next) {
+        Some(entry) => *entry,
+        None => return false,
+    };
+    it.next += 1;
+    let (k, len) = match k.as_ref() {
+        Some(k) => (k.as_ptr() as *const c_char, k.len()),
+        None => (ptr::null(), 0),
+    };
+    if let Some(key) = key.as_mut() {
+        *key = k;
+    }
+    if let Some(key_len) = key_len.as_mut() {
+        *key_len = len;
+    }
+    if let Some(value) = value.as_mut() {
+        *value = v;
+    }
+    true
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn jzon_iter_free(it: *mut Members) {
+    if !it.is_null() {
+        drop(Box::from_raw(it));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn jzon_new_null() -> *mut Jzon {
+    owned(Jzon::Null)
+}
+
+#[no_mangle]
+pub extern "C" fn jzon_new_bool(v: bool) -> *mut Jzon {
+    owned(Jzon::Bool(v))
+}
+
+#[no_mangle]
+pub extern "C" fn jzon_new_int(v: i64) -> *mut Jzon {
+    owned(Jzon::Integer(v))
+}
+
+// null for NaN and the infinities
+#[no_mangle]
+pub extern "C" fn jzon_new_double(v: f64) -> *mut Jzon {
+    if v.is_finite() {
+        owned(Jzon::Double(v))
+    } else {
+        ptr::null_mut()
+    }
+}
+
+// null unless the bytes are UTF-8
+#[no_mangle]
+pub unsafe extern "C" fn jzon_new_string(s: *const c_char, len: usize) -> *mut Jzon {
+    match bytes(s as *const u8, len).map(str::from_utf8) {
+        Ok(Ok(s)) => owned(Jzon::String(s.to_string())),
+        _ => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn jzon_new_array() -> *mut Jzon {
+    owned(Jzon::Array(vec![]))
+}
+
+#[no_mangle]
+pub extern "C" fn jzon_new_object() -> *mut Jzon {
+    owned(Jzon::Object(Map::new()))
+}
+
+// takes `value` over when it returns `JZON_OK`, and only then
+#[no_mangle]
+pub unsafe extern "C" fn jzon_push(array: *mut Jzon, value: *mut Jzon) -> Status {
+    match (array.as_mut(), value.is_null()) {
+        (Some(Jzon::Array(vec)), false) => {
+            vec.push(*Box::from_raw(value));
+            Status::Ok
+        }
+        (Some(_), false) => Status::Type,
+        _ => Status::Null,
+    }
+}
+
+// as `jzon_push`, replacing any member with the same name
+#[no_mangle]
+pub unsafe extern "C" fn jzon_insert(
+    object: *mut Jzon,
+    name: *const c_char,
+    value: *mut Jzon,
+) -> Status {
+    let name = match key(name) {
+        Ok(name) => name,
+        Err(status) => return status,
+    };
+    match (object.as_mut(), value.is_null()) {
+        (Some(Jzon::Object(map)), false) => {
+            map.insert(name.to_string(), *Box::from_raw(value));
+            Status::Ok
+        }
+        (Some(_), false) => Status::Type,
+        _ => Status::Null,
+    }
+}
+
+// Like snprintf: the text is written with a NUL after it when it fits in
+// `cap` bytes, and `len` gets its length without the NUL either way, so a
+// null buffer of 0 bytes asks for the size. `flags` takes `JZON_PRETTY`
+// and `JZON_SORT_KEYS`.
+#[no_mangle]
+pub unsafe extern "C" fn jzon_write(
+    jz: *const Jzon,
+    flags: u32,
+    buf: *mut c_char,
+    cap: usize,
+    len: *mut usize,
+) -> Status {
+    let jz = match jz.as_ref() {
+        Some(jz) if cap == 0 || !buf.is_null() => jz,
+        _ => return Status::Null,
+    };
+    let mut styled = jz.styled().sort_keys(flags & SORT_KEYS != 0);
+    if flags & PRETTY != 0 {
+        styled = styled.indent(2);
+    }
+    let text = styled.to_string();
+    if let Some(len) = len.as_mut() {
+        *len = text.len();
+    }
+    if text.len() >= cap {
+        return Status::Buffer;
+    }
+    ptr::copy_nonoverlapping(text.as_ptr(), buf as *mut u8, text.len());
+    *buf.add(text.len()) = 0;
+    Status::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::ffi::CString;
+    use std::fs;
+
+    fn parse(text: &str) -> *mut Jzon {
+        let mut jz = ptr::null_mut();
+        let status = unsafe { jzon_parse(text.as_ptr(), text.len(), &mut jz, ptr::null_mut()) };
+        assert_eq!(Status::Ok, status);
+        jz
+    }
+
+    fn string(jz: *const Jzon) -> String {
+        let (mut s, mut len) = (ptr::null(), 0);
+        assert_eq!(Status::Ok, unsafe { jzon_get_string(jz, &mut s, &mut len) });
+        let bytes = unsafe { slice::from_raw_parts(s as *const u8, len) };
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn write(jz: *const Jzon, flags: u32) -> String {
+        let mut len = 0;
+        let status = unsafe { jzon_write(jz, flags, ptr::null_mut(), 0, &mut len) };
+        assert_eq!(Status::Buffer, status);
+        let mut buf = vec![0u8; len + 1];
+        let status = unsafe {
+            jzon_write(
+                jz,
+                flags,
+                buf.as_mut_ptr() as *mut c_char,
+                len + 1,
+                &mut len,
+            )
+        };
+        assert_eq!(Status::Ok, status);
+        assert_eq!(0, buf.pop().unwrap());
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn read() {
+        let doc = parse(r#"{"a": [1, 2.5, "x\u0000y"], "b/c": {"d": true}, "n": null}"#);
+        let c = |s: &str| CString::new(s).unwrap();
+        unsafe {
+            assert_eq!(Type::Object, jzon_type_of(doc));
+            assert_eq!(3, jzon_len(doc));
+            let a = jzon_member(doc, c("a").as_ptr());
+            assert_eq!(Type::Array, jzon_type_of(a));
+            let (mut i, mut d, mut b) = (0, 0.0, false);
+            assert_eq!(Status::Ok, jzon_get_int(jzon_index(a, 0), &mut i));
+            assert_eq!(1, i);
+            assert_eq!(Status::Ok, jzon_get_double(jzon_index(a, 0), &mut d));
+            assert_eq!(1.0, d);
+            assert_eq!(Status::Type, jzon_get_int(jzon_index(a, 1), &mut i));
+            assert_eq!("x\0y", string(jzon_index(a, 2)));
+            assert!(jzon_index(a, 3).is_null());
+
+            let d = jzon_pointer(doc, c("/b~1c/d").as_ptr());
+            assert_eq!(Status::Ok, jzon_get_bool(d, &mut b));
+            assert!(b);
+            assert_eq!(doc as *const Jzon, jzon_pointer(doc, c("").as_ptr()));
+            assert!(jzon_pointer(doc, c("/a/7").as_ptr()).is_null());
+            assert!(jzon_pointer(doc, c("a").as_ptr()).is_null());
+            assert_eq!(Type::Null, jzon_type_of(jzon_member(doc, c("n").as_ptr())));
+            assert!(jzon_member(a, c("n").as_ptr()).is_null());
+
+            let it = jzon_iter_new(doc);
+            let (mut k, mut len, mut v) = (ptr::null(), 0, ptr::null());
+            let mut keys = BTreeSet::new();
+            while jzon_iter_next(it, &mut k, &mut len, &mut v) {
+                let k = slice::from_raw_parts(k as *const u8, len);
+                keys.insert(str::from_utf8(k).unwrap().to_string());
+                assert!(!v.is_null());
+            }
+            jzon_iter_free(it);
+            assert_eq!(vec!["a", "b/c", "n"], keys.into_iter().collect::<Vec<_>>());
+            let it = jzon_iter_new(a);
+            assert!(jzon_iter_next(it, &mut k, ptr::null_mut(), &mut v));
+            assert!(k.is_null());
+            assert_eq!(jzon_index(a, 0), v);
+            jzon_iter_free(it);
+            assert!(jzon_iter_new(jzon_index(a, 0)).is_null());
+            jzon_free(doc);
+        }
+    }
+
+    #[test]
+    fn build() {
+        let c = |s: &str| CString::new(s).unwrap();
+        unsafe {
+            let doc = jzon_new_object();
+            let list = jzon_new_array();
+            assert_eq!(Status::Ok, jzon_push(list, jzon_new_int(-1)));
+            assert_eq!(Status::Ok, jzon_push(list, jzon_new_double(0.5)));
+            assert!(jzon_new_double(f64::NAN).is_null());
+            assert!(jzon_new_double(f64::NEG_INFINITY).is_null());
+            assert_eq!(Status::Ok, jzon_push(list, jzon_new_null()));
+            assert_eq!(Status::Ok, jzon_insert(doc, c("list").as_ptr(), list));
+            let s = jzon_new_string("é\"".as_ptr() as *const c_char, 3);
+            assert_eq!(Status::Ok, jzon_insert(doc, c("s").as_ptr(), s));
+            assert_eq!(
+                Status::Ok,
+                jzon_insert(doc, c("b").as_ptr(), jzon_new_bool(false))
+            );
+
+            let extra = jzon_new_bool(true);
+            assert_eq!(Status::Type, jzon_push(doc, extra));
+            assert_eq!(Status::Null, jzon_insert(doc, ptr::null(), extra));
+            jzon_free(extra);
+            assert!(jzon_new_string(b"\xff".as_ptr() as *const c_char, 1).is_null());
+
+            assert_eq!(
+                r#"{"b":false,"list":[-1,0.5,null],"s":"é\""}"#,
+                write(doc, SORT_KEYS)
+            );
+            let copy = jzon_clone(doc);
+            jzon_free(doc);
+            assert_eq!(
+                "{\n  \"b\": false,\n  \"list\": [\n    -1,\n    0.5,\n    null\n  ],\n  \"s\": \"é\\\"\"\n}",
+                write(copy, PRETTY | SORT_KEYS)
+            );
+            let mut buf = [1 as c_char; 4];
+            let status = jzon_write(copy, 0, buf.as_mut_ptr(), 4, ptr::null_mut());
+            assert_eq!(Status::Buffer, status);
+            jzon_free(copy);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut jz = ptr::null_mut();
+        let mut err = Error {
+            status: Status::Ok,
+            offset: 0,
+            line: 0,
+            column: 0,
+            message: ptr::null(),
+        };
+        let text = "{\"a\": [1,\n  2, x]}";
+        let status = unsafe { jzon_parse(text.as_ptr(), text.len(), &mut jz, &mut err) };
+        assert_eq!(Status::Syntax, status);
+        assert!(jz.is_null());
+        assert_eq!(
+            (Status::Syntax, 15, 2, 6),
+            (err.status, err.offset, err.line, err.column)
+        );
+        let message = unsafe { CStr::from_ptr(err.message) };
+        assert_eq!("expected a value", message.to_str().unwrap());
+
+        // where the strict parser stops, the control char, not the string
+        let text = "[1,\n \"é\nb\"]";
+        let status = unsafe { jzon_parse(text.as_ptr(), text.len(), &mut jz, &mut err) };
+        assert_eq!(Status::Syntax, status);
+        assert_eq!((8, 2, 4), (err.offset, err.line, err.column));
+
+        let status = unsafe { jzon_parse(ptr::null(), 0, &mut jz, &mut err) };
+        assert_eq!(Status::Syntax, status);
+        assert_eq!((0, 1, 1), (err.offset, err.line, err.column));
+        let status = unsafe { jzon_parse(ptr::null(), 1, &mut jz, &mut err) };
+        assert_eq!(Status::Null, status);
+        let status = |status| unsafe { CStr::from_ptr(jzon_status_str(status)) };
+        assert_eq!(
+            "buffer too small",
+            status(Status::Buffer as c_int).to_str().unwrap()
+        );
+        assert_eq!("unknown status", status(6).to_str().unwrap());
+        assert_eq!("unknown status", status(-1).to_str().unwrap());
+    }
+
+    // The header is written from here: the compiler checks the types of
+    // every prototype against the function, and the enums, struct and flags
+    // come from their Rust counterparts. Run the tests with
+    // JZON_WRITE_HEADER set to rewrite include/jzon.h after a change.
+
+    // the C spelling of a Rust type used in the API
+    trait CType {
+        fn c() -> String;
+    }
+
+    macro_rules! c_types {
+        ($($ty:ty => $c:expr,)*) => {
+            $(impl CType for $ty {
+                fn c() -> String {
+                    String::from($c)
+                }
+            })*
+        };
+    }
+
+    c_types! {
+        () => "void",
+        bool => "bool",
+        i8 => "char",
+        u8 => "char",
+        c_int => "int",
+        u32 => "unsigned",
+        usize => "size_t",
+        i64 => "int64_t",
+        f64 => "double",
+        Jzon => "jzon",
+        Members => "jzon_iter",
+        Error => "jzon_error",
+        Status => "jzon_status",
+        Type => "jzon_type",
+    }
+
+    impl<T: CType> CType for *const T {
+        fn c() -> String {
+            pointer(format!("const {}", T::c()))
+        }
+    }
+
+    impl<T: CType> CType for *mut T {
+        fn c() -> String {
+            pointer(T::c())
+        }
+    }
+
+    fn pointer(to: String) -> String {
+        match to.ends_with('*') {
+            true => to + "*",
+            false => to + " *",
+        }
+    }
+
+    // `ty name`, or `ty *name` for pointers
+    fn c_decl(ty: &str, name: &str) -> String {
+        match ty.ends_with('*') {
+            true => format!("{}{}", ty, name),
+            false => format!("{} {}", ty, name),
+        }
+    }
+
+    // the prototype of a function of this type, with C parameter names
+    trait CFn {
+        fn prototype(name: &str, params: &[&str]) -> String;
+    }
+
+    macro_rules! c_fn {
+        ($($arg:ident)*) => {
+            impl<R: CType, $($arg: CType),*> CFn for unsafe extern "C" fn($($arg),*) -> R {
+                fn prototype(name: &str, params: &[&str]) -> String {
+                    let types: Vec<String> = vec![$($arg::c()),*];
+                    assert_eq!(types.len(), params.len(), "{}", name);
+                    let params = match types.is_empty() {
+                        true => vec![String::from("void")],
+                        false => types.iter().zip(params).map(|(t, p)| c_decl(t, p)).collect(),
+                    };
+                    format!("{}({});\n", c_decl(&R::c(), name), params.join(", "))
+                }
+            }
+        };
+    }
+
+    c_fn!();
+    c_fn!(A);
+    c_fn!(A B);
+    c_fn!(A B C);
+    c_fn!(A B C D);
+    c_fn!(A B C D E);
+
+    fn prototype<F: CFn>(_: F, name: &str, params: &[&str]) -> String {
+        F::prototype(name, params)
+    }
+
+    macro_rules! hole {
+        ($param:ident) => {
+            _
+        };
+    }
+
+    // `jzon_parse(text, len, out, err)` declared with the types it has
+    macro_rules! c_proto {
+        ($name:ident($($param:ident),*)) => {
+            prototype(
+                $name as unsafe extern "C" fn($(hole!($param)),*) -> _,
+                stringify!($name),
+                &[$(stringify!($param)),*],
+            )
+        };
+    }
+
+    // the variants of a C enum, with a comment above those that have one
+    fn c_enum<T: Copy>(
+        variants: &[(T, &str)],
+        name: fn(T) -> String,
+        value: fn(T) -> c_int,
+    ) -> String {
+        let mut out = String::new();
+        for (i, (v, comment)) in variants.iter().enumerate() {
+            if !comment.is_empty() {
+                out += &format!("    // {}\n", comment);
+            }
+            match value(*v) == i as c_int && i > 0 {
+                true => out += &format!("    {},\n", name(*v)),
+                false => out += &format!("    {} = {},\n", name(*v), value(*v)),
+            }
+        }
+        out
+    }
+
+    macro_rules! c_field {
+        ($name:ident) => {
+            (
+                std::mem::offset_of!(Error, $name),
+                c_decl(&c_type_of(|e: &Error| &e.$name), stringify!($name)),
+            )
+        };
+    }
+
+    fn c_type_of<T: CType>(_: fn(&Error) -> &T) -> String {
+        T::c()
+    }
+
+    fn c_header() -> String {
+        let mut h = String::from(
+            r#"// The C API of jzon, implemented in src/ffi.rs, whose tests write this
+// header from the declarations there, so the two always agree.
+//
+// A `jzon` is a JSON value. The ones returned by jzon_parse, jzon_new_* and
+// jzon_clone belong to the caller, who frees them with jzon_free or hands
+// them to jzon_push or jzon_insert. The ones found inside a value, by
+// jzon_index, jzon_member, jzon_pointer and jzon_iter_next, are borrowed:
+// they stay valid until the value they were found in is freed or changed.
+// Strings are UTF-8. Those read from values come with a length rather than
+// a NUL, as JSON strings may hold NULs.
+
+#ifndef JZON_H
+#define JZON_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct jzon jzon;
+typedef struct jzon_iter jzon_iter;
+
+typedef enum {
+"#,
+        );
+        let statuses: Vec<_> = STATUSES
+            .iter()
+            .map(|s| {
+                let comment = match s {
+                    Status::Ok => "",
+                    Status::Syntax => "the text isn't JSON, the jzon_error tells where",
+                    Status::Utf8 => "a string or key given isn't UTF-8",
+                    Status::Type => "the value isn't of the type asked for",
+                    Status::Buffer => "the output needs more room than given",
+                    Status::Null => "a pointer that mustn't be null is",
+                };
+                (*s, comment)
+            })
+            .collect();
+        h += &c_enum(
+            &statuses,
+            |s| match s {
+                Status::Ok => String::from("JZON_OK"),
+                s => format!("JZON_ERR_{:?}", s).to_uppercase(),
+            },
+            |s| s as c_int,
+        );
+        h += "} jzon_status;\n\ntypedef enum {\n";
+        let types = [
+            Type::Null,
+            Type::Bool,
+            Type::Integer,
+            Type::Double,
+            Type::String,
+            Type::Array,
+            Type::Object,
+        ];
+        let types: Vec<_> = types.iter().map(|t| (*t, "")).collect();
+        h += &c_enum(
+            &types,
+            |t| format!("JZON_{:?}", t).to_uppercase(),
+            |t| t as c_int,
+        );
+        h += "} jzon_type;\n\n";
+
+        h += "// Where parsing stopped. The offset counts bytes from 0, line and column\n";
+        h += "// start at 1 and the column counts chars. The message is static.\n";
+        h += "typedef struct {\n";
+        let fields = [
+            c_field!(status),
+            c_field!(offset),
+            c_field!(line),
+            c_field!(column),
+            c_field!(message),
+        ];
+        // in the order of the Rust struct
+        assert!(fields.windows(2).all(|w| w[0].0 < w[1].0));
+        for (_, field) in fields.iter() {
+            h += &format!("    {};\n", field);
+        }
+        h += "} jzon_error;\n\n";
+
+        h += "// flags of jzon_write\n";
+        h += &format!("#define JZON_PRETTY {}u\n", PRETTY);
+        h += &format!("#define JZON_SORT_KEYS {}u\n\n", SORT_KEYS);
+
+        h += "// Parses `len` bytes of `text`, which needn't end in a NUL. On success *out\n";
+        h += "// is the value, otherwise it is NULL and `err`, which may be NULL, says why.\n";
+        h += &c_proto!(jzon_parse(text, len, out, err));
+        h += &c_proto!(jzon_free(value));
+        h += &c_proto!(jzon_clone(value));
+        h += "// an int, so any value is safe to pass; unknown ones get \"unknown status\"\n";
+        h += &c_proto!(jzon_status_str(status));
+        h += "\n// JZON_NULL for a NULL value too\n";
+        h += &c_proto!(jzon_type_of(value));
+        h += &c_proto!(jzon_get_bool(value, out));
+        h += &c_proto!(jzon_get_int(value, out));
+        h += "// integers too, rounded where they have to be\n";
+        h += &c_proto!(jzon_get_double(value, out));
+        h += &c_proto!(jzon_get_string(value, out, len));
+
+        h += "\n// members of an object or elements of an array, 0 for anything else\n";
+        h += &c_proto!(jzon_len(value));
+        h += "// These return NULL when there is nothing there. Pointers are JSON\n";
+        h += "// Pointers, RFC 6901, like \"/a/0/b\", and \"\" is the value itself.\n";
+        h += &c_proto!(jzon_index(array, idx));
+        h += &c_proto!(jzon_member(object, key));
+        h += &c_proto!(jzon_pointer(value, pointer));
+
+        h += "\n// Walks the members of an object, in key order, or the elements of an\n";
+        h += "// array; NULL for anything else. jzon_iter_next returns false once there\n";
+        h += "// is nothing left. Keys are NULL for array elements, and any of the outputs\n";
+        h += "// may be NULL if it isn't wanted.\n";
+        h += &c_proto!(jzon_iter_new(value));
+        h += &c_proto!(jzon_iter_next(iter, key, key_len, value));
+        h += &c_proto!(jzon_iter_free(iter));
+
+        h += "\n";
+        h += &c_proto!(jzon_new_null());
+        h += &c_proto!(jzon_new_bool(v));
+        h += &c_proto!(jzon_new_int(v));
+        h += "// NULL for NaN and the infinities, which JSON has no form for\n";
+        h += &c_proto!(jzon_new_double(v));
+        h += "// NULL unless the bytes are UTF-8\n";
+        h += &c_proto!(jzon_new_string(s, len));
+        h += &c_proto!(jzon_new_array());
+        h += &c_proto!(jzon_new_object());
+        h += "// These take `value` over when they return JZON_OK, and only then.\n";
+        h += "// jzon_insert replaces any member with the same key.\n";
+        h += &c_proto!(jzon_push(array, value));
+        h += &c_proto!(jzon_insert(object, key, value));
+
+        h += "\n// Like snprintf: the text is written with a NUL after it when it fits in\n";
+        h += "// `cap` bytes, and *len, if `len` isn't NULL, gets its length without the\n";
+        h += "// NUL either way, so a NULL buffer of 0 bytes asks for the size. Numbers\n";
+        h += "// too large for a double, like a parsed 1e400, are written as null.\n";
+        h += &c_proto!(jzon_write(value, flags, buf, cap, len));
+
+        h + "\n#ifdef __cplusplus\n}\n#endif\n\n#endif\n"
+    }
+
+    #[test]
+    fn header() {
+        let generated = c_header();
+        if std::env::var_os("JZON_WRITE_HEADER").is_some() {
+            fs::write("include/jzon.h", &generated).unwrap();
+        }
+        let header = fs::read_to_string("include/jzon.h").unwrap();
+        assert!(
+            header == generated,
+            "include/jzon.h is out of date, rerun with JZON_WRITE_HEADER=1"
+        );
+
+        // and every exported function is in it
+        let source = fs::read_to_string("src/ffi.rs").unwrap();
+        let exported = source.matches("#[no_mangle]\npub ").count();
+        let declared = generated.lines().filter(|l| l.ends_with(");"));
+        assert_eq!(exported, declared.count());
+    }
+}
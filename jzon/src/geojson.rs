@@ -0,0 +1,482 @@
+use crate::convert::{field, ConvertErr, FromJzon, ToJzon};
+use crate::jzon::Jzon;
+use crate::map::Map;
+use std::result;
+
+// GeoJSON, RFC 7946. Positions are longitude and latitude; an altitude is
+// allowed in the input but not kept, so every coordinate array is a flat
+// `Vec<[f64; 2]>`. Foreign members and "bbox" are ignored when reading, and
+// `bbox` computes the box instead.
+
+pub type Position = [f64; 2];
+
+// west, south, east, north
+pub type Bbox = [f64; 4];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(Position),
+    MultiPoint(Vec<Position>),
+    LineString(Vec<Position>),
+    MultiLineString(Vec<Vec<Position>>),
+    // the exterior ring, then the holes
+    Polygon(Vec<Vec<Position>>),
+    MultiPolygon(Vec<Vec<Vec<Position>>>),
+    GeometryCollection(Vec<Geometry>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feature {
+    // a string or a number
+    pub id: Option<Jzon>,
+    pub geometry: Option<Geometry>,
+    pub properties: Option<Map>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureCollection {
+    pub features: Vec<Feature>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoJson {
+    Geometry(Geometry),
+    Feature(Feature),
+    FeatureCollection(FeatureCollection),
+}
+
+#[derive(Debug)]
+pub enum GeoErr {
+    // not even the right JSON shape
+    Convert(ConvertErr),
+    // "type" names nothing GeoJSON has, or not what was asked for
+    ExpectType(String),
+    // two or three numbers
+    ExpectPosition,
+    // a line string has at least two positions
+    ExpectLine,
+    // a linear ring has at least four positions and ends where it starts
+    ExpectRing,
+    // exterior rings counterclockwise and holes clockwise, only checked by
+    // `check_winding` since RFC 7946 says not to reject the input
+    ExpectWinding,
+}
+
+impl From<ConvertErr> for GeoErr {
+    fn from(e: ConvertErr) -> Self {
+        GeoErr::Convert(e)
+    }
+}
+
+type Result<T> = result::Result<T, GeoErr>;
+
+use GeoErr::*;
+
+fn array(jz: &Jzon) -> Result<&[Jzon]> {
+    match jz {
+        Jzon::Array(vec) => Ok(vec),
+        _ => Err(Convert(ConvertErr::ExpectArray)),
+    }
+}
+
+fn object(jz: &Jzon) -> Result<&Map> {
+    match jz {
+        Jzon::Object(map) => Ok(map),
+        _ => Err(Convert(ConvertErr::ExpectObject)),
+    }
+}
+
+fn member<'a>(map: &'a Map, key: &str) -> Result<&'a Jzon> {
+    map.get(key)
+        .ok_or_else(|| Convert(ConvertErr::ExpectKey(key.to_string())))
+}
+
+fn position(jz: &Jzon) -> Result<Position> {
+    match array(jz)? {
+        [x, y] | [x, y, _] => Ok([f64::from_jzon(x)?, f64::from_jzon(y)?]),
+        _ => Err(ExpectPosition),
+    }
+}
+
+fn positions(jz: &Jzon) -> Result<Vec<Position>> {
+    array(jz)?.iter().map(position).collect()
+}
+
+fn line(jz: &Jzon) -> Result<Vec<Position>> {
+    let line = positions(jz)?;
+    match line.len() {
+        0 | 1 => Err(ExpectLine),
+        _ => Ok(line),
+    }
+}
+
+fn polygon(jz: &Jzon) -> Result<Vec<Vec<Position>>> {
+    let rings = array(jz)?.iter().map(positions);
+    rings
+        .map(|ring| match ring? {
+            ring if ring.len() >= 4 && ring.first() == ring.last() => Ok(ring),
+            _ => Err(ExpectRing),
+        })
+        .collect()
+}
+
+// twice the area, positive when counterclockwise
+fn signed_area(ring: &[Position]) -> f64 {
+    ring.windows(2)
+        .map(|w| w[0][0] * w[1][1] - w[1][0] * w[0][1])
+        .sum()
+}
+
+fn check_rings(rings: &[Vec<Position>]) -> Result<()> {
+    let wound = rings.iter().enumerate().all(|(i, ring)| match i {
+        0 => signed_area(ring) >= 0.0,
+        _ => signed_area(ring) <= 0.0,
+    });
+    match wound {
+        true => Ok(()),
+        false => Err(ExpectWinding),
+    }
+}
+
+fn extend(bbox: Option<Bbox>, other: Option<Bbox>) -> Option<Bbox> {
+    match (bbox, other) {
+        (Some(a), Some(b)) => Some([
+            a[0].min(b[0]),
+            a[1].min(b[1]),
+            a[2].max(b[2]),
+            a[3].max(b[3]),
+        ]),
+        (a, b) => a.or(b),
+    }
+}
+
+fn points_bbox<'a>(points: impl IntoIterator<Item = &'a Position>) -> Option<Bbox> {
+    points
+        .into_iter()
+        .fold(None, |bbox, p| extend(bbox, Some([p[0], p[1], p[0], p[1]])))
+}
+
+fn type_of(map: &Map) -> Result<String> {
+    Ok(field(map, "type")?)
+}
+
+impl Geometry {
+    pub fn from_jzon(jz: &Jzon) -> Result<Geometry> {
+        let map = object(jz)?;
+        let kind = type_of(map)?;
+        if kind == "GeometryCollection" {
+            let geometries = array(member(map, "geometries")?)?;
+            let geometries = geometries.iter().map(Geometry::from_jzon);
+            return Ok(Geometry::GeometryCollection(
+                geometries.collect::<Result<_>>()?,
+            ));
+        }
+        let coords = member(map, "coordinates")?;
+        Ok(match kind.as_str() {
+            "Point" => Geometry::Point(position(coords)?),
+            "MultiPoint" => Geometry::MultiPoint(positions(coords)?),
+            "LineString" => Geometry::LineString(line(coords)?),
+            "MultiLineString" => {
+                Geometry::MultiLineString(array(coords)?.iter().map(line).collect::<Result<_>>()?)
+            }
+            "Polygon" => Geometry::Polygon(polygon(coords)?),
+            "MultiPolygon" => {
+                Geometry::MultiPolygon(array(coords)?.iter().map(polygon).collect::<Result<_>>()?)
+            }
+            _ => return Err(ExpectType(kind)),
+        })
+    }
+
+    pub fn bbox(&self) -> Option<Bbox> {
+        match self {
+            Geometry::Point(p) => points_bbox([p]),
+            Geometry::MultiPoint(points) | Geometry::LineString(points) => points_bbox(points),
+            Geometry::MultiLineString(lines) | Geometry::Polygon(lines) => {
+                points_bbox(lines.iter().flatten())
+            }
+            Geometry::MultiPolygon(polygons) => points_bbox(polygons.iter().flatten().flatten()),
+            Geometry::GeometryCollection(geometries) => geometries
+                .iter()
+                .fold(None, |bbox, g| extend(bbox, g.bbox())),
+        }
+    }
+
+    // the right-hand rule of RFC 7946 §3.1.6
+    pub fn check_winding(&self) -> Result<()> {
+        match self {
+            Geometry::Polygon(rings) => check_rings(rings),
+            Geometry::MultiPolygon(polygons) => polygons.iter().try_for_each(|p| check_rings(p)),
+            Geometry::GeometryCollection(geometries) => {
+                geometries.iter().try_for_each(Geometry::check_winding)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Feature {
+    pub fn from_jzon(jz: &Jzon) -> Result<Feature> {
+        let map = object(jz)?;
+        match type_of(map)?.as_str() {
+            "Feature" => (),
+            kind => return Err(ExpectType(kind.to_string())),
+        }
+        let geometry = match member(map, "geometry")? {
+            Jzon::Null => None,
+            jz => Some(Geometry::from_jzon(jz)?),
+        };
+        let id = match map.get("id") {
+            None | Some(Jzon::Null) => None,
+            Some(id @ Jzon::String(_))
+            | Some(id @ Jzon::Integer(_))
+            | Some(id @ Jzon::Double(_)) => Some(id.clone()),
+            Some(_) => return Err(Convert(ConvertErr::ExpectVariant)),
+        };
+        Ok(Feature {
+            id,
+            geometry,
+            properties: field(map, "properties")?,
+        })
+    }
+
+    pub fn bbox(&self) -> Option<Bbox> {
+        self.geometry.as_ref().and_then(Geometry::bbox)
+    }
+}
+
+impl FeatureCollection {
+    pub fn from_jzon(jz: &Jzon) -> Result<FeatureCollection> {
+        let map = object(jz)?;
+        match type_of(map)?.as_str() {
+            "FeatureCollection" => (),
+            kind => return Err(ExpectType(kind.to_string())),
+        }
+        let features = array(member(map, "features")?)?;
+        Ok(FeatureCollection {
+            features: features
+                .iter()
+                .map(Feature::from_jzon)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    pub fn bbox(&self) -> Option<Bbox> {
+        self.features
+            .iter()
+            .fold(None, |bbox, f| extend(bbox, f.bbox()))
+    }
+}
+
+impl GeoJson {
+    pub fn from_jzon(jz: &Jzon) -> Result<GeoJson> {
+        match type_of(object(jz)?)?.as_str() {
+            "Feature" => Ok(GeoJson::Feature(Feature::from_jzon(jz)?)),
+            "FeatureCollection" => Ok(GeoJson::FeatureCollection(FeatureCollection::from_jzon(
+                jz,
+            )?)),
+            _ => Ok(GeoJson::Geometry(Geometry::from_jzon(jz)?)),
+        }
+    }
+
+    pub fn bbox(&self) -> Option<Bbox> {
+        match self {
+            GeoJson::Geometry(g) => g.bbox(),
+            GeoJson::Feature(f) => f.bbox(),
+            GeoJson::FeatureCollection(fc) => fc.bbox(),
+        }
+    }
+
+    pub fn check_winding(&self) -> Result<()> {
+        match self {
+            GeoJson::Geometry(g) => g.check_winding(),
+            GeoJson::Feature(f) => f.geometry.iter().try_for_each(Geometry::check_winding),
+            GeoJson::FeatureCollection(fc) => fc
+                .features
+                .iter()
+                .filter_map(|f| f.geometry.as_ref())
+                .try_for_each(Geometry::check_winding),
+        }
+    }
+}
+
+fn typed(kind: &str, members: Vec<(&str, Jzon)>) -> Jzon {
+    let mut map = Map::new();
+    map.insert(String::from("type"), Jzon::String(kind.to_string()));
+    for (k, v) in members {
+        map.insert(k.to_string(), v);
+    }
+    Jzon::Object(map)
+}
+
+fn point(p: &Position) -> Jzon {
+    Jzon::Array(vec![Jzon::Double(p[0]), Jzon::Double(p[1])])
+}
+
+fn coordinates(points: &[Position]) -> Jzon {
+    Jzon::Array(points.iter().map(point).collect())
+}
+
+fn nested<T>(items: &[T], f: impl Fn(&T) -> Jzon) -> Jzon {
+    Jzon::Array(items.iter().map(f).collect())
+}
+
+impl ToJzon for Geometry {
+    fn to_jzon(&self) -> Jzon {
+        let (kind, coords) = match self {
+            Geometry::Point(p) => ("Point", point(p)),
+            Geometry::MultiPoint(points) => ("MultiPoint", coordinates(points)),
+            Geometry::LineString(points) => ("LineString", coordinates(points)),
+            Geometry::MultiLineString(lines) => {
+                ("MultiLineString", nested(lines, |l| coordinates(l)))
+            }
+            Geometry::Polygon(rings) => ("Polygon", nested(rings, |r| coordinates(r))),
+            Geometry::MultiPolygon(polygons) => (
+                "MultiPolygon",
+                nested(polygons, |p| nested(p, |r| coordinates(r))),
+            ),
+            Geometry::GeometryCollection(geometries) => {
+                let geometries = nested(geometries, Geometry::to_jzon);
+                return typed("GeometryCollection", vec![("geometries", geometries)]);
+            }
+        };
+        typed(kind, vec![("coordinates", coords)])
+    }
+}
+
+impl ToJzon for Feature {
+    fn to_jzon(&self) -> Jzon {
+        let mut members = vec![
+            ("geometry", self.geometry.to_jzon()),
+            ("properties", self.properties.to_jzon()),
+        ];
+        if let Some(id) = &self.id {
+            members.push(("id", id.clone()));
+        }
+        typed("Feature", members)
+    }
+}
+
+impl ToJzon for FeatureCollection {
+    fn to_jzon(&self) -> Jzon {
+        typed(
+            "FeatureCollection",
+            vec![("features", self.features.to_jzon())],
+        )
+    }
+}
+
+impl ToJzon for GeoJson {
+    fn to_jzon(&self) -> Jzon {
+        match self {
+            GeoJson::Geometry(g) => g.to_jzon(),
+            GeoJson::Feature(f) => f.to_jzon(),
+            GeoJson::FeatureCollection(fc) => fc.to_jzon(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geo(text: &str) -> Result<GeoJson> {
+        GeoJson::from_jzon(&Jzon::parse(text.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn geometries() {
+        let texts = [
+            r#"{"type": "Point", "coordinates": [1, 2, 300]}"#,
+            r#"{"type": "MultiPoint", "coordinates": [[1, 2], [-3, 4.5]]}"#,
+            r#"{"type": "LineString", "coordinates": [[1, 2], [3, 4]]}"#,
+            r#"{"type": "MultiLineString", "coordinates": [[[1, 2], [3, 4]], [[0, 0], [5, 5]]]}"#,
+            r#"{"type": "Polygon", "coordinates": [[[0, 0], [4, 0], [4, 4], [0, 4], [0, 0]],
+                                                   [[1, 1], [1, 2], [2, 2], [1, 1]]]}"#,
+            r#"{"type": "MultiPolygon", "coordinates": [[[[0, 0], [1, 0], [1, 1], [0, 0]]]]}"#,
+            r#"{"type": "GeometryCollection", "geometries": [
+                {"type": "Point", "coordinates": [9, -9]},
+                {"type": "GeometryCollection", "geometries": []}]}"#,
+            r#"{"type": "Feature", "id": 7, "geometry": null, "properties": {"a": [1]}}"#,
+            r#"{"type": "FeatureCollection", "bbox": [0, 0, 1, 1], "features": [
+                {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 1]},
+                 "properties": null}]}"#,
+        ];
+        let boxes = [
+            Some([1.0, 2.0, 1.0, 2.0]),
+            Some([-3.0, 2.0, 1.0, 4.5]),
+            Some([1.0, 2.0, 3.0, 4.0]),
+            Some([0.0, 0.0, 5.0, 5.0]),
+            Some([0.0, 0.0, 4.0, 4.0]),
+            Some([0.0, 0.0, 1.0, 1.0]),
+            Some([9.0, -9.0, 9.0, -9.0]),
+            None,
+            Some([1.0, 1.0, 1.0, 1.0]),
+        ];
+        for (text, bbox) in texts.iter().zip(boxes) {
+            let geo = geo(text).unwrap();
+            assert_eq!(bbox, geo.bbox());
+            assert!(geo.check_winding().is_ok());
+            assert_eq!(geo, GeoJson::from_jzon(&geo.to_jzon()).unwrap());
+        }
+        assert!(matches!(
+            geo(texts[0]).unwrap(),
+            GeoJson::Geometry(Geometry::Point([x, y])) if x == 1.0 && y == 2.0
+        ));
+
+        let content = std::fs::read("data/canada.json").unwrap();
+        let canada = FeatureCollection::from_jzon(&Jzon::parse(&content).unwrap()).unwrap();
+        let bbox = canada.bbox().unwrap();
+        assert!(bbox[0] < -141.0 && bbox[2] > -52.7 && bbox[1] > 41.0 && bbox[3] > 83.0);
+        match &canada.features[0].geometry {
+            Some(Geometry::Polygon(rings)) => assert_eq!(480, rings.len()),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let err = |text: &str| geo(text).err().unwrap();
+
+        assert!(matches!(
+            err(r#"{"type": "Point", "coordinates": [1]}"#),
+            ExpectPosition
+        ));
+        assert!(matches!(
+            err(r#"{"type": "Point", "coordinates": [1, 2, 3, 4]}"#),
+            ExpectPosition
+        ));
+        assert!(matches!(
+            err(r#"{"type": "LineString", "coordinates": [[1, 2]]}"#),
+            ExpectLine
+        ));
+        assert!(matches!(
+            err(r#"{"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]]}"#),
+            ExpectRing
+        ));
+        assert!(matches!(
+            err(r#"{"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [0, 0]]]}"#),
+            ExpectRing
+        ));
+        assert!(matches!(
+            err(r#"{"type": "Circle", "coordinates": [0, 0]}"#),
+            ExpectType(kind) if kind == "Circle"
+        ));
+        assert!(matches!(
+            err(r#"{"type": "Point", "coordinates": ["0", 0]}"#),
+            Convert(ConvertErr::ExpectNumber)
+        ));
+        assert!(matches!(
+            err(r#"{"type": "Feature", "geometry": null}"#),
+            Convert(ConvertErr::ExpectKey(_))
+        ));
+
+        // clockwise outside, and a hole wound the same way as the outside
+        let cw = geo(r#"{"type": "Polygon", "coordinates": [[[0, 0], [0, 1], [1, 1], [0, 0]]]}"#);
+        assert!(matches!(cw.unwrap().check_winding(), Err(ExpectWinding)));
+        let hole = geo(
+            r#"{"type": "Polygon", "coordinates": [[[0, 0], [4, 0], [4, 4], [0, 0]],
+                                                  [[1, 1], [2, 1], [2, 2], [1, 1]]]}"#,
+        );
+        assert!(matches!(hole.unwrap().check_winding(), Err(ExpectWinding)));
+    }
+}
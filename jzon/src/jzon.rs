@@ -1,14 +1,18 @@
-use std::char;
-use std::collections::HashMap;
-use std::convert::From;
-use std::f64;
-use std::fmt;
-use std::iter;
-use std::ops;
-use std::result;
-use std::str;
-use std::string::String;
-use std::vec::Vec;
+use crate::map::Map;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::char;
+use core::cmp::Ordering;
+use core::f64;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter;
+use core::ops;
+use core::result;
+use core::str;
+#[cfg(feature = "std")]
+use std::io;
 
 static ASCII: [u8; 128] = [
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x62, 0x74, 0x6E, 0x00, 0x66, 0x72, 0x00, 0x00,
@@ -21,7 +25,7 @@ static ASCII: [u8; 128] = [
     0x00, 0x00, 0x0D, 0x00, 0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
 ];
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ParseErr {
     ExpectPair,
     ExpectBool,
@@ -39,24 +43,25 @@ pub enum ParseErr {
     ExpectCommaBrace,
     ExpectNoneControl,
     ExpectCommaBracket,
+    // only `Jzon::lazy` limits how deep values nest
+    ExpectShallow,
 }
 
 #[derive(Debug)]
-struct State<T> {
-    value: T,
-    consumed: usize,
+pub(crate) struct State<T> {
+    pub(crate) value: T,
+    pub(crate) consumed: usize,
 }
 
 type ParsingResult<T> = result::Result<State<T>, ParseErr>;
 
-type Result = result::Result<Jzon, ParseErr>;
+// for the parsers that know where they are: the error comes with the
+// offset into their bytes where parsing failed
+type Located<T> = result::Result<State<T>, (ParseErr, usize)>;
 
-impl From<std::option::NoneError> for ParseErr {
-    fn from(_: std::option::NoneError) -> Self {
-        ParseErr::ExpectCodePoint
-    }
-}
+type Result = result::Result<Jzon, ParseErr>;
 
+#[allow(clippy::identity_op)]
 const START     : u16 = 2 << 0; // start
 const ZERO      : u16 = 2 << 1; // 0
 const DOT       : u16 = 2 << 2; // .
@@ -75,18 +80,22 @@ macro_rules! matches {
     }};
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Jzon {
-    Object(HashMap<String, Jzon>),
+    Object(Map),
     Array(Vec<Jzon>),
     String(String),
     Integer(i64),
     Double(f64),
     Bool(bool),
     Null,
+    // stands in for what `parse_tolerant` couldn't parse, printed as null
+    Error,
 }
 
+// not implemented yet
 #[derive(Debug)]
+#[allow(dead_code)]
 pub struct Iter {
     cur: Jzon,
     idx: usize,
@@ -100,37 +109,52 @@ impl Jzon {
     const VALUE_FALSE: Jzon = Jzon::Bool(false);
 
     pub fn parse(bytes: &[u8]) -> Result {
+        Jzon::parse_with_offset(bytes).map_err(|(err, _)| err)
+    }
+
+    // like `parse`, with the offset of the byte where parsing failed
+    pub fn parse_with_offset(bytes: &[u8]) -> result::Result<Jzon, (ParseErr, usize)> {
         let mut v = Jzon::parse_value(bytes)?;
         let State { consumed, .. } = &mut v;
 
         loop {
-            match bytes.iter().nth(*consumed) {
+            match bytes.get(*consumed) {
                 Some(b' ') | Some(b'\t') | Some(b'\r') | Some(b'\n') => {
                     *consumed += 1;
                 }
-                Some(_) => break Err(ExpectNoMore),
+                Some(_) => break Err((ExpectNoMore, *consumed)),
                 None => break Ok(v.value),
             }
         }
     }
 
-    fn parse_value(bytes: &[u8]) -> ParsingResult<Jzon> {
+    // Numbers, literals and escapes fail where they start, or at the end
+    // of the input if they run out of it.
+    fn located<T>(bytes: &[u8], start: usize, parsed: ParsingResult<T>) -> Located<T> {
+        parsed.map_err(|err| match err {
+            ExpectNoneEOF => (err, bytes.len()),
+            err => (err, start),
+        })
+    }
+
+    fn parse_value(bytes: &[u8]) -> Located<Jzon> {
         let spaces = Jzon::parse_space(bytes).unwrap();
-        let bytes = &bytes[spaces.consumed..];
-        let mut it = bytes.iter();
+        let start = spaces.consumed;
+        let token = &bytes[start..];
+        let mut it = token.iter();
 
         let parsed = match it.next() {
             Some(ch) => match *ch {
-                b'-' | b'0'..=b'9' => Jzon::parse_number(bytes),
-                b't' => Jzon::parse_true(bytes),
-                b'f' => Jzon::parse_false(bytes),
-                b'n' => Jzon::parse_null(bytes),
-                b'"' => Jzon::parse_string(bytes),
-                b'{' => Jzon::parse_object(bytes),
-                b'[' => Jzon::parse_array(bytes),
-                _ => Err(ExpectPrefix),
+                b'-' | b'0'..=b'9' => Jzon::located(bytes, start, Jzon::parse_number(token)),
+                b't' => Jzon::located(bytes, start, Jzon::parse_true(token)),
+                b'f' => Jzon::located(bytes, start, Jzon::parse_false(token)),
+                b'n' => Jzon::located(bytes, start, Jzon::parse_null(token)),
+                b'"' => Jzon::parse_string(token).map_err(|(err, at)| (err, start + at)),
+                b'{' => Jzon::parse_object(token).map_err(|(err, at)| (err, start + at)),
+                b'[' => Jzon::parse_array(token).map_err(|(err, at)| (err, start + at)),
+                _ => Err((ExpectPrefix, start)),
             },
-            None => Err(ExpectNoneEOF),
+            None => Err((ExpectNoneEOF, start)),
         }?;
 
         let consumed = parsed.consumed + spaces.consumed;
@@ -138,15 +162,16 @@ impl Jzon {
         Ok(State { value, consumed })
     }
 
-    fn parse_object(bytes: &[u8]) -> ParsingResult<Jzon> {
-        let mut map = HashMap::new();
+    fn parse_object(bytes: &[u8]) -> Located<Jzon> {
+        // sorted once at the end rather than on every insert
+        let mut members = Vec::new();
         let mut consumed = 1;
         let mut extra_comma = false;
 
         loop {
-            match bytes.iter().nth(consumed) {
+            match bytes.get(consumed) {
                 Some(ch) => match *ch as char {
-                    ',' if !extra_comma && !map.is_empty() => {
+                    ',' if !extra_comma && !members.is_empty() => {
                         extra_comma = true;
                         consumed += 1;
                         continue;
@@ -159,34 +184,39 @@ impl Jzon {
                         consumed += 1;
                         break;
                     }
+                    // members are separated by commas
+                    _ if !extra_comma && !members.is_empty() => {
+                        return Err((ExpectCommaBrace, consumed));
+                    }
                     '"' => {
                         extra_comma = false;
-                        let pair = Jzon::parse_pair(&bytes[consumed..])?;
-                        map.insert(pair.value.0, pair.value.1);
+                        let pair = Jzon::parse_pair(&bytes[consumed..])
+                            .map_err(|(err, at)| (err, consumed + at))?;
+                        members.push(pair.value);
                         consumed += pair.consumed;
                         continue;
                     }
                     _ => {
-                        return Err(ExpectPair);
+                        return Err((ExpectPair, consumed));
                     }
                 },
-                None => return Err(ExpectNoneEOF),
+                None => return Err((ExpectNoneEOF, consumed)),
             }
         }
 
         Ok(State {
-            value: Jzon::Object(map),
+            value: Jzon::Object(members.into_iter().collect()),
             consumed,
         })
     }
 
-    fn parse_array(bytes: &[u8]) -> ParsingResult<Jzon> {
+    fn parse_array(bytes: &[u8]) -> Located<Jzon> {
         let mut vec = Vec::new();
         let mut consumed = 1;
         let mut extra_comma = false;
 
         loop {
-            match bytes.iter().nth(consumed) {
+            match bytes.get(consumed) {
                 Some(ch) => match *ch as char {
                     ',' if !extra_comma && !vec.is_empty() => {
                         extra_comma = true;
@@ -201,16 +231,20 @@ impl Jzon {
                         consumed += 1;
                         break;
                     }
+                    _ if !extra_comma && !vec.is_empty() => {
+                        return Err((ExpectCommaBracket, consumed));
+                    }
                     _ => {
                         extra_comma = false;
-                        let elem = Jzon::parse_value(&bytes[consumed..])?;
+                        let elem = Jzon::parse_value(&bytes[consumed..])
+                            .map_err(|(err, at)| (err, consumed + at))?;
                         vec.push(elem.value);
                         consumed += elem.consumed;
                         continue;
                     }
                 },
                 None => {
-                    return Err(ExpectNoneEOF);
+                    return Err((ExpectNoneEOF, consumed));
                 }
             }
         }
@@ -260,67 +294,54 @@ impl Jzon {
             _ => Err(ExpectNull),
         }
     }
-    fn parse_number(bytes: &[u8]) -> ParsingResult<Jzon> {
+    pub(crate) fn parse_number(bytes: &[u8]) -> ParsingResult<Jzon> {
         let mut consumed = 0;
-        let mut n = 0i64;
-        let mut e = 0i64;
-        let mut t = 1.0f64;
-        let mut f = 0.0f64;
+        let mut n = Some(0i64);
         let mut negtive = 1;
         let mut is_float = false;
-        let mut exp_pos = 1;
         let mut st = START;
-        let mut it = bytes.iter();
 
-        loop {
-            match it.next() {
-                Some(ch) => match *ch {
-                    b'-' if matches!(st, START) => {
-                        st = NEG;
-                        negtive = -1;
-                    }
-                    b'0' if matches!(st, START | NEG) => {
-                        st = ZERO;
-                    }
-                    b'.' if matches!(st, ZERO | DIGIT0 | NONE_ZERO) => {
-                        st = DOT;
-                        is_float = true;
-                        f = n as f64;
-                    }
-                    d @ b'0'..=b'9' if matches!(st, DOT | DIGIT1) => {
-                        st = DIGIT1;
-                        t *= 10f64;
-                        f += (d - b'0') as f64 * negtive as f64 / t;
-                    }
-                    d @ b'1'..=b'9' if matches!(st, START | NEG) => {
-                        st = NONE_ZERO;
-                        n = negtive * (d - b'0') as i64;
-                    }
-                    d @ b'0'..=b'9' if matches!(st, DIGIT0 | NONE_ZERO) => {
-                        st = DIGIT0;
-                        n = n * 10i64 + negtive * (d - b'0') as i64;
-                    }
-                    b'e' | b'E' if matches!(st, ZERO | NONE_ZERO | DIGIT0 | DIGIT1) => {
-                        st = EXP;
-                        if !is_float {
-                            f = n as f64;
-                        };
-                        is_float = true;
-                    }
-                    b'+' if matches!(st, EXP) => {
-                        st = PLUS;
-                    }
-                    b'-' if matches!(st, EXP) => {
-                        st = MINUS;
-                        exp_pos = -1;
-                    }
-                    d @ b'0'..=b'9' if matches!(st, EXP | MINUS | PLUS | DIGIT2) => {
-                        st = DIGIT2;
-                        e = e * 10 + (d - b'0') as i64;
-                    }
-                    _ => break,
-                },
-                None => return Err(ExpectNoneEOF),
+        // a number may end the input, the caller decides if it can
+        for ch in bytes {
+            match *ch {
+                b'-' if matches!(st, START) => {
+                    st = NEG;
+                    negtive = -1;
+                }
+                b'0' if matches!(st, START | NEG) => {
+                    st = ZERO;
+                }
+                b'.' if matches!(st, ZERO | DIGIT0 | NONE_ZERO) => {
+                    st = DOT;
+                    is_float = true;
+                }
+                b'0'..=b'9' if matches!(st, DOT | DIGIT1) => {
+                    st = DIGIT1;
+                }
+                d @ b'1'..=b'9' if matches!(st, START | NEG) => {
+                    st = NONE_ZERO;
+                    n = Some(negtive * (d - b'0') as i64);
+                }
+                d @ b'0'..=b'9' if matches!(st, DIGIT0 | NONE_ZERO) => {
+                    st = DIGIT0;
+                    n = n
+                        .and_then(|n| n.checked_mul(10))
+                        .and_then(|n| n.checked_add(negtive * (d - b'0') as i64));
+                }
+                b'e' | b'E' if matches!(st, ZERO | NONE_ZERO | DIGIT0 | DIGIT1) => {
+                    st = EXP;
+                    is_float = true;
+                }
+                b'+' if matches!(st, EXP) => {
+                    st = PLUS;
+                }
+                b'-' if matches!(st, EXP) => {
+                    st = MINUS;
+                }
+                b'0'..=b'9' if matches!(st, EXP | MINUS | PLUS | DIGIT2) => {
+                    st = DIGIT2;
+                }
+                _ => break,
             }
             consumed += 1;
         }
@@ -329,53 +350,59 @@ impl Jzon {
             return Err(ExpectPrefix);
         }
 
-        Ok(State {
-            value: if is_float {
-                f *= 10f64.powf((e * exp_pos) as f64);
-                Jzon::Double(f)
-            } else {
-                Jzon::Integer(n)
-            },
-            consumed,
-        })
+        // integers too large for i64 fall back to doubles; the literal is
+        // validated ASCII, so the standard library can round it correctly
+        let value = match n {
+            Some(n) if !is_float => Jzon::Integer(n),
+            _ => {
+                let literal = str::from_utf8(&bytes[..consumed]).unwrap();
+                Jzon::Double(literal.parse().unwrap())
+            }
+        };
+        Ok(State { value, consumed })
     }
 
-    fn parse_string(bytes: &[u8]) -> ParsingResult<Jzon> {
-        let State { value, consumed } = Jzon::parse_string_literal(&bytes)?;
+    fn parse_string(bytes: &[u8]) -> Located<Jzon> {
+        let State { value, consumed } = Jzon::parse_string_literal(bytes)?;
         Ok(State {
             value: Jzon::String(value),
             consumed,
         })
     }
 
-    fn parse_pair(bytes: &[u8]) -> ParsingResult<(String, Jzon)> {
+    fn parse_pair(bytes: &[u8]) -> Located<(String, Jzon)> {
         let key = Jzon::parse_string_literal(bytes)?;
         let spaces = Jzon::parse_space(&bytes[key.consumed..]).unwrap();
+        let colon = key.consumed + spaces.consumed;
 
-        if bytes[key.consumed + spaces.consumed] != b':' {
-            return Err(ExpectColon);
+        match bytes.get(colon) {
+            Some(b':') => (),
+            Some(_) => return Err((ExpectColon, colon)),
+            None => return Err((ExpectNoneEOF, colon)),
         }
 
-        let val = Jzon::parse_value(&bytes[key.consumed + 1 + spaces.consumed..])?;
+        let val =
+            Jzon::parse_value(&bytes[colon + 1..]).map_err(|(err, at)| (err, colon + 1 + at))?;
         Ok(State {
             value: (key.value, val.value),
             consumed: key.consumed + spaces.consumed + 1 + val.consumed,
         })
     }
 
-    fn parse_string_literal(bytes: &[u8]) -> ParsingResult<String> {
+    // an invalid UTF-8 sequence fails at the opening quote
+    fn parse_string_literal(bytes: &[u8]) -> Located<String> {
         let mut value: Vec<u8> = vec![];
         let mut consumed = 1;
         let mut encode_buf = [0; 4];
         loop {
-            match bytes.iter().nth(consumed) {
+            match bytes.get(consumed) {
                 Some(ch) => match *ch {
                     b'\\' => {
-                        let escaped = Jzon::parse_escaped(&bytes[consumed..])?;
+                        let escape = &bytes[consumed..];
+                        let escaped = Jzon::located(escape, 0, Jzon::parse_escaped(escape))
+                            .map_err(|(err, at)| (err, consumed + at))?;
                         let s = escaped.value.encode_utf8(&mut encode_buf);
-                        for i in 0..s.len() {
-                            value.push(encode_buf[i]);
-                        }
+                        value.extend_from_slice(s.as_bytes());
                         consumed += escaped.consumed;
                     }
                     b'\"' => {
@@ -384,24 +411,24 @@ impl Jzon {
                     }
                     // according to ECMA-404
                     0x0000..=0x001F => {
-                        return Err(ExpectNoneControl);
+                        return Err((ExpectNoneControl, consumed));
                     }
                     ch => {
                         value.push(ch);
                         consumed += 1;
                     }
                 },
-                None => return Err(ExpectNoneEOF),
+                None => return Err((ExpectNoneEOF, consumed)),
             }
         }
 
         match String::from_utf8(value) {
             Ok(value) => Ok(State { value, consumed }),
-            Err(_) => Err(ExpectCodePoint),
+            Err(_) => Err((ExpectCodePoint, 0)),
         }
     }
 
-    fn parse_escaped(bytes: &[u8]) -> ParsingResult<char> {
+    pub(crate) fn parse_escaped(bytes: &[u8]) -> ParsingResult<char> {
         let consumed = 2;
         let mut it = bytes[1..].iter();
         let value = match it.next() {
@@ -413,7 +440,7 @@ impl Jzon {
             Some(b'"') => '"',
             Some(b'/') => '/',
             Some(b'\\') => '\\',
-            Some(b'u') => return Jzon::parse_unicode(&bytes),
+            Some(b'u') => return Jzon::parse_unicode(bytes),
             Some(_) => return Err(ExpectEscaped),
             None => return Err(ExpectNoneEOF),
         };
@@ -431,11 +458,11 @@ impl Jzon {
         consumed += 4;
         let mut uc = state.value;
 
-        if 0xDC00 <= uc && uc <= 0xDFFF || uc == 0 {
+        if (0xDC00..=0xDFFF).contains(&uc) {
             return Err(ExpectCodePoint);
         }
 
-        if 0xD800 <= uc && uc <= 0xDBFF {
+        if (0xD800..=0xDBFF).contains(&uc) {
             if bytes.len() < 12 {
                 return Err(ExpectNoneEOF);
             }
@@ -448,11 +475,14 @@ impl Jzon {
                 value: uc2,
                 consumed: _,
             } = Jzon::parse_hex4(&bytes[8..12])?;
+            if !(0xDC00..=0xDFFF).contains(&uc2) {
+                return Err(ExpectCodePoint);
+            }
             consumed += 4;
-            uc = 0x10000 + (((uc & 0x3FF) << 10 | uc2) & 0x3FF);
+            uc = 0x10000 + ((uc & 0x3FF) << 10 | (uc2 & 0x3FF));
         }
 
-        let value = char::from_u32(uc)?;
+        let value = char::from_u32(uc).ok_or(ExpectCodePoint)?;
         Ok(State { value, consumed })
     }
 
@@ -463,10 +493,10 @@ impl Jzon {
         if let Some(hex) = bytes[0..4]
             .iter()
             .enumerate()
-            .fold(Some(0u32), |init, (i, ch)| {
+            .try_fold(0u32, |x, (i, ch)| {
                 (*ch as char)
                     .to_digit(16)
-                    .and_then(|d| init.and_then(|x| Some(x + d * (0x1000u32 >> (i as u32 * 4)))))
+                    .map(|d| x + d * (0x1000u32 >> (i as u32 * 4)))
             })
         {
             Ok(State {
@@ -482,17 +512,10 @@ impl Jzon {
     fn parse_space(bytes: &[u8]) -> ParsingResult<()> {
         let value = ();
         let mut consumed = 0;
-        let mut it = bytes.iter();
-        loop {
-            match it.next() {
-                Some(ch) => match *ch as char {
-                    ' ' | '\t' | '\n' | '\r' => {
-                        consumed += 1;
-                        continue;
-                    }
-                    _ => break,
-                },
-                None => break,
+        for ch in bytes {
+            match *ch as char {
+                ' ' | '\t' | '\n' | '\r' => consumed += 1,
+                _ => break,
             }
         }
 
@@ -500,22 +523,98 @@ impl Jzon {
     }
 }
 
-/*
-const BLACK: &str = "\033[0;30m";
-const RED: &str = "\033[0;31m";
-const GREEN: &str = "\033[0;32m";
-const YELLOW: &str = "\033[0;33m";
-const BLUE: &str = "\033[0;34m";
-const PURPLE: &str = "\033[0;35m";
-const SKY: &str = "\033[0;36m";
-const WHITE: &str = "\033[0;37m";
-const RESET: &str = "\033[0m";
-const HIGHLIGHT: &str = "\033[1m";
-const UNDERLINE: &str = "\033[4m";
-const BLINK: &str = "\033[5m";
-const REVERSE: &str = "\033[7m";
-const FADEOUT: &str = "\033[8m";
-*/
+pub const BLACK: &str = "\x1b[0;30m";
+pub const RED: &str = "\x1b[0;31m";
+pub const GREEN: &str = "\x1b[0;32m";
+pub const YELLOW: &str = "\x1b[0;33m";
+pub const BLUE: &str = "\x1b[0;34m";
+pub const PURPLE: &str = "\x1b[0;35m";
+pub const SKY: &str = "\x1b[0;36m";
+pub const WHITE: &str = "\x1b[0;37m";
+pub const RESET: &str = "\x1b[0m";
+pub const HIGHLIGHT: &str = "\x1b[1m";
+pub const UNDERLINE: &str = "\x1b[4m";
+pub const BLINK: &str = "\x1b[5m";
+pub const REVERSE: &str = "\x1b[7m";
+pub const FADEOUT: &str = "\x1b[8m";
+
+// ANSI escape sequences for each kind of token
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub key: String,
+    pub string: String,
+    pub number: String,
+    pub boolean: String,
+    pub null: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            key: format!("{}{}", BLUE, HIGHLIGHT),
+            string: GREEN.to_string(),
+            number: YELLOW.to_string(),
+            boolean: PURPLE.to_string(),
+            null: SKY.to_string(),
+        }
+    }
+}
+
+impl Theme {
+    // Overrides the default with a spec like "key=1;34:string=32", giving
+    // the SGR parameters of each of key, string, number, bool and null.
+    // Malformed entries are ignored.
+    pub fn parse(spec: &str) -> Theme {
+        let mut theme = Theme::default();
+        for entry in spec.split(':') {
+            let mut kv = entry.splitn(2, '=');
+            let (role, sgr) = match (kv.next(), kv.next()) {
+                (Some(role), Some(sgr)) if sgr.bytes().all(|b| b.is_ascii_digit() || b == b';') => {
+                    (role, format!("\x1b[{}m", sgr))
+                }
+                _ => continue,
+            };
+            match role {
+                "key" => theme.key = sgr,
+                "string" => theme.string = sgr,
+                "number" => theme.number = sgr,
+                "bool" => theme.boolean = sgr,
+                "null" => theme.null = sgr,
+                _ => (),
+            }
+        }
+        theme
+    }
+}
+
+// What string literals escape beyond what RFC 8259 requires. Control
+// characters, DEL, U+2028 and U+2029 are always escaped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Escaping {
+    // everything outside ASCII, as `\uXXXX` or a surrogate pair of them
+    pub ascii: bool,
+    // `<`, `>` and `&`, so the output can sit inside an HTML script element
+    pub html: bool,
+    pub slash: bool,
+}
+
+impl Default for Escaping {
+    fn default() -> Self {
+        Escaping {
+            ascii: false,
+            html: false,
+            slash: true,
+        }
+    }
+}
+
+pub struct Styled<'a> {
+    jz: &'a Jzon,
+    theme: Option<&'a Theme>,
+    indent: Option<usize>,
+    sort_keys: bool,
+    escaping: Escaping,
+}
 
 impl Jzon {
     pub fn stringify(&self) -> String {
@@ -525,48 +624,305 @@ impl Jzon {
     pub fn beautify(&self) -> String {
         format!("{:#}", self)
     }
+
+    // compact output, streamed through a buffer instead of built in memory
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, out: &mut W) -> io::Result<()> {
+        self.styled().write_to(out)
+    }
+
+    pub fn styled(&self) -> Styled<'_> {
+        Styled {
+            jz: self,
+            theme: None,
+            indent: None,
+            sort_keys: false,
+            escaping: Escaping::default(),
+        }
+    }
+
+    pub fn highlight<'a>(&'a self, theme: &'a Theme) -> Styled<'a> {
+        self.styled().theme(theme)
+    }
 }
 
-impl fmt::Display for Jzon {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fn escape(s: &str) -> String {
-            let mut escaped: Vec<u8> = vec![];
-            for ch in s.bytes() {
-                match ch {
-                    0x08 | 0x09 | 0x0A | 0x0C | 0x0D | 0x22 | 0x2F | 0x5C => {
-                        escaped.push(b'\\');
-                        escaped.push(ASCII[ch as usize]);
+// Writes `s` as a string literal, handing `put` the plain runs and the
+// escapes in turn so nothing has to be allocated.
+pub(crate) fn escape<E>(
+    s: &str,
+    escaping: Escaping,
+    mut put: impl FnMut(&str) -> result::Result<(), E>,
+) -> result::Result<(), E> {
+    let mut short = [b'\\', 0];
+    let mut unicode = [0; 12];
+    let mut start = 0;
+    put("\"")?;
+    for (i, ch) in s.char_indices() {
+        let escaped = match ch {
+            '\x08' | '\t' | '\n' | '\x0C' | '\r' | '"' | '\\' | '/'
+                if ch != '/' || escaping.slash =>
+            {
+                short[1] = ASCII[ch as usize];
+                str::from_utf8(&short).unwrap()
+            }
+            '\0'..='\x1F' | '\x7F' | '\u{2028}' | '\u{2029}' => escape_unicode(ch, &mut unicode),
+            '<' | '>' | '&' if escaping.html => escape_unicode(ch, &mut unicode),
+            _ if escaping.ascii && !ch.is_ascii() => escape_unicode(ch, &mut unicode),
+            _ => continue,
+        };
+        put(&s[start..i])?;
+        put(escaped)?;
+        start = i + ch.len_utf8();
+    }
+    put(&s[start..])?;
+    put("\"")
+}
+
+// `ch` as `\uXXXX`, or two of them for a surrogate pair
+fn escape_unicode(ch: char, buf: &mut [u8; 12]) -> &str {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut len = 0;
+    for unit in ch.encode_utf16(&mut [0; 2]) {
+        buf[len..len + 2].copy_from_slice(b"\\u");
+        for k in 0..4 {
+            buf[len + 2 + k] = HEX[(*unit >> (12 - 4 * k) & 0xF) as usize];
+        }
+        len += 6;
+    }
+    str::from_utf8(&buf[..len]).unwrap()
+}
+
+// Number::toString of ECMA-262 §7.1.12.1, the shortest digits that read
+// back as `v`, which must be finite. Integral values have neither a point
+// nor an exponent below 1e21 and zero is "0", signed or not.
+pub(crate) fn write_number<W: fmt::Write>(out: &mut W, v: f64) -> fmt::Result {
+    if v == 0.0 {
+        return out.write_str("0");
+    }
+    if v < 0.0 {
+        out.write_str("-")?;
+    }
+
+    // `{:e}` yields the fewest digits that round-trip, e.g. "1.2345e-7",
+    // but ECMAScript also wants the closest of those, ties to even, which
+    // the exact formatting at that precision gives
+    let shortest = format!("{:e}", v.abs());
+    let precision = shortest.find('e').unwrap().saturating_sub(2);
+    let sci = format!("{:.*e}", precision, v.abs());
+    let (mantissa, exp) = sci.split_at(sci.find('e').unwrap());
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let digits = digits.trim_end_matches('0');
+    let k = digits.len() as i32;
+    // value = 0.digits * 10^n
+    let n = exp[1..].parse::<i32>().unwrap() + 1;
+    let zeros = |out: &mut W, count: i32| (0..count).try_for_each(|_| out.write_str("0"));
+
+    if k <= n && n <= 21 {
+        out.write_str(digits)?;
+        zeros(out, n - k)
+    } else if 0 < n && n <= 21 {
+        write!(out, "{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        out.write_str("0.")?;
+        zeros(out, -n)?;
+        out.write_str(digits)
+    } else {
+        out.write_str(&digits[..1])?;
+        if k > 1 {
+            write!(out, ".{}", &digits[1..])?;
+        }
+        write!(out, "e{}{}", if n > 0 { '+' } else { '-' }, (n - 1).abs())
+    }
+}
+
+#[cfg(feature = "std")]
+const BUF_SIZE: usize = 8 * 1024;
+
+// Gathers what the printer writes into one reused buffer and passes it on
+// in large writes. `fmt::Error` can't carry the io error, so it's kept here.
+#[cfg(feature = "std")]
+struct IoWriter<'a, W> {
+    inner: &'a mut W,
+    buf: Vec<u8>,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> IoWriter<'_, W> {
+    fn flush(&mut self) -> fmt::Result {
+        if let Err(e) = self.inner.write_all(&self.buf) {
+            self.error = Some(e);
+            return Err(fmt::Error);
+        }
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.buf.len() + s.len() > BUF_SIZE {
+            self.flush()?;
+        }
+        self.buf.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<'a> Styled<'a> {
+    pub fn theme(self, theme: &'a Theme) -> Self {
+        Styled {
+            theme: Some(theme),
+            ..self
+        }
+    }
+
+    // one value per line; `{:#}` alone indents by 2
+    pub fn indent(self, width: usize) -> Self {
+        Styled {
+            indent: Some(width),
+            ..self
+        }
+    }
+
+    pub fn sort_keys(self, sort_keys: bool) -> Self {
+        Styled { sort_keys, ..self }
+    }
+
+    pub fn escaping(self, escaping: Escaping) -> Self {
+        Styled { escaping, ..self }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut w = IoWriter {
+            inner: out,
+            buf: Vec::with_capacity(BUF_SIZE),
+            error: None,
+        };
+        match self
+            .write(&mut w, self.jz, self.indent, 0)
+            .and_then(|_| w.flush())
+        {
+            Ok(()) => Ok(()),
+            Err(_) => Err(w
+                .error
+                .unwrap_or_else(|| io::Error::other("formatter error"))),
+        }
+    }
+
+    fn paint(&self, role: fn(&Theme) -> &String) -> (&'a str, &'static str) {
+        match self.theme {
+            Some(theme) => (role(theme), RESET),
+            None => ("", ""),
+        }
+    }
+
+    fn write<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        jz: &Jzon,
+        indent: Option<usize>,
+        depth: usize,
+    ) -> fmt::Result {
+        let newline = |out: &mut W, depth: usize| match indent {
+            Some(width) => write!(out, "\n{:1$}", "", width * depth),
+            None => Ok(()),
+        };
+
+        let (on, off) = match jz {
+            Jzon::Null | Jzon::Error => self.paint(|t| &t.null),
+            Jzon::Bool(_) => self.paint(|t| &t.boolean),
+            Jzon::Double(_) | Jzon::Integer(_) => self.paint(|t| &t.number),
+            Jzon::String(_) => self.paint(|t| &t.string),
+            Jzon::Object(_) | Jzon::Array(_) => ("", ""),
+        };
+
+        match jz {
+            Jzon::Null | Jzon::Error => write!(out, "{}null{}", on, off),
+            Jzon::Bool(true) => write!(out, "{}true{}", on, off),
+            Jzon::Bool(false) => write!(out, "{}false{}", on, off),
+            Jzon::Double(v) if v.is_finite() => {
+                let mut num = String::new();
+                if *v == 0.0 && v.is_sign_negative() {
+                    num.push('-');
+                }
+                write_number(&mut num, *v)?;
+                // so it reads back as a double, not an integer
+                if !num.contains(['.', 'e']) {
+                    num.push_str(".0");
+                }
+                write!(out, "{}{}{}", on, num, off)
+            }
+            // NaN and the infinities have no JSON form, null is what
+            // JSON.stringify writes for them too
+            Jzon::Double(_) => write!(out, "{}null{}", on, off),
+            Jzon::Integer(v) => write!(out, "{}{}{}", on, v, off),
+            Jzon::String(v) => {
+                out.write_str(on)?;
+                escape(v, self.escaping, |s| out.write_str(s))?;
+                out.write_str(off)
+            }
+            Jzon::Object(map) if map.is_empty() => out.write_str("{}"),
+            Jzon::Object(map) => {
+                let (key_on, key_off) = self.paint(|t| &t.key);
+                out.write_str("{")?;
+                let mut entry = |i: usize, k: &String, v: &Jzon| {
+                    if i > 0 {
+                        out.write_str(",")?;
+                    }
+                    newline(out, depth + 1)?;
+                    out.write_str(key_on)?;
+                    escape(k, self.escaping, |s| out.write_str(s))?;
+                    out.write_str(key_off)?;
+                    out.write_str(if indent.is_some() { ": " } else { ":" })?;
+                    self.write(out, v, indent, depth + 1)
+                };
+                if self.sort_keys {
+                    let mut entries: Vec<_> = map.iter().collect();
+                    entries.sort_by(|a, b| a.0.cmp(b.0));
+                    for (i, (k, v)) in entries.into_iter().enumerate() {
+                        entry(i, k, v)?;
+                    }
+                } else {
+                    for (i, (k, v)) in map.iter().enumerate() {
+                        entry(i, k, v)?;
                     }
-                    _ => escaped.push(ch),
                 }
+                newline(out, depth)?;
+                out.write_str("}")
+            }
+            Jzon::Array(vec) if vec.is_empty() => out.write_str("[]"),
+            Jzon::Array(vec) => {
+                out.write_str("[")?;
+                for (i, v) in vec.iter().enumerate() {
+                    if i > 0 {
+                        out.write_str(",")?;
+                    }
+                    newline(out, depth + 1)?;
+                    self.write(out, v, indent, depth + 1)?;
+                }
+                newline(out, depth)?;
+                out.write_str("]")
             }
-            String::from_utf8(escaped).unwrap()
         }
+    }
+}
 
-        match self {
-            Jzon::Null => write!(f, "null"),
-            Jzon::Bool(true) => write!(f, "true"),
-            Jzon::Bool(false) => write!(f, "false"),
-            Jzon::Double(v) => write!(f, "{}", v),
-            Jzon::Integer(v) => write!(f, "{}", v),
-            Jzon::String(v) => write!(f, "\"{}\"", escape(&v)), // TODO: escaping
-            Jzon::Object(map) => write!(
-                f,
-                "{{{}}}",
-                map.iter()
-                    .map(|(k, v)| format!(r#""{}":{}"#, k, v))
-                    .collect::<Vec<_>>()
-                    .join(",")
-            ),
-            Jzon::Array(vec) => write!(
-                f,
-                "[{}]",
-                vec.iter()
-                    .map(|v| format!("{}", v))
-                    .collect::<Vec<_>>()
-                    .join(",")
-            ),
-        }
+impl fmt::Display for Jzon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.styled().fmt(f)
+    }
+}
+
+impl fmt::Display for Styled<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = match self.indent {
+            None if f.alternate() => Some(2),
+            indent => indent,
+        };
+        self.write(f, self.jz, indent, 0)
     }
 }
 
@@ -619,6 +975,130 @@ impl PartialEq<Jzon> for f64 {
     }
 }
 
+// Structural equality: numbers only equal numbers of the same variant, so
+// 1 and 1.0 differ. Doubles compare by value, except that NaN equals NaN,
+// whatever its payload, and -0.0 equals 0.0.
+impl PartialEq for Jzon {
+    fn eq(&self, other: &Jzon) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Jzon {}
+
+impl PartialOrd for Jzon {
+    fn partial_cmp(&self, other: &Jzon) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// A total order across types: null < error < booleans < numbers < strings
+// < arrays < objects. Integers and doubles are ordered together by value,
+// an integer first when they are equal, and NaN after every other number.
+// Strings compare bytewise, arrays element by element, and objects as
+// their members sorted by key.
+impl Ord for Jzon {
+    fn cmp(&self, other: &Jzon) -> Ordering {
+        fn rank(jz: &Jzon) -> u8 {
+            match jz {
+                Jzon::Null => 0,
+                Jzon::Error => 1,
+                Jzon::Bool(_) => 2,
+                Jzon::Integer(_) | Jzon::Double(_) => 3,
+                Jzon::String(_) => 4,
+                Jzon::Array(_) => 5,
+                Jzon::Object(_) => 6,
+            }
+        }
+
+        match (self, other) {
+            (Jzon::Bool(a), Jzon::Bool(b)) => a.cmp(b),
+            (Jzon::Integer(a), Jzon::Integer(b)) => a.cmp(b),
+            (Jzon::Double(a), Jzon::Double(b)) => cmp_double(*a, *b),
+            (Jzon::Integer(a), Jzon::Double(b)) => cmp_mixed(*a, *b),
+            (Jzon::Double(a), Jzon::Integer(b)) => cmp_mixed(*b, *a).reverse(),
+            (Jzon::String(a), Jzon::String(b)) => a.cmp(b),
+            (Jzon::Array(a), Jzon::Array(b)) => a.cmp(b),
+            (Jzon::Object(a), Jzon::Object(b)) => a.cmp(b),
+            _ => rank(self).cmp(&rank(other)),
+        }
+    }
+}
+
+fn cmp_double(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        _ => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+// exact, where converting either side could round
+fn cmp_mixed(a: i64, b: f64) -> Ordering {
+    // 2^63, the first double beyond i64
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if b.is_nan() || b >= LIMIT {
+        return Ordering::Less;
+    }
+    if b < -LIMIT {
+        return Ordering::Greater;
+    }
+    // the cast truncates, and is exact within the limits
+    let whole = b as i64;
+    match a.cmp(&whole) {
+        Ordering::Equal if b > whole as f64 => Ordering::Less,
+        Ordering::Equal if b < whole as f64 => Ordering::Greater,
+        // the integer goes first
+        Ordering::Equal => Ordering::Less,
+        ordering => ordering,
+    }
+}
+
+// agrees with `==`: all NaNs hash alike, as do 0.0 and -0.0, and members
+// hash the same in any order
+impl Hash for Jzon {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Jzon::Null => state.write_u8(0),
+            Jzon::Error => state.write_u8(1),
+            Jzon::Bool(v) => {
+                state.write_u8(2);
+                v.hash(state);
+            }
+            Jzon::Integer(v) => {
+                state.write_u8(3);
+                v.hash(state);
+            }
+            Jzon::Double(v) => {
+                state.write_u8(4);
+                let v = match v {
+                    v if v.is_nan() => f64::NAN,
+                    v if *v == 0.0 => 0.0,
+                    v => *v,
+                };
+                v.to_bits().hash(state);
+            }
+            Jzon::String(s) => {
+                state.write_u8(5);
+                s.hash(state);
+            }
+            Jzon::Array(vec) => {
+                state.write_u8(6);
+                vec.hash(state);
+            }
+            Jzon::Object(map) => {
+                state.write_u8(7);
+                state.write_usize(map.len());
+                // sorted, so equal maps hash their members in the same order
+                for member in map {
+                    member.hash(state);
+                }
+            }
+        }
+    }
+}
+
 // TODO: impl Iter
 impl ops::Index<usize> for Jzon {
     type Output = Jzon;
@@ -650,7 +1130,7 @@ impl iter::Iterator for Iter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    const JSON: &'static str = r#"
+    const JSON: &str = r#"
     {
         "string": "a string literal",
         "integer": -142,
@@ -667,7 +1147,7 @@ mod tests {
         let jz = Jzon::parse(JSON.as_bytes()).unwrap();
         assert_eq!(jz["string"], "a string literal");
         assert_eq!(jz["integer"], -142);
-        //assert_eq!(jz["double"], -0.34E+12);
+        assert_eq!(jz["double"], -0.34E+12);
         assert_eq!(jz["array"][0], "a");
         assert_eq!(jz["array"][1], "b");
         assert_eq!(jz["array"][2], "c");
@@ -694,6 +1174,28 @@ mod tests {
         } else {
             panic!();
         }
+        assert_eq!(Err(ExpectCommaBracket), Jzon::parse(b"[1 2]"));
+        assert_eq!(Err(ExpectCommaBracket), Jzon::parse(b"[[] {}]"));
+        assert_eq!(Err(ExpectCommaBrace), Jzon::parse(br#"{"a": 1 "b": 2}"#));
+        assert_eq!(Err(ExpectCommaBrace), Jzon::parse(br#"{"a": 1]"#));
+        assert_eq!(Err(ExpectPrefix), Jzon::parse(b"[1,]"));
+        assert_eq!(Err(ExpectPair), Jzon::parse(br#"{"a": 1,}"#));
+    }
+
+    #[test]
+    fn parse_offsets() {
+        let failed = |text: &str| Jzon::parse_with_offset(text.as_bytes()).unwrap_err();
+        assert_eq!((ExpectNoneControl, 2), failed("\"a\nb\""));
+        assert_eq!((ExpectEscaped, 3), failed(r#"["a\x"]"#));
+        assert_eq!((ExpectColon, 11), failed("[1,\n  {\"a\" 2}]"));
+        assert_eq!((ExpectPrefix, 15), failed("{\"a\": [1,\n  2, x]}"));
+        assert_eq!((ExpectCommaBracket, 3), failed("[1 2]"));
+        assert_eq!((ExpectCommaBrace, 8), failed(r#"{"a": 1 "b": 2}"#));
+        assert_eq!((ExpectBool, 1), failed("[tru]"));
+        assert_eq!((ExpectNoneEOF, 4), failed("[tr "));
+        assert_eq!((ExpectNoneEOF, 5), failed("[1, 2"));
+        assert_eq!((ExpectNoneEOF, 0), failed(""));
+        assert_eq!((ExpectNoMore, 2), failed("1 2"));
     }
 
     #[test]
@@ -716,6 +1218,18 @@ mod tests {
         assert_eq!(jz.value, -1.23E-10);
         let jz = Jzon::parse_number("-1E-10,".as_bytes()).unwrap();
         assert_eq!(jz.value, -1E-10);
+        let jz = Jzon::parse_number("333333333.33333329,".as_bytes()).unwrap();
+        assert_eq!(jz.value, 333333333.3333333);
+        let jz = Jzon::parse_number("0.000000000000000000000000001,".as_bytes()).unwrap();
+        assert_eq!(jz.value, 1e-27);
+        let jz = Jzon::parse_number("-9223372036854775808,".as_bytes()).unwrap();
+        assert_eq!(jz.value, i64::MIN);
+        let jz = Jzon::parse_number("295147905179352825856,".as_bytes()).unwrap();
+        assert_eq!(jz.value, 295147905179352825856.0);
+
+        let jz = Jzon::parse("-12".as_bytes()).unwrap();
+        assert_eq!(jz, -12);
+        assert!(Jzon::parse("[-12".as_bytes()).is_err());
 
         let jz = Jzon::parse_number("--1.23E-10,".as_bytes());
         assert!(jz.is_err());
@@ -738,11 +1252,7 @@ mod tests {
     #[test]
     fn parse_null() {
         let jz = Jzon::parse_null("null".as_bytes()).unwrap();
-        if let Jzon::Null = jz.value {
-            ()
-        } else {
-            panic!();
-        }
+        assert!(core::matches!(jz.value, Jzon::Null));
     }
 
     #[test]
@@ -786,7 +1296,7 @@ mod tests {
         assert_eq!(6, s.consumed);
 
         let s = Jzon::parse_unicode("\\ud801\\udc37".as_bytes()).unwrap();
-        assert_eq!('𐀷', s.value);
+        assert_eq!('𐐷', s.value);
         assert_eq!(12, s.consumed);
     }
 
@@ -807,6 +1317,193 @@ mod tests {
         assert_eq!("\"\\r\\n\\t\\f\\b\"", format!("{}", jz));
     }
 
+    #[test]
+    fn fmt_double() {
+        let cases = [
+            ("1.0", "1.0"),
+            ("-0.0", "-0.0"),
+            ("1e2", "100.0"),
+            ("1.5e-7", "1.5e-7"),
+            ("0.000001", "0.000001"),
+            ("1e21", "1e+21"),
+            ("123456789012345680000.0", "123456789012345680000.0"),
+            ("0.30000000000000004", "0.30000000000000004"),
+        ];
+        for (text, printed) in cases.iter() {
+            let jz = Jzon::parse(text.as_bytes()).unwrap();
+            assert!(core::matches!(jz, Jzon::Double(_)), "{}", text);
+            assert_eq!(*printed, jz.stringify());
+            assert_eq!(jz, Jzon::parse(jz.stringify().as_bytes()).unwrap());
+        }
+
+        // beyond what a double holds
+        let inf = Jzon::parse(b"1e400").unwrap();
+        assert_eq!(Jzon::Double(f64::INFINITY), inf);
+        assert_eq!("[null]", Jzon::Array(vec![inf]).stringify());
+        assert_eq!("null", Jzon::Double(f64::NAN).stringify());
+        assert_eq!("null", Jzon::Double(f64::NEG_INFINITY).stringify());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_to() {
+        struct Chunks(Vec<usize>);
+        impl io::Write for Chunks {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if self.0.len() == 3 {
+                    return Err(io::Error::other("full"));
+                }
+                self.0.push(buf.len());
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let content = std::fs::read("data/citm_catalog.json").unwrap();
+        let jz = Jzon::parse(&content).unwrap();
+        let mut out = vec![];
+        jz.styled().indent(2).write_to(&mut out).unwrap();
+        assert_eq!(jz.beautify().as_bytes(), &out[..]);
+
+        let mut chunks = Chunks(vec![]);
+        let err = jz.write_to(&mut chunks).unwrap_err();
+        assert_eq!("full", err.to_string());
+        assert!(chunks
+            .0
+            .iter()
+            .all(|&len| len > BUF_SIZE / 2 && len <= BUF_SIZE));
+
+        let jz = Jzon::String("\u{0}\u{1f}\u{7f}\"".to_string());
+        assert_eq!(r#""\u0000\u001f\u007f\"""#, jz.stringify());
+    }
+
+    #[test]
+    fn escaping() {
+        let jz = Jzon::String("a/<b>&\u{7f}\u{2028}é阿𐐷\u{1}".to_string());
+        assert_eq!(
+            r#""a\/<b>&\u007f\u2028é阿𐐷\u0001""#,
+            jz.stringify()
+        );
+        let all = Escaping {
+            ascii: true,
+            html: true,
+            slash: false,
+        };
+        assert_eq!(
+            r#""a/\u003cb\u003e\u0026\u007f\u2028\u00e9\u963f\ud801\udc37\u0001""#,
+            format!("{}", jz.styled().escaping(all))
+        );
+
+        // every mode reads back the same, whatever the escapes
+        let mut text = String::new();
+        for cp in (0..0x800).chain(0x2020..0x2030).chain(0xFFF0..0x10010) {
+            text.extend(char::from_u32(cp));
+        }
+        text.push('\u{10FFFF}');
+        let jz = Jzon::String(text.clone());
+        for &ascii in &[false, true] {
+            for &html in &[false, true] {
+                for &slash in &[false, true] {
+                    let escaping = Escaping { ascii, html, slash };
+                    let out = format!("{}", jz.styled().escaping(escaping));
+                    assert_eq!(text, Jzon::parse(out.as_bytes()).unwrap());
+                    assert!(!ascii || out.is_ascii());
+                    assert!(!html || !out.contains(|c| "<>&".contains(c)));
+                    assert_eq!(slash, out.contains("\\/"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn highlight() {
+        let theme = Theme::parse("key=1;34:string=32:number=33:bool=35:null=90:bogus=1:key=x");
+        assert_eq!("\x1b[1;34m", theme.key);
+        assert_eq!("\x1b[90m", theme.null);
+        let jz = Jzon::parse(r#"{"k": [1, "s", true, null]}"#.as_bytes()).unwrap();
+        assert_eq!(
+            "{\x1b[1;34m\"k\"\x1b[0m:[\x1b[33m1\x1b[0m,\x1b[32m\"s\"\x1b[0m,\x1b[35mtrue\x1b[0m,\x1b[90mnull\x1b[0m]}",
+            format!("{}", jz.highlight(&theme))
+        );
+        assert_eq!(r#"{"k":[1,"s",true,null]}"#, format!("{}", jz));
+    }
+
+    #[test]
+    fn pretty() {
+        let jz = Jzon::parse(r#"{"b": [1, {}], "a": {"c": []}}"#.as_bytes()).unwrap();
+        assert_eq!(
+            "{\n  \"a\": {\n    \"c\": []\n  },\n  \"b\": [\n    1,\n    {}\n  ]\n}",
+            format!("{:#}", jz.styled().sort_keys(true))
+        );
+        assert_eq!(
+            "{\n\"a\": {\n\"c\": []\n},\n\"b\": [\n1,\n{}\n]\n}",
+            format!("{}", jz.styled().sort_keys(true).indent(0))
+        );
+        assert_eq!(
+            r#"{"a":{"c":[]},"b":[1,{}]}"#,
+            format!("{}", jz.styled().sort_keys(true))
+        );
+        assert_eq!(
+            "[\n    1\n]",
+            format!("{:#}", Jzon::parse(b"[1]").unwrap().styled().indent(4))
+        );
+    }
+
+    #[test]
+    fn compare() {
+        use std::collections::HashSet;
+
+        let parse = |text: &str| Jzon::parse(text.as_bytes()).unwrap();
+        let a = parse(r#"{"a": [1, 2.5, null], "b": {"c": "d"}}"#);
+        let b = parse(r#"{"b": {"c": "d"}, "a": [1, 2.5, null]}"#);
+        assert_eq!(a, b);
+        assert_eq!(a, a.clone());
+        assert_ne!(a, parse(r#"{"a": [1, 2.5, null], "b": {"c": "e"}}"#));
+        assert_ne!(Jzon::Integer(1), Jzon::Double(1.0));
+        assert_eq!(Jzon::Double(f64::NAN), Jzon::Double(-f64::NAN));
+        assert_eq!(Jzon::Double(0.0), Jzon::Double(-0.0));
+
+        let mut values = vec![
+            parse(r#"{"a": 2}"#),
+            parse(r#"{"a": 1, "b": 0}"#),
+            parse("[1, 2]"),
+            parse("[1]"),
+            parse(r#""b""#),
+            parse(r#""a""#),
+            Jzon::Double(f64::NAN),
+            Jzon::Double(9.3e18),
+            Jzon::Integer(i64::MAX),
+            Jzon::Double(1.0),
+            Jzon::Integer(1),
+            Jzon::Double(-0.5),
+            Jzon::Integer(i64::MIN),
+            Jzon::Double(f64::NEG_INFINITY),
+            Jzon::Bool(true),
+            Jzon::Bool(false),
+            Jzon::Error,
+            Jzon::Null,
+        ];
+        let expected = values.iter().rev().cloned().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(expected, values);
+
+        let set: HashSet<_> = vec![
+            a.clone(),
+            b,
+            Jzon::Double(-0.0),
+            Jzon::Double(0.0),
+            Jzon::Double(f64::NAN),
+            Jzon::Double(-f64::NAN),
+            Jzon::Integer(0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(4, set.len());
+        assert!(set.contains(&a));
+    }
+
     #[test]
     #[should_panic]
     fn index() {
@@ -0,0 +1,561 @@
+use crate::diff::{Path, Segment};
+use crate::jzon::{Jzon, ParseErr};
+use std::borrow::Cow;
+use std::result;
+use std::str;
+
+// On-demand access in the spirit of simdjson's On-Demand front end.
+// `Jzon::lazy` checks the whole text once without building anything; after
+// that, lookups scan past the values in their way by matching brackets, and
+// strings and numbers are only converted when asked for.
+
+type Result<T> = result::Result<T, ParseErr>;
+
+// values nested deeper than this fail the check with `ExpectShallow` rather
+// than recursing until the stack runs out
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Lazy<'a> {
+    bytes: &'a [u8],
+    // the first byte of the value
+    pos: usize,
+}
+
+use ParseErr::*;
+
+impl Jzon {
+    pub fn lazy(bytes: &[u8]) -> Result<Lazy<'_>> {
+        // outside strings anything but ASCII is an error anyway
+        str::from_utf8(bytes).map_err(|_| ExpectCodePoint)?;
+        let pos = space(bytes, 0);
+        let end = space(bytes, check(bytes, pos, 0)?);
+        if end < bytes.len() {
+            return Err(ExpectNoMore);
+        }
+        Ok(Lazy { bytes, pos })
+    }
+}
+
+impl<'a> Lazy<'a> {
+    // the last member named `key`, the one `Jzon::parse` keeps, so all the
+    // members are scanned
+    pub fn get(&self, key: &str) -> Option<Lazy<'a>> {
+        self.members()
+            .filter(|(k, _)| k == key)
+            .last()
+            .map(|(_, v)| v)
+    }
+
+    pub fn at(&self, idx: usize) -> Option<Lazy<'a>> {
+        self.elements().nth(idx)
+    }
+
+    // indices also match object keys, as everywhere else paths are used
+    pub fn get_path(&self, path: &Path) -> Option<Lazy<'a>> {
+        path.0.iter().try_fold(*self, |lazy, seg| match seg {
+            Segment::Key(k) => lazy.get(k),
+            Segment::Index(i) if lazy.is_object() => lazy.get(&i.to_string()),
+            Segment::Index(i) => lazy.at(*i),
+        })
+    }
+
+    pub fn pointer(&self, pointer: &str) -> Option<Lazy<'a>> {
+        self.get_path(&Path::from_pointer(pointer)?)
+    }
+
+    // empty unless this is an object
+    pub fn members(&self) -> Members<'a> {
+        let pos = match self.bytes[self.pos] {
+            b'{' => Some(space(self.bytes, self.pos + 1)),
+            _ => None,
+        };
+        Members {
+            bytes: self.bytes,
+            pos,
+            last: None,
+        }
+    }
+
+    // empty unless this is an array
+    pub fn elements(&self) -> Elements<'a> {
+        let pos = match self.bytes[self.pos] {
+            b'[' => Some(space(self.bytes, self.pos + 1)),
+            _ => None,
+        };
+        Elements {
+            bytes: self.bytes,
+            pos,
+            last: None,
+        }
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.bytes[self.pos] == b'{'
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.bytes[self.pos] == b'['
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.bytes[self.pos] == b'n'
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.bytes[self.pos] {
+            b't' => Some(true),
+            b'f' => Some(false),
+            _ => None,
+        }
+    }
+
+    // borrowed from the text unless there are escapes to decode
+    pub fn as_str(&self) -> Option<Cow<'a, str>> {
+        match self.bytes[self.pos] {
+            b'"' => Some(decode(&self.bytes[self.pos + 1..self.end() - 1])),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.number()? {
+            Jzon::Integer(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.number()? {
+            Jzon::Integer(v) => Some(v as f64),
+            Jzon::Double(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    // the value's text as it appears in the document
+    pub fn raw(&self) -> &'a str {
+        str::from_utf8(&self.bytes[self.pos..self.end()]).unwrap()
+    }
+
+    // the whole subtree, parsed for real
+    pub fn to_jzon(&self) -> Jzon {
+        Jzon::parse(&self.bytes[self.pos..self.end()]).expect("checked by Jzon::lazy")
+    }
+
+    fn number(&self) -> Option<Jzon> {
+        match self.bytes[self.pos] {
+            b'-' | b'0'..=b'9' => Jzon::parse_number(&self.bytes[self.pos..])
+                .ok()
+                .map(|state| state.value),
+            _ => None,
+        }
+    }
+
+    fn end(&self) -> usize {
+        skip(self.bytes, self.pos)
+    }
+}
+
+// Both iterators only skip past a value when asked for the next one, so
+// taking an entry never scans what it holds.
+pub struct Members<'a> {
+    bytes: &'a [u8],
+    // the next key, `None` once the object is done
+    pos: Option<usize>,
+    // the value handed out last
+    last: Option<usize>,
+}
+
+impl<'a> Iterator for Members<'a> {
+    type Item = (Cow<'a, str>, Lazy<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes;
+        if let Some(last) = self.last.take() {
+            self.pos = next_entry(bytes, last);
+        }
+        let pos = self.pos.filter(|&pos| bytes[pos] == b'"')?;
+        let key_end = skip_string(bytes, pos);
+        let key = decode(&bytes[pos + 1..key_end - 1]);
+        let value = space(bytes, space(bytes, key_end) + 1);
+        self.last = Some(value);
+        Some((key, Lazy { bytes, pos: value }))
+    }
+}
+
+pub struct Elements<'a> {
+    bytes: &'a [u8],
+    // the next element, `None` once the array is done
+    pos: Option<usize>,
+    last: Option<usize>,
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = Lazy<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes;
+        if let Some(last) = self.last.take() {
+            self.pos = next_entry(bytes, last);
+        }
+        let pos = self.pos.filter(|&pos| bytes[pos] != b']')?;
+        self.last = Some(pos);
+        Some(Lazy { bytes, pos })
+    }
+}
+
+// where the entry after the value at `pos` starts, if there is one
+fn next_entry(bytes: &[u8], pos: usize) -> Option<usize> {
+    let end = space(bytes, skip(bytes, pos));
+    match bytes[end] {
+        b',' => Some(space(bytes, end + 1)),
+        _ => None,
+    }
+}
+
+fn space(bytes: &[u8], pos: usize) -> usize {
+    let n = bytes[pos.min(bytes.len())..]
+        .iter()
+        .take_while(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+        .count();
+    pos + n
+}
+
+fn byte(bytes: &[u8], pos: usize) -> Result<u8> {
+    bytes.get(pos).copied().ok_or(ExpectNoneEOF)
+}
+
+// checks the value at `pos`, inside `depth` others, as strictly as
+// `Jzon::parse`, returning its end
+fn check(bytes: &[u8], pos: usize, depth: usize) -> Result<usize> {
+    if depth == MAX_DEPTH {
+        return Err(ExpectShallow);
+    }
+    match byte(bytes, pos)? {
+        b'{' => {
+            let mut p = space(bytes, pos + 1);
+            if byte(bytes, p)? == b'}' {
+                return Ok(p + 1);
+            }
+            loop {
+                if byte(bytes, p)? != b'"' {
+                    return Err(ExpectPair);
+                }
+                p = space(bytes, check_string(bytes, p)?);
+                if byte(bytes, p)? != b':' {
+                    return Err(ExpectColon);
+                }
+                p = space(bytes, check(bytes, space(bytes, p + 1), depth + 1)?);
+                match byte(bytes, p)? {
+                    b',' => p = space(bytes, p + 1),
+                    b'}' => return Ok(p + 1),
+                    _ => return Err(ExpectCommaBrace),
+                }
+            }
+        }
+        b'[' => {
+            let mut p = space(bytes, pos + 1);
+            if byte(bytes, p)? == b']' {
+                return Ok(p + 1);
+            }
+            loop {
+                p = space(bytes, check(bytes, p, depth + 1)?);
+                match byte(bytes, p)? {
+                    b',' => p = space(bytes, p + 1),
+                    b']' => return Ok(p + 1),
+                    _ => return Err(ExpectCommaBracket),
+                }
+            }
+        }
+        b'"' => check_string(bytes, pos),
+        b't' => check_literal(bytes, pos, b"true", ExpectBool),
+        b'f' => check_literal(bytes, pos, b"false", ExpectBool),
+        b'n' => check_literal(bytes, pos, b"null", ExpectNull),
+        b'-' | b'0'..=b'9' => check_number(bytes, pos),
+        _ => Err(ExpectPrefix),
+    }
+}
+
+fn check_string(bytes: &[u8], pos: usize) -> Result<usize> {
+    let mut p = pos + 1;
+    loop {
+        p += bytes[p..]
+            .iter()
+            .position(|&b| QUOTES[b as usize] || b < 0x20)
+            .ok_or(ExpectNoneEOF)?;
+        match bytes[p] {
+            b'"' => return Ok(p + 1),
+            b'\\' => p += Jzon::parse_escaped(&bytes[p..])?.consumed,
+            // according to ECMA-404
+            _ => return Err(ExpectNoneControl),
+        }
+    }
+}
+
+fn check_literal(bytes: &[u8], pos: usize, word: &[u8], err: ParseErr) -> Result<usize> {
+    match bytes.get(pos..pos + word.len()) {
+        Some(found) if found == word => Ok(pos + word.len()),
+        Some(_) => Err(err),
+        None => Err(ExpectNoneEOF),
+    }
+}
+
+// the grammar of `Jzon::parse_number` without computing the value
+fn check_number(bytes: &[u8], pos: usize) -> Result<usize> {
+    let digits = |p: usize| {
+        let n = bytes[p.min(bytes.len())..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if n == 0 {
+            Err(ExpectDigit)
+        } else {
+            Ok(p + n)
+        }
+    };
+    let mut p = pos;
+    if bytes[p] == b'-' {
+        p += 1;
+    }
+    p = match bytes.get(p) {
+        Some(b'0') => p + 1,
+        _ => digits(p)?,
+    };
+    if bytes.get(p) == Some(&b'.') {
+        p = digits(p + 1)?;
+    }
+    if let Some(b'e') | Some(b'E') = bytes.get(p) {
+        p += 1;
+        if let Some(b'+') | Some(b'-') = bytes.get(p) {
+            p += 1;
+        }
+        p = digits(p)?;
+    }
+    Ok(p)
+}
+
+// the bytes `skip` stops at, inside and outside strings
+const BRACKETS: [bool; 256] = class(b"{[]}\"");
+const QUOTES: [bool; 256] = class(b"\"\\");
+
+const fn class(members: &[u8]) -> [bool; 256] {
+    let mut table = [false; 256];
+    let mut i = 0;
+    while i < members.len() {
+        table[members[i] as usize] = true;
+        i += 1;
+    }
+    table
+}
+
+// where the already checked value at `pos` ends
+fn skip(bytes: &[u8], pos: usize) -> usize {
+    match bytes[pos] {
+        b'"' => skip_string(bytes, pos),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut p = pos;
+            loop {
+                p += bytes[p..]
+                    .iter()
+                    .position(|&b| BRACKETS[b as usize])
+                    .unwrap();
+                match bytes[p] {
+                    b'{' | b'[' => depth += 1,
+                    b'"' => {
+                        p = skip_string(bytes, p);
+                        continue;
+                    }
+                    _ => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return p + 1;
+                        }
+                    }
+                }
+                p += 1;
+            }
+        }
+        _ => {
+            let n = bytes[pos..]
+                .iter()
+                .take_while(|b| !matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r'))
+                .count();
+            pos + n
+        }
+    }
+}
+
+fn skip_string(bytes: &[u8], pos: usize) -> usize {
+    let mut p = pos + 1;
+    loop {
+        let n = bytes[p..].iter().position(|&b| QUOTES[b as usize]).unwrap();
+        if bytes[p + n] == b'"' {
+            return p + n + 1;
+        }
+        p += n + 2;
+    }
+}
+
+// the checked contents of a string literal
+fn decode(raw: &[u8]) -> Cow<'_, str> {
+    if !raw.contains(&b'\\') {
+        return Cow::Borrowed(str::from_utf8(raw).unwrap());
+    }
+    let mut s = String::with_capacity(raw.len());
+    let mut p = 0;
+    while p < raw.len() {
+        if raw[p] == b'\\' {
+            let escaped = Jzon::parse_escaped(&raw[p..]).unwrap();
+            s.push(escaped.value);
+            p += escaped.consumed;
+        } else {
+            let n = raw[p..]
+                .iter()
+                .position(|&b| b == b'\\')
+                .unwrap_or(raw.len() - p);
+            s.push_str(str::from_utf8(&raw[p..p + n]).unwrap());
+            p += n;
+        }
+    }
+    Cow::Owned(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access() {
+        let text = br#" {"a": 1, "a": {"b]": "}\"]", "c": [1, -2.5e1, [], {}]}, "escape": "x\ty",
+            "t": true, "f": false, "n": null, "0": "zero", "d": {"x": 1}, "d": 2} "#;
+        let lazy = Jzon::lazy(text).unwrap();
+        assert_eq!(r#"}"]"#, lazy.pointer("/a/b]").unwrap().as_str().unwrap());
+        assert!(matches!(
+            lazy.get("0").unwrap().as_str(),
+            Some(Cow::Borrowed(_))
+        ));
+        let c = lazy.pointer("/a/c").unwrap();
+        assert_eq!(Some(1), c.at(0).unwrap().as_i64());
+        assert_eq!(None, c.at(1).unwrap().as_i64());
+        assert_eq!(Some(-25.0), c.at(1).unwrap().as_f64());
+        assert_eq!("[]", c.at(2).unwrap().raw());
+        assert_eq!("{}", c.at(3).unwrap().raw());
+        assert!(c.at(4).is_none());
+        assert_eq!(4, c.elements().count());
+        assert_eq!(0, c.at(2).unwrap().elements().count());
+        assert_eq!("x\ty", lazy.get("escape").unwrap().as_str().unwrap());
+        assert_eq!(Some(true), lazy.get("t").unwrap().as_bool());
+        assert_eq!(Some(false), lazy.get("f").unwrap().as_bool());
+        assert!(lazy.get("n").unwrap().is_null());
+        assert_eq!("zero", lazy.pointer("/0").unwrap().as_str().unwrap());
+        assert!(lazy.get("a").unwrap().is_object());
+        assert!(lazy.pointer("/a/c/9").is_none());
+        assert!(lazy.pointer("/t/x").is_none());
+        assert!(lazy.get("t").unwrap().as_str().is_none());
+
+        let keys: Vec<_> = lazy.members().map(|(k, _)| k).collect();
+        assert_eq!(vec!["a", "a", "escape", "t", "f", "n", "0", "d", "d"], keys);
+        assert_eq!(
+            r#"{"b]":"}\"]","c":[1,-25.0,[],{}]}"#,
+            format!(
+                "{}",
+                lazy.pointer("/a")
+                    .unwrap()
+                    .to_jzon()
+                    .styled()
+                    .sort_keys(true)
+            )
+        );
+
+        // of duplicate keys the last counts, as in `Jzon::parse`
+        let jz = Jzon::parse(text).unwrap();
+        let pointers = [
+            "", "/a", "/a/b]", "/a/c/1", "/a/c/9", "/0", "/t/x", "/d", "/d/x",
+        ];
+        for pointer in pointers.iter() {
+            assert_eq!(
+                jz.pointer(pointer),
+                lazy.pointer(pointer).map(|v| v.to_jzon()).as_ref(),
+                "{}",
+                pointer
+            );
+        }
+    }
+
+    #[test]
+    fn twitter() {
+        let content = std::fs::read("data/twitter.json").unwrap();
+        let jz = Jzon::parse(&content).unwrap();
+        let lazy = Jzon::lazy(&content).unwrap();
+        assert_eq!(
+            jz["search_metadata"]["count"],
+            lazy.pointer("/search_metadata/count")
+                .unwrap()
+                .as_i64()
+                .unwrap()
+        );
+        let statuses = lazy.get("statuses").unwrap();
+        for (i, status) in statuses.elements().enumerate() {
+            let user = status.get("user").unwrap();
+            assert_eq!(
+                jz["statuses"][i]["user"]["screen_name"],
+                user.get("screen_name").unwrap().as_str().unwrap().as_ref()
+            );
+            assert_eq!(
+                jz["statuses"][i]["text"],
+                status.get("text").unwrap().as_str().unwrap().as_ref()
+            );
+        }
+        assert_eq!(
+            format!("{}", jz.styled().sort_keys(true)),
+            format!("{}", lazy.to_jzon().styled().sort_keys(true))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        // `Jzon::parse` rejects the same texts, if not always for the
+        // same reason
+        let cases = [
+            (ExpectNoneEOF, ""),
+            (ExpectNoneEOF, "[1, 2"),
+            (ExpectNoneEOF, r#"{"a"#),
+            (ExpectNoMore, "1 2"),
+            (ExpectCommaBracket, "[1 2]"),
+            (ExpectCommaBrace, r#"{"a": 1]"#),
+            (ExpectPrefix, "[1,]"),
+            (ExpectPair, r#"{"a": 1,}"#),
+            (ExpectColon, r#"{"a" 1}"#),
+            (ExpectDigit, "[1.]"),
+            (ExpectDigit, "-"),
+            (ExpectDigit, "1e+"),
+            (ExpectNoMore, "01"),
+            (ExpectBool, "[tru]"),
+            (ExpectNull, "nul "),
+            (ExpectNoneControl, "\"a\nb\""),
+            (ExpectEscaped, r#""\x""#),
+            (ExpectCodePoint, r#""\ud801xxxxxxx""#),
+        ];
+        for (err, text) in cases.iter() {
+            assert_eq!(
+                Err(*err),
+                Jzon::lazy(text.as_bytes()).map(|_| ()),
+                "{}",
+                text
+            );
+            assert!(Jzon::parse(text.as_bytes()).is_err(), "{}", text);
+        }
+
+        // refused before the recursion runs out of stack
+        let nested = |depth: usize| format!("{}0{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Jzon::lazy(nested(127).as_bytes()).is_ok());
+        assert_eq!(
+            Err(ExpectShallow),
+            Jzon::lazy(nested(128).as_bytes()).map(|_| ())
+        );
+        let deep = "[".repeat(200_000);
+        assert_eq!(Err(ExpectShallow), Jzon::lazy(deep.as_bytes()).map(|_| ()));
+    }
+}
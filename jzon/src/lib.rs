@@ -1,5 +1,102 @@
-#![feature(try_trait)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Only the parser, `Jzon` and its printer are there without std.
+extern crate alloc;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+#[cfg(feature = "std")]
+mod base64;
+#[cfg(feature = "std")]
+pub mod canonical;
+#[cfg(feature = "std")]
+pub mod cbor;
+#[cfg(feature = "std")]
+pub mod codegen;
+#[cfg(feature = "std")]
+pub mod compact;
+#[cfg(feature = "std")]
+pub mod convert;
+#[cfg(feature = "std")]
+pub mod cst;
+#[cfg(feature = "std")]
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod encoding;
+#[cfg(feature = "std")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod geojson;
 pub mod jzon;
+#[cfg(feature = "std")]
+pub mod lazy;
+pub mod map;
+#[cfg(feature = "std")]
+pub mod msgpack;
+#[cfg(feature = "std")]
+pub mod push;
+#[cfg(feature = "std")]
+pub mod reference;
+#[cfg(feature = "std")]
+pub mod schema;
+#[cfg(feature = "std")]
+pub mod shared;
+#[cfg(feature = "std")]
+pub mod tolerant;
+#[cfg(feature = "std")]
+pub mod writer;
+#[cfg(feature = "std")]
+pub use self::canonical::CanonicalErr;
+#[cfg(feature = "std")]
+pub use self::cbor::CborErr;
+#[cfg(feature = "std")]
+pub use self::compact::Compact;
+#[cfg(feature = "std")]
+pub use self::compact::Symbols;
+#[cfg(feature = "std")]
+pub use self::convert::ConvertErr;
+#[cfg(feature = "std")]
+pub use self::convert::FromJzon;
+#[cfg(feature = "std")]
+pub use self::convert::ToJzon;
+#[cfg(feature = "std")]
+pub use self::cst::Cst;
+#[cfg(feature = "std")]
+pub use self::cst::CstErr;
+#[cfg(feature = "std")]
+pub use self::encoding::Bom;
+#[cfg(feature = "std")]
+pub use self::encoding::EncodingErr;
+#[cfg(feature = "std")]
+pub use self::geojson::GeoErr;
+#[cfg(feature = "std")]
+pub use self::geojson::GeoJson;
+pub use self::jzon::Escaping;
 pub use self::jzon::Iter;
 pub use self::jzon::Jzon;
 pub use self::jzon::ParseErr;
+pub use self::jzon::Styled;
+pub use self::jzon::Theme;
+#[cfg(feature = "std")]
+pub use self::lazy::Lazy;
+pub use self::map::Map;
+#[cfg(feature = "std")]
+pub use self::msgpack::MsgpackErr;
+#[cfg(feature = "std")]
+pub use self::push::PushParser;
+#[cfg(feature = "std")]
+pub use self::reference::Loader;
+#[cfg(feature = "std")]
+pub use self::reference::RefErr;
+#[cfg(feature = "std")]
+pub use self::reference::Resolver;
+#[cfg(feature = "std")]
+pub use self::schema::Inferrer;
+#[cfg(feature = "std")]
+pub use self::shared::Shared;
+#[cfg(feature = "std")]
+pub use self::tolerant::Diagnostic;
+#[cfg(feature = "std")]
+pub use self::writer::JzonWriter;
+#[cfg(feature = "std")]
+pub use self::writer::WriterErr;
@@ -1,15 +1,38 @@
-#![feature(try_trait)]
 extern crate jzon;
+use jzon::compact::{Compact, Symbols};
 use jzon::jzon::Jzon;
+use std::alloc::{GlobalAlloc, Layout, System};
 use std::fs;
 use std::io;
+use std::mem;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time;
 
 const UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
 const PASSED_MARK: &str = ":heavy_check_mark:";
 const FAILED_MARK: &str = ":x:";
 
+// counts the bytes allocated and not yet freed, for the memory report
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
 fn main() {
     print!(r#"## Jzon
 A simple and ease-of-use JSON library in Rust.
@@ -20,7 +43,6 @@ A simple and ease-of-use JSON library in Rust.
 - TODO: impl Iterator trait
 - TODO: impl Deref trait
 - TODO: impl From trait
-- FIXME: float point number parsing precision
 
 ## Sample Results
 Sample files from [JSON\_checker](http://www.json.org/JSON\_checker/).
@@ -29,17 +51,39 @@ P.S.: `fail01.json` is excluded as it is relaxed in RFC7159. `fail18.json` is ex
 ### Roundtrip
 "#);
     print_table_header();
-    let _ = test_json_dir(&Path::new("data/roundtrip"));
+    let _ = test_json_dir(Path::new("data/roundtrip"));
 
     println!("\n### Corner Cases");
     print_table_header();
-    let _ = test_json_dir(&Path::new("data/jsonchecker"));
+    let _ = test_json_dir(Path::new("data/jsonchecker"));
 
     println!("\n### Big Files");
     print_table_header();
-    test_json_file(&Path::new("data/canada.json"));
-    test_json_file(&Path::new("data/twitter.json"));
-    test_json_file(&Path::new("data/citm_catalog.json"));
+    test_json_file(Path::new("data/canada.json"));
+    test_json_file(Path::new("data/twitter.json"));
+    test_json_file(Path::new("data/citm_catalog.json"));
+
+    println!("\n### On Demand");
+    println!("Reading four fields of `twitter.json`, after a full parse and lazily.\n");
+    print_table_header();
+    bench_lazy(Path::new("data/twitter.json"));
+
+    println!("\n### Memory");
+    println!(
+        "Heap taken by each tree; a `Jzon` node is {} bytes, a `Compact` one {}.\n",
+        mem::size_of::<Jzon>(),
+        mem::size_of::<Compact>()
+    );
+    print_memory_line("file", "jzon", "compact", "interned");
+    print_memory_line(
+        &format!(":{}", "-".repeat(17)),
+        "-----:",
+        &format!("{}:", "-".repeat(13)),
+        &format!("{}:", "-".repeat(13)),
+    );
+    measure_memory(Path::new("data/canada.json"));
+    measure_memory(Path::new("data/twitter.json"));
+    measure_memory(Path::new("data/citm_catalog.json"));
 }
 
 // m a -> (a -> m b) -> m b
@@ -52,7 +96,7 @@ fn test_json_dir(dir: &Path) -> io::Result<()> {
             if ext != "json" {
                 continue;
             }
-            let _ = test_json_file(&path);
+            test_json_file(&path);
         }
     }
     Ok(())
@@ -74,6 +118,81 @@ fn test_json_file(path: &Path) {
     print_table_line(file, passed, &size_str(size), &format!("{:.3?}", cost));
 }
 
+const ROUNDS: u32 = 20;
+
+fn bench_lazy(path: &Path) {
+    let content = fs::read(path).unwrap();
+    let size = size_str(content.len());
+    let mut fields = vec![];
+
+    let start = time::Instant::now();
+    for _ in 0..ROUNDS {
+        let jz = Jzon::parse(&content).unwrap();
+        fields.push(jz["search_metadata"]["count"].stringify());
+        fields.push(jz["statuses"][0]["user"]["screen_name"].stringify());
+        fields.push(jz["statuses"][50]["text"].stringify());
+        fields.push(jz["statuses"][99]["id"].stringify());
+    }
+    let cost = start.elapsed() / ROUNDS;
+    print_table_line("parse", PASSED_MARK, &size, &format!("{:.3?}", cost));
+
+    let start = time::Instant::now();
+    for _ in 0..ROUNDS {
+        let lazy = Jzon::lazy(&content).unwrap();
+        let field = |pointer| lazy.pointer(pointer).unwrap().raw().to_string();
+        fields.push(field("/search_metadata/count"));
+        fields.push(field("/statuses/0/user/screen_name"));
+        fields.push(field("/statuses/50/text"));
+        fields.push(field("/statuses/99/id"));
+    }
+    let cost = start.elapsed() / ROUNDS;
+    let (parsed, lazy) = fields.split_at(fields.len() / 2);
+    let passed = if parsed == lazy {
+        PASSED_MARK
+    } else {
+        FAILED_MARK
+    };
+    print_table_line("lazy", passed, &size, &format!("{:.3?}", cost));
+}
+
+// the heap `build` leaves allocated
+fn heap<T>(build: impl FnOnce() -> T) -> (T, usize) {
+    let before = LIVE.load(Ordering::Relaxed);
+    let built = build();
+    (built, LIVE.load(Ordering::Relaxed) - before)
+}
+
+fn measure_memory(path: &Path) {
+    let file = path.file_name().unwrap().to_str().unwrap();
+    let content = fs::read(path).unwrap();
+    let (jz, tree) = heap(|| Jzon::parse(&content).unwrap());
+    drop(jz);
+    let (doc, compact) = heap(|| Compact::parse(&content).unwrap());
+    drop(doc);
+    // the symbol table counts too
+    let (doc, interned) = heap(|| {
+        let mut symbols = Symbols::new();
+        let doc = Compact::parse_interned(&content, &mut symbols).unwrap();
+        (doc, symbols)
+    });
+    drop(doc);
+    let saved = |size: usize| {
+        format!(
+            "{} ({:+.0}%)",
+            size_str(size),
+            (size as f64 / tree as f64 - 1.0) * 100.0
+        )
+    };
+    print_memory_line(file, &size_str(tree), &saved(compact), &saved(interned));
+}
+
+fn print_memory_line(file: &str, tree: &str, compact: &str, interned: &str) {
+    println!(
+        "| {:<18} | {:>6} | {:>14} | {:>14} |",
+        file, tree, compact, interned
+    );
+}
+
 fn size_str(len: usize) -> String {
     let mut size: f64 = len as f64;
     let mut e = 0;
@@ -0,0 +1,250 @@
+use crate::jzon::Jzon;
+use alloc::string::String;
+use alloc::vec::{self, Vec};
+use core::fmt;
+use core::iter::FromIterator;
+use core::mem;
+use core::ops;
+use core::slice;
+
+// Members of an object as a vector sorted by key, the same with or without
+// std. Most objects have a few members, for which a binary search is as
+// quick as hashing and the vector takes 24 bytes and no spare nodes, so a
+// `Jzon` is 32 bytes instead of the 56 a hash map made it.
+//
+// Inserting a key out of order shifts the members after it, so objects
+// that may be large are better built with `collect`, which sorts once.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Map {
+    entries: Vec<(String, Jzon)>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Map::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Map {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn find(&self, key: &str) -> Result<usize, usize> {
+        self.entries.binary_search_by(|(k, _)| k.as_str().cmp(key))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Jzon> {
+        let idx = self.find(key).ok()?;
+        Some(&self.entries[idx].1)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Jzon> {
+        let idx = self.find(key).ok()?;
+        Some(&mut self.entries[idx].1)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.find(key).is_ok()
+    }
+
+    // the value the key had before, if any
+    pub fn insert(&mut self, key: String, value: Jzon) -> Option<Jzon> {
+        match self.find(&key) {
+            Ok(idx) => Some(mem::replace(&mut self.entries[idx].1, value)),
+            Err(idx) => {
+                self.entries.insert(idx, (key, value));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Jzon> {
+        let idx = self.find(key).ok()?;
+        Some(self.entries.remove(idx).1)
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.entries.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut(self.entries.iter_mut())
+    }
+
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &String> + ExactSizeIterator {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &Jzon> + ExactSizeIterator {
+        self.iter().map(|(_, v)| v)
+    }
+
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Jzon> + ExactSizeIterator {
+        self.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl ops::Index<&str> for Map {
+    type Output = Jzon;
+    fn index(&self, key: &str) -> &Jzon {
+        self.get(key).expect("no such key")
+    }
+}
+
+// where a key comes more than once the last value wins, as with `insert`
+impl FromIterator<(String, Jzon)> for Map {
+    fn from_iter<I: IntoIterator<Item = (String, Jzon)>>(iter: I) -> Self {
+        let mut entries: Vec<_> = iter.into_iter().collect();
+        // stable, so equal keys stay in the order they came in
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries.dedup_by(|later, earlier| {
+            let same = later.0 == earlier.0;
+            if same {
+                mem::swap(later, earlier);
+            }
+            same
+        });
+        Map { entries }
+    }
+}
+
+impl Extend<(String, Jzon)> for Map {
+    fn extend<I: IntoIterator<Item = (String, Jzon)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = (String, Jzon);
+    type IntoIter = vec::IntoIter<(String, Jzon)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Map {
+    type Item = (&'a String, &'a Jzon);
+    type IntoIter = Iter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Map {
+    type Item = (&'a String, &'a mut Jzon);
+    type IntoIter = IterMut<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// in key order
+pub struct Iter<'a>(slice::Iter<'a, (String, Jzon)>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a String, &'a Jzon);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+// the keys stay shared, changing them would break the order
+pub struct IterMut<'a>(slice::IterMut<'a, (String, Jzon)>);
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = (&'a String, &'a mut Jzon);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for IterMut<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, v)| (&*k, v))
+    }
+}
+
+impl<'a> ExactSizeIterator for IterMut<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    fn entry(k: &str, v: i64) -> (String, Jzon) {
+        (k.to_string(), Jzon::Integer(v))
+    }
+
+    #[test]
+    fn sorted() {
+        let mut map = Map::new();
+        assert_eq!(None, map.insert("b".to_string(), Jzon::Integer(1)));
+        assert_eq!(None, map.insert("a".to_string(), Jzon::Integer(2)));
+        assert_eq!(None, map.insert("c".to_string(), Jzon::Integer(3)));
+        assert_eq!(
+            Some(Jzon::Integer(1)),
+            map.insert("b".to_string(), Jzon::Integer(4))
+        );
+        let keys: Vec<&String> = map.keys().collect();
+        assert_eq!(vec!["a", "b", "c"], keys);
+        assert_eq!(Some(&Jzon::Integer(4)), map.get("b"));
+        assert_eq!(Jzon::Integer(3), map["c"]);
+        assert!(!map.contains_key("d"));
+
+        *map.get_mut("a").unwrap() = Jzon::Null;
+        assert_eq!(Some(Jzon::Null), map.remove("a"));
+        assert_eq!(None, map.remove("a"));
+        assert_eq!(2, map.len());
+    }
+
+    #[test]
+    fn collect() {
+        let map: Map = vec![entry("b", 1), entry("a", 2), entry("b", 3), entry("c", 4)]
+            .into_iter()
+            .collect();
+        let expected: Vec<(String, Jzon)> = vec![entry("a", 2), entry("b", 3), entry("c", 4)];
+        assert_eq!(expected, map.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn size() {
+        assert_eq!(24, mem::size_of::<Map>());
+        assert_eq!(32, mem::size_of::<Jzon>());
+    }
+}
@@ -0,0 +1,578 @@
+use crate::base64;
+use crate::jzon::Jzon;
+use std::convert::TryFrom;
+use std::io;
+use std::io::Read;
+use std::result;
+
+// MessagePack, https://github.com/msgpack/msgpack/blob/master/spec.md
+
+#[derive(Debug)]
+pub enum MsgpackErr {
+    Io(io::Error),
+    ExpectUtf8,
+    ExpectNoMore,
+    ExpectNoneEOF,
+    ExpectFormat,
+    ExpectNoneBin,
+    ExpectExt(i8),
+    UnexpectedDepth,
+}
+
+impl From<io::Error> for MsgpackErr {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::UnexpectedEof => ExpectNoneEOF,
+            _ => MsgpackErr::Io(e),
+        }
+    }
+}
+
+// What to do with `bin` values, which have no JSON counterpart.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Bin {
+    #[default]
+    Base64,
+    Reject,
+}
+
+// Receives the type and payload of an ext value; `None` rejects it.
+pub type ExtHandler = dyn FnMut(i8, &[u8]) -> Option<Jzon>;
+
+type Result = result::Result<Jzon, MsgpackErr>;
+
+use MsgpackErr::*;
+
+const NIL: u8 = 0xC0;
+const NEVER_USED: u8 = 0xC1;
+const FALSE: u8 = 0xC2;
+const TRUE: u8 = 0xC3;
+const BIN8: u8 = 0xC4;
+const BIN32: u8 = 0xC6;
+const EXT8: u8 = 0xC7;
+const EXT32: u8 = 0xC9;
+const FLOAT32: u8 = 0xCA;
+const FLOAT64: u8 = 0xCB;
+const UINT8: u8 = 0xCC;
+const UINT16: u8 = 0xCD;
+const UINT32: u8 = 0xCE;
+const UINT64: u8 = 0xCF;
+const INT8: u8 = 0xD0;
+const INT16: u8 = 0xD1;
+const INT32: u8 = 0xD2;
+const INT64: u8 = 0xD3;
+const FIXEXT1: u8 = 0xD4;
+const FIXEXT16: u8 = 0xD8;
+const STR8: u8 = 0xD9;
+const STR16: u8 = 0xDA;
+const STR32: u8 = 0xDB;
+const ARRAY16: u8 = 0xDC;
+const ARRAY32: u8 = 0xDD;
+const MAP16: u8 = 0xDE;
+const MAP32: u8 = 0xDF;
+
+// arrays and maps nested deeper than this are refused rather than read by
+// recursing until the stack runs out
+const MAX_DEPTH: usize = 128;
+
+impl Jzon {
+    pub fn to_msgpack(&self) -> Vec<u8> {
+        let mut writer = Writer::new(vec![]);
+        // writing into a Vec never fails
+        writer.write(self).unwrap();
+        writer.into_inner()
+    }
+
+    pub fn from_msgpack(bytes: &[u8]) -> Result {
+        let mut reader = Reader::new(bytes);
+        let value = reader.read()?.ok_or(ExpectNoneEOF)?;
+        if reader.inner.is_empty() {
+            Ok(value)
+        } else {
+            Err(ExpectNoMore)
+        }
+    }
+}
+
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W: io::Write> Writer<W> {
+    pub fn new(inner: W) -> Self {
+        Writer { inner }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    pub fn write(&mut self, jz: &Jzon) -> io::Result<()> {
+        match jz {
+            Jzon::Null | Jzon::Error => self.inner.write_all(&[NIL]),
+            Jzon::Bool(false) => self.inner.write_all(&[FALSE]),
+            Jzon::Bool(true) => self.inner.write_all(&[TRUE]),
+            Jzon::Integer(v) => self.write_int(*v),
+            Jzon::Double(v) => {
+                self.inner.write_all(&[FLOAT64])?;
+                self.inner.write_all(&v.to_be_bytes())
+            }
+            Jzon::String(s) => self.write_str(s),
+            Jzon::Array(vec) => {
+                self.write_len(vec.len(), 0x90, ARRAY16, ARRAY32)?;
+                vec.iter().try_for_each(|v| self.write(v))
+            }
+            Jzon::Object(map) => {
+                self.write_len(map.len(), 0x80, MAP16, MAP32)?;
+                map.iter().try_for_each(|(k, v)| {
+                    self.write_str(k)?;
+                    self.write(v)
+                })
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    fn write_int(&mut self, v: i64) -> io::Result<()> {
+        let w = &mut self.inner;
+        if (0..=0x7F).contains(&v) || (-32..0).contains(&v) {
+            // positive and negative fixint
+            w.write_all(&[v as u8])
+        } else if v > 0 {
+            if v <= u8::MAX as i64 {
+                w.write_all(&[UINT8, v as u8])
+            } else if v <= u16::MAX as i64 {
+                w.write_all(&[UINT16])?;
+                w.write_all(&(v as u16).to_be_bytes())
+            } else if v <= u32::MAX as i64 {
+                w.write_all(&[UINT32])?;
+                w.write_all(&(v as u32).to_be_bytes())
+            } else {
+                w.write_all(&[UINT64])?;
+                w.write_all(&(v as u64).to_be_bytes())
+            }
+        } else if v >= i8::MIN as i64 {
+            w.write_all(&[INT8, v as u8])
+        } else if v >= i16::MIN as i64 {
+            w.write_all(&[INT16])?;
+            w.write_all(&(v as i16).to_be_bytes())
+        } else if v >= i32::MIN as i64 {
+            w.write_all(&[INT32])?;
+            w.write_all(&(v as i32).to_be_bytes())
+        } else {
+            w.write_all(&[INT64])?;
+            w.write_all(&v.to_be_bytes())
+        }
+    }
+
+    fn write_str(&mut self, s: &str) -> io::Result<()> {
+        let len = s.len();
+        if len < 32 {
+            self.inner.write_all(&[0xA0 | len as u8])?;
+        } else if len <= u8::MAX as usize {
+            self.inner.write_all(&[STR8, len as u8])?;
+        } else {
+            self.write_len(len, 0, STR16, STR32)?;
+        }
+        self.inner.write_all(s.as_bytes())
+    }
+
+    // fix formats hold up to 15 entries; `fix` is unused for strings
+    fn write_len(&mut self, len: usize, fix: u8, len16: u8, len32: u8) -> io::Result<()> {
+        if len < 16 && fix != 0 {
+            self.inner.write_all(&[fix | len as u8])
+        } else if len <= u16::MAX as usize {
+            self.inner.write_all(&[len16])?;
+            self.inner.write_all(&(len as u16).to_be_bytes())
+        } else {
+            let len = u32::try_from(len).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidInput, "length exceeds 2^32-1")
+            })?;
+            self.inner.write_all(&[len32])?;
+            self.inner.write_all(&len.to_be_bytes())
+        }
+    }
+}
+
+pub struct Reader<R> {
+    inner: R,
+    bin: Bin,
+    ext: Option<Box<ExtHandler>>,
+    // values being read, the current one included
+    depth: usize,
+}
+
+impl<R: io::Read> Reader<R> {
+    pub fn new(inner: R) -> Self {
+        Reader {
+            inner,
+            bin: Bin::default(),
+            ext: None,
+            depth: 0,
+        }
+    }
+
+    pub fn with_bin(mut self, bin: Bin) -> Self {
+        self.bin = bin;
+        self
+    }
+
+    pub fn with_ext<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(i8, &[u8]) -> Option<Jzon> + 'static,
+    {
+        self.ext = Some(Box::new(handler));
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    // Reads the next value of a stream, `None` once it ends between values.
+    pub fn read(&mut self) -> result::Result<Option<Jzon>, MsgpackErr> {
+        let mut buf = [0];
+        loop {
+            match self.inner.read(&mut buf) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return self.value(buf[0]).map(Some),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    fn byte(&mut self) -> result::Result<u8, MsgpackErr> {
+        let mut buf = [0];
+        self.inner.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn be<const N: usize>(&mut self) -> result::Result<[u8; N], MsgpackErr> {
+        let mut buf = [0; N];
+        self.inner.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn len(&mut self, width: u8) -> result::Result<usize, MsgpackErr> {
+        let len = match width {
+            1 => self.byte()? as u32,
+            2 => u16::from_be_bytes(self.be()?) as u32,
+            _ => u32::from_be_bytes(self.be()?),
+        };
+        Ok(len as usize)
+    }
+
+    // reads `len` bytes without trusting `len` for the allocation
+    fn bytes(&mut self, len: usize) -> result::Result<Vec<u8>, MsgpackErr> {
+        let mut buf = vec![];
+        (&mut self.inner).take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() < len {
+            return Err(ExpectNoneEOF);
+        }
+        Ok(buf)
+    }
+
+    fn next(&mut self) -> Result {
+        let marker = self.byte()?;
+        self.value(marker)
+    }
+
+    fn value(&mut self, marker: u8) -> Result {
+        if self.depth == MAX_DEPTH {
+            return Err(UnexpectedDepth);
+        }
+        self.depth += 1;
+        let value = self.item(marker);
+        self.depth -= 1;
+        value
+    }
+
+    fn item(&mut self, marker: u8) -> Result {
+        let value = match marker {
+            0x00..=0x7F => Jzon::Integer(marker as i64),
+            0x80..=0x8F => self.map((marker & 0x0F) as usize)?,
+            0x90..=0x9F => self.array((marker & 0x0F) as usize)?,
+            0xA0..=0xBF => self.str((marker & 0x1F) as usize)?,
+            NIL => Jzon::Null,
+            NEVER_USED => return Err(ExpectFormat),
+            FALSE => Jzon::Bool(false),
+            TRUE => Jzon::Bool(true),
+            BIN8..=BIN32 => {
+                let len = self.len(1 << (marker - BIN8))?;
+                self.bin(len)?
+            }
+            EXT8..=EXT32 => {
+                let len = self.len(1 << (marker - EXT8))?;
+                self.ext(len)?
+            }
+            FLOAT32 => Jzon::Double(f32::from_be_bytes(self.be()?) as f64),
+            FLOAT64 => Jzon::Double(f64::from_be_bytes(self.be()?)),
+            UINT8 => Jzon::Integer(self.byte()? as i64),
+            UINT16 => Jzon::Integer(u16::from_be_bytes(self.be()?) as i64),
+            UINT32 => Jzon::Integer(u32::from_be_bytes(self.be()?) as i64),
+            UINT64 => {
+                let v = u64::from_be_bytes(self.be()?);
+                match i64::try_from(v) {
+                    Ok(v) => Jzon::Integer(v),
+                    Err(_) => Jzon::Double(v as f64),
+                }
+            }
+            INT8 => Jzon::Integer(self.byte()? as i8 as i64),
+            INT16 => Jzon::Integer(i16::from_be_bytes(self.be()?) as i64),
+            INT32 => Jzon::Integer(i32::from_be_bytes(self.be()?) as i64),
+            INT64 => Jzon::Integer(i64::from_be_bytes(self.be()?)),
+            FIXEXT1..=FIXEXT16 => self.ext(1 << (marker - FIXEXT1))?,
+            STR8..=STR32 => {
+                let len = self.len(1 << (marker - STR8))?;
+                self.str(len)?
+            }
+            ARRAY16 | ARRAY32 => {
+                let len = self.len(if marker == ARRAY16 { 2 } else { 4 })?;
+                self.array(len)?
+            }
+            MAP16 | MAP32 => {
+                let len = self.len(if marker == MAP16 { 2 } else { 4 })?;
+                self.map(len)?
+            }
+            0xE0..=0xFF => Jzon::Integer(marker as i8 as i64),
+        };
+        Ok(value)
+    }
+
+    fn str(&mut self, len: usize) -> Result {
+        let bytes = self.bytes(len)?;
+        String::from_utf8(bytes)
+            .map(Jzon::String)
+            .map_err(|_| ExpectUtf8)
+    }
+
+    fn bin(&mut self, len: usize) -> Result {
+        let bytes = self.bytes(len)?;
+        match self.bin {
+            Bin::Base64 => Ok(Jzon::String(base64::encode(&bytes))),
+            Bin::Reject => Err(ExpectNoneBin),
+        }
+    }
+
+    fn ext(&mut self, len: usize) -> Result {
+        let ty = self.byte()? as i8;
+        let data = self.bytes(len)?;
+        match self.ext.as_mut().and_then(|handler| handler(ty, &data)) {
+            Some(value) => Ok(value),
+            None => Err(ExpectExt(ty)),
+        }
+    }
+
+    fn array(&mut self, len: usize) -> Result {
+        let mut vec = vec![];
+        for _ in 0..len {
+            vec.push(self.next()?);
+        }
+        Ok(Jzon::Array(vec))
+    }
+
+    fn map(&mut self, len: usize) -> Result {
+        let mut members = vec![];
+        for _ in 0..len {
+            let key = match self.next()? {
+                Jzon::String(s) => s,
+                other => other.stringify(),
+            };
+            members.push((key, self.next()?));
+        }
+        Ok(Jzon::Object(members.into_iter().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Map;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn encode_smallest() {
+        let cases = [
+            (Jzon::Integer(0), "00"),
+            (Jzon::Integer(127), "7f"),
+            (Jzon::Integer(128), "cc80"),
+            (Jzon::Integer(256), "cd0100"),
+            (Jzon::Integer(65536), "ce00010000"),
+            (Jzon::Integer(1 << 32), "cf0000000100000000"),
+            (Jzon::Integer(-1), "ff"),
+            (Jzon::Integer(-32), "e0"),
+            (Jzon::Integer(-33), "d0df"),
+            (Jzon::Integer(-129), "d1ff7f"),
+            (Jzon::Integer(-32769), "d2ffff7fff"),
+            (Jzon::Integer(i64::MIN), "d38000000000000000"),
+            (Jzon::Double(1.5), "cb3ff8000000000000"),
+            (Jzon::Null, "c0"),
+            (Jzon::Bool(false), "c2"),
+            (Jzon::Bool(true), "c3"),
+            (Jzon::String("".into()), "a0"),
+            (
+                Jzon::String("a".repeat(31)),
+                &format!("bf{}", "61".repeat(31)),
+            ),
+            (
+                Jzon::String("a".repeat(32)),
+                &format!("d920{}", "61".repeat(32)),
+            ),
+            (
+                Jzon::String("a".repeat(256)),
+                &format!("da0100{}", "61".repeat(256)),
+            ),
+            (Jzon::Array(vec![]), "90"),
+            (
+                Jzon::Array((0..15).map(Jzon::Integer).collect()),
+                "9f000102030405060708090a0b0c0d0e",
+            ),
+            (
+                Jzon::Array((0..16).map(|_| Jzon::Integer(1)).collect()),
+                &format!("dc0010{}", "01".repeat(16)),
+            ),
+            (Jzon::Object(Map::new()), "80"),
+        ];
+        for (jz, packed) in cases.iter() {
+            assert_eq!(hex(packed), jz.to_msgpack(), "{}", jz);
+        }
+
+        let jz = Jzon::parse(r#"{"a": [1, null]}"#.as_bytes()).unwrap();
+        assert_eq!(hex("81a1619201c0"), jz.to_msgpack());
+    }
+
+    #[test]
+    fn decode() {
+        let cases = [
+            ("7f", "127"),
+            ("e0", "-32"),
+            ("cc80", "128"),
+            ("cd0100", "256"),
+            ("ce00010000", "65536"),
+            ("cfffffffffffffffff", "18446744073709552000.0"),
+            ("d0df", "-33"),
+            ("d1ff7f", "-129"),
+            ("d2ffff7fff", "-32769"),
+            ("ca3fc00000", "1.5"),
+            ("cb3ff8000000000000", "1.5"),
+            ("a3616263", r#""abc""#),
+            ("d903616263", r#""abc""#),
+            ("da0003616263", r#""abc""#),
+            ("db00000003616263", r#""abc""#),
+            ("c403666f6f", r#""Zm9v""#),
+            ("c50003666f6f", r#""Zm9v""#),
+            ("9201c2", "[1,false]"),
+            ("dc000201c3", "[1,true]"),
+            ("dd0000000201c0", "[1,null]"),
+            ("8101a161", r#"{"1":"a"}"#),
+            ("de0001a16101", r#"{"a":1}"#),
+            ("df00000001a16101", r#"{"a":1}"#),
+        ];
+        for (packed, json) in cases.iter() {
+            let jz = Jzon::from_msgpack(&hex(packed)).unwrap();
+            assert_eq!(*json, jz.stringify(), "{}", packed);
+        }
+    }
+
+    #[test]
+    fn decode_malformed() {
+        assert!(matches!(Jzon::from_msgpack(&hex("")), Err(ExpectNoneEOF)));
+        assert!(matches!(
+            Jzon::from_msgpack(&hex("cd01")),
+            Err(ExpectNoneEOF)
+        ));
+        assert!(matches!(
+            Jzon::from_msgpack(&hex("dbffffffff")),
+            Err(ExpectNoneEOF)
+        ));
+        assert!(matches!(
+            Jzon::from_msgpack(&hex("9201")),
+            Err(ExpectNoneEOF)
+        ));
+        assert!(matches!(
+            Jzon::from_msgpack(&hex("0101")),
+            Err(ExpectNoMore)
+        ));
+        assert!(matches!(Jzon::from_msgpack(&hex("c1")), Err(ExpectFormat)));
+        assert!(matches!(
+            Jzon::from_msgpack(&hex("a2c328")),
+            Err(ExpectUtf8)
+        ));
+        assert!(matches!(
+            Jzon::from_msgpack(&hex("d40501")),
+            Err(ExpectExt(5))
+        ));
+
+        let mut nested = vec![0x91; 127];
+        nested.push(0);
+        assert!(Jzon::from_msgpack(&nested).is_ok());
+        nested.insert(0, 0x91);
+        assert!(matches!(Jzon::from_msgpack(&nested), Err(UnexpectedDepth)));
+        let deep = vec![0x91; 200_000];
+        assert!(matches!(Jzon::from_msgpack(&deep), Err(UnexpectedDepth)));
+        let mut reader = Reader::new(io::Cursor::new(deep));
+        assert!(matches!(reader.read(), Err(UnexpectedDepth)));
+    }
+
+    #[test]
+    fn bin_mode() {
+        let bytes = hex("c403666f6f");
+        let mut reader = Reader::new(&bytes[..]).with_bin(Bin::Reject);
+        assert!(matches!(reader.read(), Err(ExpectNoneBin)));
+    }
+
+    #[test]
+    fn ext_handler() {
+        // a timestamp 32 followed by an ext 8 with an application type
+        let bytes = hex("92d6ff00000010c70301aabbcc");
+        let mut reader = Reader::new(&bytes[..]).with_ext(|ty, data| match ty {
+            -1 if data.len() == 4 => {
+                let secs = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+                Some(Jzon::Integer(secs as i64))
+            }
+            1 => Some(Jzon::Integer(data.len() as i64)),
+            _ => None,
+        });
+        let jz = reader.read().unwrap().unwrap();
+        assert_eq!(jz[0], 16);
+        assert_eq!(jz[1], 3);
+    }
+
+    #[test]
+    fn stream() {
+        let mut writer = Writer::new(vec![]);
+        for json in ["[1,2]", r#"{"k":"v"}"#, "null"].iter() {
+            writer
+                .write(&Jzon::parse(json.as_bytes()).unwrap())
+                .unwrap();
+        }
+        let bytes = writer.into_inner();
+
+        let mut reader = Reader::new(io::Cursor::new(bytes));
+        assert_eq!("[1,2]", reader.read().unwrap().unwrap().stringify());
+        assert_eq!(r#"{"k":"v"}"#, reader.read().unwrap().unwrap().stringify());
+        assert_eq!("null", reader.read().unwrap().unwrap().stringify());
+        assert!(reader.read().unwrap().is_none());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let content = std::fs::read("data/twitter.json").unwrap();
+        let jz = Jzon::parse(&content).unwrap();
+        let packed = jz.to_msgpack();
+        assert!(packed.len() < content.len());
+        let back = Jzon::from_msgpack(&packed).unwrap();
+        assert_eq!(jz, back);
+        assert_eq!(back["statuses"][0]["user"]["screen_name"], "ayuu0123");
+    }
+}
@@ -0,0 +1,429 @@
+use crate::jzon::{Jzon, ParseErr};
+use std::mem;
+use std::result;
+use std::str;
+
+// An incremental parser for input that arrives in pieces, e.g. off a socket.
+// Chunks may split the input anywhere, even inside a string, an escape or a
+// number, and each top-level value is handed back by the `feed` that closes
+// it. Top-level values may simply follow one another, as in NDJSON, with
+// whitespace or a bracket after a number, true, false or null.
+
+type Result<T> = result::Result<T, ParseErr>;
+
+#[derive(Debug)]
+enum Frame {
+    // the key waiting for its value
+    Object(Vec<(String, Jzon)>, Option<String>),
+    Array(Vec<Jzon>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Want {
+    Value,
+    // right after `[`, where `]` may come instead
+    FirstValue,
+    Key,
+    // right after `{`, where `}` may come instead
+    FirstKey,
+    Colon,
+    // a comma or the closing bracket
+    Comma,
+    // the next top-level value after a number or literal, which needs
+    // whitespace or a bracket before it
+    Apart,
+}
+
+// the token read so far, carried over to the next chunk
+#[derive(Debug)]
+enum Token {
+    None,
+    String {
+        buf: Vec<u8>,
+        // an escape sequence not yet complete, from its backslash on
+        escape: Vec<u8>,
+        key: bool,
+    },
+    Number(Vec<u8>),
+    // the literal and how much of it has matched
+    Literal(&'static [u8], usize),
+}
+
+#[derive(Debug)]
+pub struct PushParser {
+    stack: Vec<Frame>,
+    want: Want,
+    token: Token,
+    // the parser refuses further input once it has failed
+    error: Option<ParseErr>,
+}
+
+use ParseErr::*;
+
+impl Default for PushParser {
+    fn default() -> Self {
+        PushParser {
+            stack: vec![],
+            want: Want::Value,
+            token: Token::None,
+            error: None,
+        }
+    }
+}
+
+impl PushParser {
+    pub fn new() -> Self {
+        PushParser::default()
+    }
+
+    // the values that the chunk completes
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Jzon>> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let mut done = vec![];
+        for &b in chunk {
+            if let Err(err) = self.push(b, &mut done) {
+                self.error = Some(err);
+                return Err(err);
+            }
+        }
+        Ok(done)
+    }
+
+    // ends the input, which may still complete a trailing number
+    pub fn finish(mut self) -> Result<Vec<Jzon>> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let mut done = vec![];
+        if let Token::Number(_) = self.token {
+            self.end_number(&mut done)?;
+        }
+        match self.token {
+            Token::None if self.stack.is_empty() => Ok(done),
+            _ => Err(ExpectNoneEOF),
+        }
+    }
+
+    // true between values, when finishing would lose nothing
+    pub fn is_idle(&self) -> bool {
+        self.error.is_none() && self.stack.is_empty() && matches!(self.token, Token::None)
+    }
+
+    fn push(&mut self, b: u8, done: &mut Vec<Jzon>) -> Result<()> {
+        match &mut self.token {
+            Token::None => (),
+            Token::String { .. } => return self.string(b, done),
+            Token::Literal(word, matched) => {
+                if word[*matched] != b {
+                    return Err(if word[0] == b'n' {
+                        ExpectNull
+                    } else {
+                        ExpectBool
+                    });
+                }
+                *matched += 1;
+                if *matched == word.len() {
+                    let value = match word[0] {
+                        b't' => Jzon::Bool(true),
+                        b'f' => Jzon::Bool(false),
+                        _ => Jzon::Null,
+                    };
+                    self.token = Token::None;
+                    self.complete(value, done);
+                }
+                return Ok(());
+            }
+            Token::Number(buf) if is_number(b) => {
+                buf.push(b);
+                return Ok(());
+            }
+            // the byte ends the number and still needs handling
+            Token::Number(_) => self.end_number(done)?,
+        }
+
+        if let b' ' | b'\t' | b'\n' | b'\r' = b {
+            if self.want == Want::Apart {
+                self.want = Want::Value;
+            }
+            return Ok(());
+        }
+
+        match (self.want, b) {
+            (Want::Apart, b'[') | (Want::Apart, b'{') => self.begin(b),
+            (Want::Apart, _) => Err(ExpectNoMore),
+            (Want::Value, _) | (Want::FirstValue, _) if b != b']' => self.begin(b),
+            (Want::Key, b'"') | (Want::FirstKey, b'"') => {
+                self.token = Token::String {
+                    buf: vec![],
+                    escape: vec![],
+                    key: true,
+                };
+                Ok(())
+            }
+            (Want::Key, _) => Err(ExpectPair),
+            (Want::FirstKey, b'}') => self.close(done),
+            (Want::FirstKey, _) => Err(ExpectPair),
+            (Want::Colon, b':') => {
+                self.want = Want::Value;
+                Ok(())
+            }
+            (Want::Colon, _) => Err(ExpectColon),
+            (Want::FirstValue, _) => self.close(done),
+            (Want::Comma, _) => match (self.stack.last(), b) {
+                (Some(Frame::Object(..)), b',') => {
+                    self.want = Want::Key;
+                    Ok(())
+                }
+                (Some(Frame::Array(_)), b',') => {
+                    self.want = Want::Value;
+                    Ok(())
+                }
+                (Some(Frame::Object(..)), b'}') | (Some(Frame::Array(_)), b']') => self.close(done),
+                (Some(Frame::Object(..)), _) => Err(ExpectCommaBrace),
+                _ => Err(ExpectCommaBracket),
+            },
+            (Want::Value, _) => Err(ExpectValue),
+        }
+    }
+
+    // the first byte of a value
+    fn begin(&mut self, b: u8) -> Result<()> {
+        match b {
+            b'"' => {
+                self.token = Token::String {
+                    buf: vec![],
+                    escape: vec![],
+                    key: false,
+                }
+            }
+            b'-' | b'0'..=b'9' => self.token = Token::Number(vec![b]),
+            b't' => self.token = Token::Literal(b"true", 1),
+            b'f' => self.token = Token::Literal(b"false", 1),
+            b'n' => self.token = Token::Literal(b"null", 1),
+            b'{' => {
+                self.stack.push(Frame::Object(vec![], None));
+                self.want = Want::FirstKey;
+            }
+            b'[' => {
+                self.stack.push(Frame::Array(vec![]));
+                self.want = Want::FirstValue;
+            }
+            _ => return Err(ExpectPrefix),
+        }
+        Ok(())
+    }
+
+    fn string(&mut self, b: u8, done: &mut Vec<Jzon>) -> Result<()> {
+        let (buf, escape) = match &mut self.token {
+            Token::String { buf, escape, .. } => (buf, escape),
+            _ => unreachable!(),
+        };
+        if !escape.is_empty() {
+            escape.push(b);
+            if let Some(ch) = escaped(escape)? {
+                let mut encode_buf = [0; 4];
+                buf.extend_from_slice(ch.encode_utf8(&mut encode_buf).as_bytes());
+                escape.clear();
+            }
+            return Ok(());
+        }
+        match b {
+            b'\\' => escape.push(b),
+            b'"' => {
+                let (buf, key) = match mem::replace(&mut self.token, Token::None) {
+                    Token::String { buf, key, .. } => (buf, key),
+                    _ => unreachable!(),
+                };
+                let s = String::from_utf8(buf).map_err(|_| ExpectCodePoint)?;
+                match self.stack.last_mut() {
+                    Some(Frame::Object(_, pending)) if key => {
+                        *pending = Some(s);
+                        self.want = Want::Colon;
+                    }
+                    _ => self.complete(Jzon::String(s), done),
+                }
+            }
+            // according to ECMA-404
+            0x00..=0x1F => return Err(ExpectNoneControl),
+            _ => buf.push(b),
+        }
+        Ok(())
+    }
+
+    fn end_number(&mut self, done: &mut Vec<Jzon>) -> Result<()> {
+        let buf = match mem::replace(&mut self.token, Token::None) {
+            Token::Number(buf) => buf,
+            _ => unreachable!(),
+        };
+        match Jzon::parse_number(&buf) {
+            Ok(state) if state.consumed == buf.len() => {
+                self.complete(state.value, done);
+                Ok(())
+            }
+            _ => Err(ExpectDigit),
+        }
+    }
+
+    fn close(&mut self, done: &mut Vec<Jzon>) -> Result<()> {
+        let value = match self.stack.pop() {
+            Some(Frame::Object(members, _)) => Jzon::Object(members.into_iter().collect()),
+            Some(Frame::Array(vec)) => Jzon::Array(vec),
+            None => unreachable!(),
+        };
+        self.complete(value, done);
+        Ok(())
+    }
+
+    fn complete(&mut self, value: Jzon, done: &mut Vec<Jzon>) {
+        self.want = Want::Comma;
+        match self.stack.last_mut() {
+            Some(Frame::Object(members, key)) => {
+                members.push((key.take().unwrap(), value));
+            }
+            Some(Frame::Array(vec)) => vec.push(value),
+            None => {
+                self.want = match value {
+                    Jzon::String(_) | Jzon::Array(_) | Jzon::Object(_) => Want::Value,
+                    _ => Want::Apart,
+                };
+                done.push(value);
+            }
+        }
+    }
+}
+
+fn is_number(b: u8) -> bool {
+    matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')
+}
+
+// The escape sequence so far decoded, once `parse_escaped` has all it needs;
+// after a high surrogate that includes the `\uXXXX` of the low one.
+fn escaped(escape: &[u8]) -> Result<Option<char>> {
+    let complete = match escape.len() {
+        2 => escape[1] != b'u',
+        6 => !is_high_surrogate(&escape[2..6]),
+        7 if escape[6] != b'\\' => return Err(ExpectCodePoint),
+        8 if escape[7] != b'u' => return Err(ExpectCodePoint),
+        12 => true,
+        _ => false,
+    };
+    if !complete {
+        return Ok(None);
+    }
+    Jzon::parse_escaped(escape).map(|state| Some(state.value))
+}
+
+fn is_high_surrogate(hex: &[u8]) -> bool {
+    str::from_utf8(hex)
+        .ok()
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .is_some_and(|uc| (0xD800..=0xDBFF).contains(&uc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(jz: &Jzon) -> String {
+        format!("{}", jz.styled().sort_keys(true))
+    }
+
+    // feeds `input` in chunks of every size from 1 to 8
+    fn chunked(input: &[u8]) -> Vec<String> {
+        let mut all: Vec<Vec<String>> = vec![];
+        for size in 1..=8 {
+            let mut parser = PushParser::new();
+            let mut values = vec![];
+            for chunk in input.chunks(size) {
+                values.extend(parser.feed(chunk).unwrap());
+            }
+            values.extend(parser.finish().unwrap());
+            all.push(values.iter().map(sorted).collect());
+        }
+        assert!(all.iter().all(|values| *values == all[0]));
+        all.remove(0)
+    }
+
+    #[test]
+    fn chunks() {
+        let input = br#"{"s": "a\"\u00e9\ud801\udc37\n", "n": [-1.5e3, 0, 12], "t": true, "f": false, "z": null}"#;
+        let expected = sorted(&Jzon::parse(input).unwrap());
+        assert_eq!(vec![expected], chunked(input));
+
+        let content = std::fs::read("data/twitter.json").unwrap();
+        let expected = sorted(&Jzon::parse(&content).unwrap());
+        let mut parser = PushParser::new();
+        let mut values = vec![];
+        for chunk in content.chunks(1500) {
+            values.extend(parser.feed(chunk).unwrap());
+        }
+        assert!(parser.is_idle());
+        assert_eq!(1, values.len());
+        assert_eq!(expected, sorted(&values[0]));
+    }
+
+    #[test]
+    fn emit() {
+        let mut parser = PushParser::new();
+        assert_eq!(0, parser.feed(b"{\"a\": [1, 2").unwrap().len());
+        let values = parser.feed(b"]}\n[3]\n4").unwrap();
+        assert_eq!(
+            vec![r#"{"a":[1,2]}"#, "[3]"],
+            values.iter().map(sorted).collect::<Vec<_>>()
+        );
+        // the number could still go on
+        assert!(!parser.is_idle());
+        assert_eq!(0, parser.feed(b"2").unwrap().len());
+        assert_eq!(1, parser.feed(b" ").unwrap().len());
+        assert!(parser.is_idle());
+
+        let mut parser = PushParser::new();
+        parser.feed(b"1").unwrap();
+        assert_eq!(1, parser.finish().unwrap()[0]);
+        assert_eq!(vec!["1", "\"x\"", "true"], chunked(b"1 \"x\"true"));
+        assert_eq!(
+            vec!["null", "[2]", "-3", "{}", "false"],
+            chunked(b"null[2]-3{}false")
+        );
+    }
+
+    #[test]
+    fn errors() {
+        fn fails(input: &[u8]) -> ParseErr {
+            let mut parser = PushParser::new();
+            match parser.feed(input) {
+                Ok(_) => parser.finish().unwrap_err(),
+                Err(err) => {
+                    assert_eq!(Some(err), parser.feed(b" ").err());
+                    err
+                }
+            }
+        }
+
+        assert_eq!(ExpectNoneEOF, fails(b"[1, 2"));
+        assert_eq!(ExpectNoneEOF, fails(b"\"abc"));
+        assert_eq!(ExpectNoneEOF, fails(b"tru"));
+        assert_eq!(ExpectBool, fails(b"trie"));
+        assert_eq!(ExpectNull, fails(b"nul "));
+        assert_eq!(ExpectCommaBracket, fails(b"[1 2]"));
+        assert_eq!(ExpectCommaBrace, fails(b"{\"a\": 1]"));
+        assert_eq!(ExpectValue, fails(b"[1,]"));
+        assert_eq!(ExpectPair, fails(b"{\"a\": 1,}"));
+        assert_eq!(ExpectColon, fails(b"{\"a\" 1}"));
+        assert_eq!(ExpectDigit, fails(b"01"));
+        assert_eq!(ExpectDigit, fails(b"[1.]"));
+        assert_eq!(ExpectValue, fails(b"]"));
+        assert_eq!(ExpectPrefix, fails(b"@"));
+        assert_eq!(ExpectNoMore, fails(b"1true"));
+        assert_eq!(ExpectNoMore, fails(b"true1"));
+        assert_eq!(ExpectNoMore, fails(b"null\"x\""));
+        assert_eq!(ExpectNoMore, fails(b"[1] 2null"));
+        assert_eq!(ExpectNoneControl, fails(b"\"a\nb\""));
+        assert_eq!(ExpectEscaped, fails(b"\"\\x\""));
+        assert_eq!(ExpectHexDigit, fails(b"\"\\u12g4\""));
+        assert_eq!(ExpectCodePoint, fails(b"\"\\ud801x\""));
+        assert_eq!(ExpectCodePoint, fails(b"\"\xff\""));
+    }
+}
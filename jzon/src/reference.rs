@@ -0,0 +1,378 @@
+use crate::diff::Path;
+use crate::encoding::Bom;
+use crate::jzon::Jzon;
+use crate::map::Map;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{self, Component, PathBuf};
+use std::result;
+
+// JSON Reference: an object with a string "$ref" member stands for the
+// value the reference points at, like "common.json#/definitions/Id" or
+// "#/definitions/Id". Files are named relative to the document the
+// reference is in, and the fragment is a JSON Pointer. Other members next
+// to "$ref" are ignored.
+
+#[derive(Debug)]
+pub enum RefErr {
+    // the loader has no document by this name
+    ExpectDocument(String),
+    // the fragment of this reference isn't a JSON Pointer
+    ExpectPointer(String),
+    // nothing at this reference
+    ExpectTarget(String),
+    // these references lead back to the first one
+    ExpectNoneCycle(Vec<String>),
+}
+
+type Result<T> = result::Result<T, RefErr>;
+
+use RefErr::*;
+
+// Where documents come from, by name, after relative names are resolved
+// against the referring document. Anything with "://" is passed on as is.
+pub trait Loader {
+    fn load(&mut self, uri: &str) -> Option<Jzon>;
+}
+
+// documents kept in memory, by name
+impl Loader for HashMap<String, Jzon> {
+    fn load(&mut self, uri: &str) -> Option<Jzon> {
+        self.get(uri).cloned()
+    }
+}
+
+// files under a directory, in any encoding `Jzon::parse_encoded` takes;
+// absolute names and ".." past the directory find nothing
+pub struct FileLoader {
+    root: PathBuf,
+}
+
+impl FileLoader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FileLoader { root: root.into() }
+    }
+}
+
+impl Loader for FileLoader {
+    fn load(&mut self, uri: &str) -> Option<Jzon> {
+        if uri.contains("://") || !stays_under(uri) {
+            return None;
+        }
+        let bytes = fs::read(self.root.join(uri)).ok()?;
+        Jzon::parse_encoded(&bytes, Bom::Accept).ok()
+    }
+}
+
+// Loads every document once, when a reference first needs it.
+pub struct Resolver<L> {
+    loader: L,
+    docs: HashMap<String, Jzon>,
+}
+
+impl<L: Loader> Resolver<L> {
+    pub fn new(loader: L) -> Self {
+        Resolver {
+            loader,
+            docs: HashMap::new(),
+        }
+    }
+
+    // The document at `uri` with every reference replaced by a copy of what
+    // it points at, all the way down. Recursive structures can't be inlined
+    // and end in `ExpectNoneCycle`.
+    pub fn bundle(&mut self, uri: &str) -> Result<Jzon> {
+        let doc = self.document(uri)?.clone();
+        self.inline(uri, &doc, &mut vec![])
+    }
+
+    // The value `reference` points at, read as if it were in the document
+    // `base`, together with its absolute form. References on the way are
+    // followed, those inside the value are left for the caller to resolve,
+    // with the returned name as their base, only if they are ever needed.
+    pub fn resolve(&mut self, base: &str, reference: &str) -> Result<(String, &Jzon)> {
+        let mut seen = vec![];
+        let mut target = absolute(base, reference);
+        loop {
+            if seen.contains(&target) {
+                seen.push(target);
+                return Err(ExpectNoneCycle(seen));
+            }
+            let next = match as_reference(self.lookup(&target)?) {
+                Some(next) => absolute(&target, next),
+                None => break,
+            };
+            seen.push(target);
+            target = next;
+        }
+        let value = self.lookup(&target)?;
+        Ok((target, value))
+    }
+
+    fn inline(&mut self, base: &str, jz: &Jzon, stack: &mut Vec<String>) -> Result<Jzon> {
+        if let Some(reference) = as_reference(jz) {
+            let target = absolute(base, reference);
+            if stack.contains(&target) {
+                stack.push(target);
+                return Err(ExpectNoneCycle(stack.split_off(0)));
+            }
+            let value = self.lookup(&target)?.clone();
+            stack.push(target.clone());
+            let inlined = self.inline(&target, &value, stack)?;
+            stack.pop();
+            return Ok(inlined);
+        }
+        match jz {
+            Jzon::Object(map) => {
+                let mut inlined = Map::new();
+                for (k, v) in map {
+                    inlined.insert(k.clone(), self.inline(base, v, stack)?);
+                }
+                Ok(Jzon::Object(inlined))
+            }
+            Jzon::Array(vec) => vec
+                .iter()
+                .map(|v| self.inline(base, v, stack))
+                .collect::<Result<_>>()
+                .map(Jzon::Array),
+            _ => Ok(jz.clone()),
+        }
+    }
+
+    // the value at an absolute reference
+    fn lookup(&mut self, target: &str) -> Result<&Jzon> {
+        let (uri, fragment) = target.split_once('#').unwrap_or((target, ""));
+        let path = Path::from_pointer(&percent_decode(fragment))
+            .ok_or_else(|| ExpectPointer(target.to_string()))?;
+        self.document(uri)?
+            .get_path(&path)
+            .ok_or_else(|| ExpectTarget(target.to_string()))
+    }
+
+    fn document(&mut self, uri: &str) -> Result<&Jzon> {
+        if !self.docs.contains_key(uri) {
+            let doc = self
+                .loader
+                .load(uri)
+                .ok_or_else(|| ExpectDocument(uri.to_string()))?;
+            self.docs.insert(uri.to_string(), doc);
+        }
+        Ok(&self.docs[uri])
+    }
+}
+
+// whether a relative path never climbs above where it starts
+fn stays_under(uri: &str) -> bool {
+    let mut depth = 0usize;
+    for component in path::Path::new(uri).components() {
+        depth = match component {
+            Component::Normal(_) => depth + 1,
+            Component::CurDir => depth,
+            Component::ParentDir if depth > 0 => depth - 1,
+            _ => return false,
+        };
+    }
+    true
+}
+
+fn as_reference(jz: &Jzon) -> Option<&str> {
+    match jz {
+        Jzon::Object(map) => match map.get("$ref") {
+            Some(Jzon::String(s)) => Some(s),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// `reference` as written in the document `base`, with the document named
+// in full and always a fragment, "" for the whole document
+fn absolute(base: &str, reference: &str) -> String {
+    let base = base.split('#').next().unwrap();
+    let (uri, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+    let uri = if uri.is_empty() {
+        base.to_string()
+    } else if uri.contains("://") || uri.starts_with('/') {
+        uri.to_string()
+    } else {
+        let dir = base.rfind('/').map_or("", |i| &base[..=i]);
+        normalize(&format!("{}{}", dir, uri))
+    };
+    format!("{}#{}", uri, fragment)
+}
+
+// without "." and ".." segments, as far as they can go
+fn normalize(uri: &str) -> String {
+    let mut segments: Vec<&str> = vec![];
+    for seg in uri.split('/') {
+        match seg {
+            "." => (),
+            ".." if segments.last().is_some_and(|s| *s != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(seg),
+        }
+    }
+    segments.join("/")
+}
+
+// fragments are URI encoded, so "%25" is "%"
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| {
+            let h = std::str::from_utf8(h).ok()?;
+            u8::from_str_radix(h, 16).ok()
+        });
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader() -> HashMap<String, Jzon> {
+        let docs = [
+            (
+                "api/spec.json",
+                r##"{"user": {"$ref": "../common.json#/definitions/User"},
+                    "ids": [{"$ref": "../common.json#/definitions/Id"}, {"$ref": "#/local"}],
+                    "local": {"$ref": "#/chained"},
+                    "chained": "here",
+                    "loop": {"$ref": "#/loop"},
+                    "tree": {"$ref": "../common.json#/definitions/Tree"}}"##,
+            ),
+            (
+                "common.json",
+                r##"{"definitions": {
+                    "Id": {"type": "integer"},
+                    "User": {"properties": {"id": {"$ref": "#/definitions/Id"},
+                                            "a/b": {"$ref": "./types/x.json"}}},
+                    "Tree": {"items": {"$ref": "#/definitions/Tree"}},
+                    "%": 1}}"##,
+            ),
+            ("types/x.json", r#"{"type": "string"}"#),
+        ];
+        docs.iter()
+            .map(|(name, text)| (name.to_string(), Jzon::parse(text.as_bytes()).unwrap()))
+            .collect()
+    }
+
+    fn parse(text: &str) -> Jzon {
+        Jzon::parse(text.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn resolve() {
+        let mut resolver = Resolver::new(loader());
+        let (uri, user) = resolver.resolve("api/spec.json", "#/user").unwrap();
+        assert_eq!("common.json#/definitions/User", uri);
+        // the reference inside is left alone
+        assert_eq!(
+            &parse(r##"{"$ref": "#/definitions/Id"}"##),
+            &user["properties"]["id"]
+        );
+        let (uri, id) = resolver
+            .resolve(&uri, "#/definitions/User/properties/id")
+            .unwrap();
+        assert_eq!("common.json#/definitions/Id", uri);
+        assert_eq!(&parse(r#"{"type": "integer"}"#), id);
+        let (_, local) = resolver.resolve("api/spec.json", "#/ids/1").unwrap();
+        assert_eq!(&parse(r#""here""#), local);
+        let (_, x) = resolver.resolve("common.json", "types/x.json").unwrap();
+        assert_eq!(&parse(r#"{"type": "string"}"#), x);
+        let (_, percent) = resolver
+            .resolve("common.json", "#/definitions/%25")
+            .unwrap();
+        assert_eq!(&Jzon::Integer(1), percent);
+        // recursive, but fine as long as nothing inlines it
+        assert!(resolver.resolve("api/spec.json", "#/tree").is_ok());
+
+        assert!(matches!(
+            resolver.resolve("api/spec.json", "#/loop"),
+            Err(ExpectNoneCycle(refs)) if refs == ["api/spec.json#/loop"; 2]
+        ));
+        assert!(matches!(
+            resolver.resolve("api/spec.json", "missing.json"),
+            Err(ExpectDocument(uri)) if uri == "api/missing.json"
+        ));
+        assert!(matches!(
+            resolver.resolve("api/spec.json", "#/nothing"),
+            Err(ExpectTarget(_))
+        ));
+        assert!(matches!(
+            resolver.resolve("api/spec.json", "#nothing"),
+            Err(ExpectPointer(_))
+        ));
+    }
+
+    #[test]
+    fn bundle() {
+        let mut docs = loader();
+        let spec = docs.get_mut("api/spec.json").unwrap();
+        if let Jzon::Object(map) = spec {
+            map.remove("loop");
+            map.remove("tree");
+        }
+        let mut resolver = Resolver::new(docs);
+        let expected = parse(
+            r#"{"user": {"properties": {"id": {"type": "integer"},
+                                        "a/b": {"type": "string"}}},
+                "ids": [{"type": "integer"}, "here"],
+                "local": "here",
+                "chained": "here"}"#,
+        );
+        assert_eq!(expected, resolver.bundle("api/spec.json").unwrap());
+
+        let mut resolver = Resolver::new(loader());
+        assert!(matches!(
+            resolver.bundle("common.json"),
+            Err(ExpectNoneCycle(refs))
+                if refs == ["common.json#/definitions/Tree"; 2]
+        ));
+        assert!(matches!(
+            resolver.bundle("nothing.json"),
+            Err(ExpectDocument(_))
+        ));
+    }
+
+    #[test]
+    fn file_loader() {
+        let mut resolver = Resolver::new(FileLoader::new("data/roundtrip"));
+        let (_, doc) = resolver.resolve("a/b.json", "../roundtrip01.json").unwrap();
+        assert_eq!(&parse("[null]"), doc);
+
+        // data/twitter.json is there, but not under the root
+        assert!(matches!(
+            resolver.resolve("a.json", "../twitter.json"),
+            Err(ExpectDocument(uri)) if uri == "../twitter.json"
+        ));
+        assert!(matches!(
+            resolver.resolve("a.json", "./x/../../twitter.json"),
+            Err(ExpectDocument(_))
+        ));
+        let outside = fs::canonicalize("data/twitter.json").unwrap();
+        let outside = outside.to_str().unwrap();
+        assert!(matches!(
+            resolver.resolve("a.json", outside),
+            Err(ExpectDocument(uri)) if uri == outside
+        ));
+        assert!(FileLoader::new("data").load("twitter.json").is_some());
+        assert!(FileLoader::new("data/roundtrip")
+            .load("x/../../twitter.json")
+            .is_none());
+    }
+}
@@ -0,0 +1,479 @@
+use crate::jzon::Jzon;
+use crate::map::Map;
+use std::collections::{BTreeMap, BTreeSet};
+
+// Infers a JSON Schema, draft 2020-12, from sample documents.
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+// Strings become an enum when there are at most this many distinct values
+// and each of them was seen at least twice on average.
+const ENUM_LIMIT: usize = 8;
+
+#[derive(Debug, Default)]
+pub struct Inferrer {
+    root: Shape,
+}
+
+// everything the samples showed at one place in the documents
+#[derive(Debug, Default)]
+struct Shape {
+    seen: usize,
+    null: bool,
+    boolean: bool,
+    integer: bool,
+    number: bool,
+    string: Option<Strings>,
+    // all elements of all arrays merged
+    array: Option<Box<Shape>>,
+    object: Option<Object>,
+}
+
+#[derive(Debug)]
+struct Strings {
+    seen: usize,
+    // dropped once there are too many to make an enum
+    values: Option<BTreeSet<String>>,
+    // the formats every value so far has had
+    formats: Vec<Format>,
+}
+
+#[derive(Debug, Default)]
+struct Object {
+    seen: usize,
+    fields: BTreeMap<String, Shape>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    DateTime,
+    Date,
+    Uuid,
+    Email,
+    Uri,
+    Ipv4,
+}
+
+const FORMATS: [Format; 6] = [
+    Format::DateTime,
+    Format::Date,
+    Format::Uuid,
+    Format::Email,
+    Format::Uri,
+    Format::Ipv4,
+];
+
+impl Jzon {
+    pub fn infer_schema<'a, I: IntoIterator<Item = &'a Jzon>>(samples: I) -> Jzon {
+        let mut inferrer = Inferrer::new();
+        for sample in samples {
+            inferrer.add(sample);
+        }
+        inferrer.schema()
+    }
+}
+
+impl Inferrer {
+    pub fn new() -> Self {
+        Inferrer::default()
+    }
+
+    pub fn add(&mut self, sample: &Jzon) {
+        self.root.add(sample);
+    }
+
+    pub fn samples(&self) -> usize {
+        self.root.seen
+    }
+
+    pub fn schema(&self) -> Jzon {
+        let mut schema = self.root.schema();
+        if let Jzon::Object(map) = &mut schema {
+            map.insert("$schema".into(), Jzon::String(DRAFT.into()));
+        }
+        schema
+    }
+}
+
+impl Shape {
+    fn add(&mut self, jz: &Jzon) {
+        self.seen += 1;
+        match jz {
+            Jzon::Null => self.null = true,
+            Jzon::Bool(_) => self.boolean = true,
+            Jzon::Integer(_) => self.integer = true,
+            Jzon::Double(_) => self.number = true,
+            Jzon::String(s) => self.string.get_or_insert_with(Strings::new).add(s),
+            Jzon::Array(vec) => {
+                let items = self.array.get_or_insert_with(Default::default);
+                for v in vec {
+                    items.add(v);
+                }
+            }
+            Jzon::Object(map) => {
+                let object = self.object.get_or_insert_with(Default::default);
+                object.seen += 1;
+                for (k, v) in map {
+                    object.fields.entry(k.clone()).or_default().add(v);
+                }
+            }
+            // a placeholder tells nothing about the type
+            Jzon::Error => (),
+        }
+    }
+
+    fn schema(&self) -> Jzon {
+        let mut schema = Map::new();
+        let mut types = vec![];
+
+        if let Some(object) = &self.object {
+            types.push("object");
+            let properties = object
+                .fields
+                .iter()
+                .map(|(k, shape)| (k.clone(), shape.schema()))
+                .collect();
+            schema.insert("properties".into(), Jzon::Object(properties));
+            let required: Vec<Jzon> = object
+                .fields
+                .iter()
+                .filter(|(_, shape)| shape.seen == object.seen)
+                .map(|(k, _)| Jzon::String(k.clone()))
+                .collect();
+            if !required.is_empty() {
+                schema.insert("required".into(), Jzon::Array(required));
+            }
+        }
+        if let Some(items) = &self.array {
+            types.push("array");
+            if items.seen > 0 {
+                schema.insert("items".into(), items.schema());
+            }
+        }
+        if let Some(strings) = &self.string {
+            types.push("string");
+            match (strings.formats.first(), &strings.values) {
+                (Some(format), _) => {
+                    schema.insert("format".into(), Jzon::String(format.name().into()));
+                }
+                // an enum would reject values of the other types
+                (None, Some(values))
+                    if strings.repeated()
+                        && types.len() == 1
+                        && !self.numeric()
+                        && !self.boolean =>
+                {
+                    let mut values: Vec<Jzon> =
+                        values.iter().map(|v| Jzon::String(v.clone())).collect();
+                    if self.null {
+                        values.push(Jzon::Null);
+                    }
+                    schema.insert("enum".into(), Jzon::Array(values));
+                }
+                _ => (),
+            }
+        }
+        // integers are numbers too
+        if self.number {
+            types.push("number");
+        } else if self.integer {
+            types.push("integer");
+        }
+        if self.boolean {
+            types.push("boolean");
+        }
+        if self.null {
+            types.push("null");
+        }
+
+        match types.as_slice() {
+            [] => (),
+            [t] => {
+                schema.insert("type".into(), Jzon::String(t.to_string()));
+            }
+            _ => {
+                let types = types.iter().map(|t| Jzon::String(t.to_string())).collect();
+                schema.insert("type".into(), Jzon::Array(types));
+            }
+        }
+        Jzon::Object(schema)
+    }
+
+    fn numeric(&self) -> bool {
+        self.integer || self.number
+    }
+}
+
+impl Strings {
+    fn new() -> Self {
+        Strings {
+            seen: 0,
+            values: Some(BTreeSet::new()),
+            formats: FORMATS.to_vec(),
+        }
+    }
+
+    fn add(&mut self, s: &str) {
+        self.seen += 1;
+        if let Some(values) = &mut self.values {
+            values.insert(s.to_string());
+            if values.len() > ENUM_LIMIT {
+                self.values = None;
+            }
+        }
+        self.formats.retain(|format| format.matches(s));
+    }
+
+    fn repeated(&self) -> bool {
+        self.values
+            .as_ref()
+            .is_some_and(|values| self.seen >= 2 * values.len())
+    }
+}
+
+impl Format {
+    fn name(self) -> &'static str {
+        match self {
+            Format::DateTime => "date-time",
+            Format::Date => "date",
+            Format::Uuid => "uuid",
+            Format::Email => "email",
+            Format::Uri => "uri",
+            Format::Ipv4 => "ipv4",
+        }
+    }
+
+    fn matches(self, s: &str) -> bool {
+        match self {
+            Format::DateTime => is_date_time(s),
+            Format::Date => is_date(s),
+            Format::Uuid => is_uuid(s),
+            Format::Email => is_email(s),
+            Format::Uri => is_uri(s),
+            Format::Ipv4 => is_ipv4(s),
+        }
+    }
+}
+
+// `pattern` has 9 for a digit, anything else stands for itself
+fn shaped(s: &[u8], pattern: &str) -> bool {
+    s.len() == pattern.len()
+        && s.iter().zip(pattern.bytes()).all(|(c, p)| match p {
+            b'9' => c.is_ascii_digit(),
+            _ => *c == p,
+        })
+}
+
+fn number(s: &[u8]) -> u32 {
+    s.iter().fold(0, |n, d| n * 10 + (d - b'0') as u32)
+}
+
+fn is_date(s: &str) -> bool {
+    full_date(s.as_bytes())
+}
+
+// full-date of RFC 3339
+fn full_date(s: &[u8]) -> bool {
+    shaped(s, "9999-99-99")
+        && (1..=12).contains(&number(&s[5..7]))
+        && (1..=31).contains(&number(&s[8..10]))
+}
+
+// date-time of RFC 3339, e.g. 2024-01-31T23:59:60.5+01:00
+fn is_date_time(s: &str) -> bool {
+    let b = s.as_bytes();
+    if b.len() < 20 || !full_date(&b[..10]) || !b"Tt".contains(&b[10]) {
+        return false;
+    }
+    if !shaped(&b[11..19], "99:99:99")
+        || number(&b[11..13]) > 23
+        || number(&b[14..16]) > 59
+        || number(&b[17..19]) > 60
+    {
+        return false;
+    }
+    let mut rest = &b[19..];
+    if rest.first() == Some(&b'.') {
+        let digits = rest[1..].iter().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return false;
+        }
+        rest = &rest[1 + digits..];
+    }
+    match rest {
+        b"Z" | b"z" => true,
+        [b'+' | b'-', ..] => shaped(&rest[1..], "99:99") && number(&rest[1..3]) <= 23,
+        _ => false,
+    }
+}
+
+fn is_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.bytes().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == b'-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+// a practical subset of RFC 5321 addresses, the domain needs a dot
+fn is_email(s: &str) -> bool {
+    let (local, domain) = match s.split_once('@') {
+        Some(split) => split,
+        None => return false,
+    };
+    let atext = |c: char| c.is_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c);
+    !local.is_empty()
+        && local.chars().all(atext)
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+}
+
+// absolute URIs with an authority, like http://host/path
+fn is_uri(s: &str) -> bool {
+    let (scheme, rest) = match s.split_once("://") {
+        Some(split) => split,
+        None => return false,
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        && !rest.is_empty()
+        && !rest.starts_with('/')
+        && !rest.contains(|c: char| c.is_whitespace() || c.is_control())
+}
+
+fn is_ipv4(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('.').collect();
+    parts.len() == 4
+        && parts.iter().all(|p| {
+            (1..=3).contains(&p.len())
+                && p.bytes().all(|c| c.is_ascii_digit())
+                && (p.len() == 1 || !p.starts_with('0'))
+                && number(p.as_bytes()) <= 255
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn infer(samples: &[&str]) -> Jzon {
+        let samples: Vec<Jzon> = samples
+            .iter()
+            .map(|s| Jzon::parse(s.as_bytes()).unwrap())
+            .collect();
+        Jzon::infer_schema(&samples)
+    }
+
+    fn compact(jz: &Jzon) -> String {
+        format!("{}", jz.styled().sort_keys(true))
+    }
+
+    #[test]
+    fn objects() {
+        let schema = infer(&[
+            r#"{"id": 1, "name": "a", "tags": ["x"], "score": 1}"#,
+            r#"{"id": 2, "name": null, "tags": [], "score": 2.5}"#,
+            r#"{"id": 3, "name": "c", "extra": {"k": true}}"#,
+        ]);
+        assert_eq!(DRAFT, schema["$schema"]);
+        assert_eq!("object", schema["type"]);
+        assert_eq!(r#"["id","name"]"#, compact(&schema["required"]));
+        let props = &schema["properties"];
+        assert_eq!("integer", props["id"]["type"]);
+        assert_eq!(r#"["string","null"]"#, compact(&props["name"]["type"]));
+        assert_eq!(
+            r#"{"items":{"type":"string"},"type":"array"}"#,
+            compact(&props["tags"])
+        );
+        assert_eq!("number", props["score"]["type"]);
+        assert_eq!(
+            r#"{"properties":{"k":{"type":"boolean"}},"required":["k"],"type":"object"}"#,
+            compact(&props["extra"])
+        );
+    }
+
+    #[test]
+    fn enums() {
+        let schema = infer(&[r#"["red", "green", "red", "blue", null, "green", "blue"]"#]);
+        assert_eq!(
+            r#"{"enum":["blue","green","red",null],"type":["string","null"]}"#,
+            compact(&schema["items"])
+        );
+        // too few repetitions to tell
+        let schema = infer(&[r#"["a", "b", "c"]"#]);
+        assert_eq!(r#"{"type":"string"}"#, compact(&schema["items"]));
+        let schema = infer(&[r#"["a", "a", 1, 1]"#]);
+        assert_eq!(
+            r#"{"type":["string","integer"]}"#,
+            compact(&schema["items"])
+        );
+        let schema = infer(&[
+            r#"["a", "b", "c", "d", "e", "f", "g", "h", "i", "a", "b", "c", "d", "e", "f", "g", "h", "i"]"#,
+        ]);
+        assert_eq!(r#"{"type":"string"}"#, compact(&schema["items"]));
+    }
+
+    #[test]
+    fn formats() {
+        let format = |values: &str| {
+            let schema = infer(&[values]);
+            compact(&schema["items"])
+        };
+        assert_eq!(
+            r#"{"format":"date-time","type":"string"}"#,
+            format(
+                r#"["2024-01-31T23:59:60.5+01:00", "1985-04-12T23:20:50Z", "1985-04-12T23:20:50Z"]"#
+            )
+        );
+        assert_eq!(
+            r#"{"format":"date","type":"string"}"#,
+            format(r#"["2024-02-29", "1999-12-01"]"#)
+        );
+        assert_eq!(
+            r#"{"format":"uuid","type":"string"}"#,
+            format(
+                r#"["123e4567-e89b-12d3-a456-426614174000", "00000000-0000-0000-0000-000000000000"]"#
+            )
+        );
+        assert_eq!(
+            r#"{"format":"email","type":"string"}"#,
+            format(r#"["a.b+c@example.com", "x@y.io"]"#)
+        );
+        assert_eq!(
+            r#"{"format":"uri","type":"string"}"#,
+            format(r#"["https://example.com/a?b", "ftp://h"]"#)
+        );
+        assert_eq!(
+            r#"{"format":"ipv4","type":"string"}"#,
+            format(r#"["10.0.0.1", "255.255.255.0"]"#)
+        );
+        assert_eq!(r#"{"type":"string"}"#, format(r#"["2024-01-31", "soon"]"#));
+
+        assert!(!is_date("2024-13-01"));
+        assert!(!is_date_time("2024-01-31 23:59:59Z"));
+        assert!(!is_date_time("2024-01-31T24:00:00Z"));
+        assert!(!is_email("a@localhost"));
+        assert!(!is_email(".a@b.c"));
+        assert!(!is_uri("mailto:a@b.c"));
+        assert!(!is_ipv4("1.2.3.04"));
+    }
+
+    #[test]
+    fn mixed() {
+        assert_eq!(
+            format!(r#"{{"$schema":{}}}"#, Jzon::String(DRAFT.into())),
+            compact(&Inferrer::new().schema())
+        );
+        let schema = infer(&["1", "[]", r#"{"a": 1}"#]);
+        assert_eq!(r#"["object","array","integer"]"#, compact(&schema["type"]));
+        assert!(matches!(schema["properties"], Jzon::Object(_)));
+    }
+}
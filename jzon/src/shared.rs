@@ -0,0 +1,593 @@
+use crate::diff::{Path, Segment};
+use crate::jzon::Jzon;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::slice;
+use std::sync::Arc;
+
+// An immutable document for handing one parsed tree to many threads. Every
+// node sits behind an `Arc`, so cloning is O(1), and `with_path` copies
+// only the nodes on the way to the change: objects are hash array mapped
+// tries and arrays 32-way tries, so that is a handful of small nodes per
+// level, while everything else stays shared with the original.
+
+pub type Key = Arc<str>;
+
+#[derive(Debug, Clone)]
+pub enum Shared {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Double(f64),
+    String(Arc<str>),
+    Array(Vector),
+    Object(Map),
+}
+
+// bits of the hash or index consumed per level
+const BITS: u32 = 5;
+const MASK: usize = (1 << BITS) - 1;
+
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    len: usize,
+    root: Arc<Node>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    // an entry for every bit set in the bitmap, in bit order
+    Branch(u32, Vec<Entry>),
+    // leaves whose hashes are equal all the way down
+    Collision(Vec<Entry>),
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    Leaf(u64, Key, Shared),
+    Node(Arc<Node>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Vector {
+    len: usize,
+    // of the root, 0 while it is a leaf
+    shift: u32,
+    root: Arc<Chunk>,
+}
+
+#[derive(Debug, Clone)]
+enum Chunk {
+    Inner(Vec<Arc<Chunk>>),
+    Leaf(Vec<Shared>),
+}
+
+impl Shared {
+    pub fn from_jzon(jz: &Jzon) -> Shared {
+        match jz {
+            Jzon::Null | Jzon::Error => Shared::Null,
+            Jzon::Bool(v) => Shared::Bool(*v),
+            Jzon::Integer(v) => Shared::Integer(*v),
+            Jzon::Double(v) => Shared::Double(*v),
+            Jzon::String(s) => Shared::String(Arc::from(s.as_str())),
+            Jzon::Array(vec) => {
+                let mut vector = Vector::new();
+                vec.iter().for_each(|v| vector.push(Shared::from_jzon(v)));
+                Shared::Array(vector)
+            }
+            Jzon::Object(map) => {
+                let mut members = Map::new();
+                for (k, v) in map {
+                    members.insert(Arc::from(k.as_str()), Shared::from_jzon(v));
+                }
+                Shared::Object(members)
+            }
+        }
+    }
+
+    pub fn to_jzon(&self) -> Jzon {
+        match self {
+            Shared::Null => Jzon::Null,
+            Shared::Bool(v) => Jzon::Bool(*v),
+            Shared::Integer(v) => Jzon::Integer(*v),
+            Shared::Double(v) => Jzon::Double(*v),
+            Shared::String(s) => Jzon::String(s.to_string()),
+            Shared::Array(vector) => Jzon::Array(vector.iter().map(Shared::to_jzon).collect()),
+            Shared::Object(map) => Jzon::Object(
+                map.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_jzon()))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Shared> {
+        match self {
+            Shared::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn at(&self, idx: usize) -> Option<&Shared> {
+        match self {
+            Shared::Array(vector) => vector.get(idx),
+            _ => None,
+        }
+    }
+
+    // indices also match object keys, as everywhere else paths are used
+    pub fn get_path(&self, path: &Path) -> Option<&Shared> {
+        path.0.iter().try_fold(self, |v, seg| match (v, seg) {
+            (Shared::Object(map), seg) => map.get(&segment_key(seg)),
+            (Shared::Array(vector), Segment::Index(i)) => vector.get(*i),
+            _ => None,
+        })
+    }
+
+    pub fn pointer(&self, pointer: &str) -> Option<&Shared> {
+        self.get_path(&Path::from_pointer(pointer)?)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Shared::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    // A copy with the value at `path` replaced, or inserted if the path
+    // doesn't exist yet but its parent does; an index equal to the length
+    // appends. `None` if there is nowhere to put it.
+    pub fn with_path(&self, path: &Path, value: Shared) -> Option<Shared> {
+        if let Some((last, parent)) = path.0.split_last() {
+            match (self.get_path(&Path(parent.to_vec()))?, last) {
+                (Shared::Object(_), _) => (),
+                (Shared::Array(vector), Segment::Index(i)) if *i <= vector.len() => (),
+                _ => return None,
+            }
+        }
+        let mut doc = self.clone();
+        doc.set(&path.0, value);
+        Some(doc)
+    }
+
+    // the path was checked, so only nodes that change get copied
+    fn set(&mut self, path: &[Segment], value: Shared) {
+        let (seg, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return *self = value,
+        };
+        match self {
+            Shared::Object(map) if rest.is_empty() => {
+                map.insert(Arc::from(segment_key(seg)), value)
+            }
+            Shared::Object(map) => map.get_mut(&segment_key(seg)).unwrap().set(rest, value),
+            Shared::Array(vector) => match seg {
+                Segment::Index(i) if rest.is_empty() && *i == vector.len() => vector.push(value),
+                Segment::Index(i) => vector.get_mut(*i).unwrap().set(rest, value),
+                Segment::Key(_) => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn segment_key(seg: &Segment) -> String {
+    match seg {
+        Segment::Key(k) => k.clone(),
+        Segment::Index(i) => i.to_string(),
+    }
+}
+
+fn hash(key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+// the bit for `hash` in a branch at `shift`
+fn bit(hash: u64, shift: u32) -> u32 {
+    1 << ((hash >> shift) as usize & MASK)
+}
+
+// where the entry for `bit` is among those the bitmap has
+fn index(bitmap: u32, bit: u32) -> usize {
+    (bitmap & (bit - 1)).count_ones() as usize
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Node::Branch(0, vec![])
+    }
+}
+
+impl Node {
+    fn entries(&self) -> &[Entry] {
+        match self {
+            Node::Branch(_, entries) | Node::Collision(entries) => entries,
+        }
+    }
+
+    fn get_mut(&mut self, hash: u64, shift: u32, key: &str) -> Option<&mut Shared> {
+        let entries = match self {
+            Node::Branch(bitmap, entries) => {
+                let bit = bit(hash, shift);
+                if *bitmap & bit == 0 {
+                    return None;
+                }
+                match &mut entries[index(*bitmap, bit)] {
+                    Entry::Node(child) => {
+                        return Arc::make_mut(child).get_mut(hash, shift + BITS, key)
+                    }
+                    entry => slice::from_mut(entry),
+                }
+            }
+            Node::Collision(entries) => entries,
+        };
+        entries.iter_mut().find_map(|entry| match entry {
+            Entry::Leaf(_, k, v) if k.as_ref() == key => Some(v),
+            _ => None,
+        })
+    }
+
+    // true if the key is new
+    fn insert(&mut self, hash: u64, shift: u32, key: Key, value: Shared) -> bool {
+        let (bitmap, entries) = match self {
+            Node::Branch(bitmap, entries) => (bitmap, entries),
+            Node::Collision(entries) => {
+                for entry in entries.iter_mut() {
+                    if let Entry::Leaf(_, k, v) = entry {
+                        if *k == key {
+                            *v = value;
+                            return false;
+                        }
+                    }
+                }
+                entries.push(Entry::Leaf(hash, key, value));
+                return true;
+            }
+        };
+        let bit = bit(hash, shift);
+        let i = index(*bitmap, bit);
+        if *bitmap & bit == 0 {
+            *bitmap |= bit;
+            entries.insert(i, Entry::Leaf(hash, key, value));
+            return true;
+        }
+        let slot = &mut entries[i];
+        match slot {
+            Entry::Node(child) => Arc::make_mut(child).insert(hash, shift + BITS, key, value),
+            Entry::Leaf(_, k, v) if *k == key => {
+                *v = value;
+                false
+            }
+            Entry::Leaf(h, k, v) => {
+                let old = Entry::Leaf(*h, k.clone(), v.clone());
+                let new = Entry::Leaf(hash, key, value);
+                *slot = Entry::Node(Arc::new(Node::split(old, new, shift + BITS)));
+                true
+            }
+        }
+    }
+
+    // a node at `shift` for two leaves whose hashes agreed so far
+    fn split(a: Entry, b: Entry, shift: u32) -> Node {
+        let hashes = match (&a, &b) {
+            (Entry::Leaf(ha, _, _), Entry::Leaf(hb, _, _)) => (*ha, *hb),
+            _ => unreachable!(),
+        };
+        if shift >= u64::BITS {
+            return Node::Collision(vec![a, b]);
+        }
+        let (ba, bb) = (bit(hashes.0, shift), bit(hashes.1, shift));
+        if ba == bb {
+            let child = Node::split(a, b, shift + BITS);
+            Node::Branch(ba, vec![Entry::Node(Arc::new(child))])
+        } else if ba < bb {
+            Node::Branch(ba | bb, vec![a, b])
+        } else {
+            Node::Branch(ba | bb, vec![b, a])
+        }
+    }
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Map::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Shared> {
+        let hash = hash(key);
+        let mut node = &*self.root;
+        let mut shift = 0;
+        loop {
+            let entry = match node {
+                Node::Branch(bitmap, entries) => {
+                    let bit = bit(hash, shift);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+                    &entries[index(*bitmap, bit)]
+                }
+                Node::Collision(entries) => {
+                    return entries.iter().find_map(|entry| match entry {
+                        Entry::Leaf(_, k, v) if k.as_ref() == key => Some(v),
+                        _ => None,
+                    })
+                }
+            };
+            match entry {
+                Entry::Leaf(_, k, v) => return Some(v).filter(|_| k.as_ref() == key),
+                Entry::Node(child) => node = child,
+            }
+            shift += BITS;
+        }
+    }
+
+    // copies the nodes on the way unless this map is their only owner
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Shared> {
+        Arc::make_mut(&mut self.root).get_mut(hash(key), 0, key)
+    }
+
+    pub fn insert(&mut self, key: Key, value: Shared) {
+        let hash = hash(&key);
+        if Arc::make_mut(&mut self.root).insert(hash, 0, key, value) {
+            self.len += 1;
+        }
+    }
+
+    // in no particular order
+    pub fn iter(&self) -> MapIter<'_> {
+        MapIter {
+            stack: vec![self.root.entries().iter()],
+        }
+    }
+}
+
+pub struct MapIter<'a> {
+    stack: Vec<slice::Iter<'a, Entry>>,
+}
+
+impl<'a> Iterator for MapIter<'a> {
+    type Item = (&'a Key, &'a Shared);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some(Entry::Leaf(_, k, v)) => return Some((k, v)),
+                Some(Entry::Node(child)) => self.stack.push(child.entries().iter()),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+impl Default for Vector {
+    fn default() -> Self {
+        Vector {
+            len: 0,
+            shift: 0,
+            root: Arc::new(Chunk::Leaf(vec![])),
+        }
+    }
+}
+
+impl Chunk {
+    // a chunk at `shift` down to a leaf holding only `value`
+    fn single(shift: u32, value: Shared) -> Chunk {
+        match shift {
+            0 => Chunk::Leaf(vec![value]),
+            _ => Chunk::Inner(vec![Arc::new(Chunk::single(shift - BITS, value))]),
+        }
+    }
+
+    fn get_mut(&mut self, shift: u32, idx: usize) -> &mut Shared {
+        match self {
+            Chunk::Inner(children) => {
+                let child = &mut children[(idx >> shift) & MASK];
+                Arc::make_mut(child).get_mut(shift - BITS, idx)
+            }
+            Chunk::Leaf(values) => &mut values[idx & MASK],
+        }
+    }
+
+    fn push(&mut self, shift: u32, idx: usize, value: Shared) {
+        match self {
+            Chunk::Inner(children) => {
+                let slot = (idx >> shift) & MASK;
+                match children.get_mut(slot) {
+                    Some(child) => Arc::make_mut(child).push(shift - BITS, idx, value),
+                    None => children.push(Arc::new(Chunk::single(shift - BITS, value))),
+                }
+            }
+            Chunk::Leaf(values) => values.push(value),
+        }
+    }
+}
+
+impl Vector {
+    pub fn new() -> Self {
+        Vector::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&Shared> {
+        if idx >= self.len {
+            return None;
+        }
+        self.leaf(idx).get(idx & MASK)
+    }
+
+    // copies the chunks on the way unless this vector is their only owner
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut Shared> {
+        if idx >= self.len {
+            return None;
+        }
+        Some(Arc::make_mut(&mut self.root).get_mut(self.shift, idx))
+    }
+
+    pub fn push(&mut self, value: Shared) {
+        if self.len == 1 << (self.shift + BITS) {
+            let root = Arc::clone(&self.root);
+            self.root = Arc::new(Chunk::Inner(vec![root]));
+            self.shift += BITS;
+        }
+        Arc::make_mut(&mut self.root).push(self.shift, self.len, value);
+        self.len += 1;
+    }
+
+    pub fn iter(&self) -> VectorIter<'_> {
+        VectorIter {
+            vector: self,
+            leaf: [].iter(),
+            next: 0,
+        }
+    }
+
+    // the leaf holding `idx`
+    fn leaf(&self, idx: usize) -> &[Shared] {
+        let mut chunk = &*self.root;
+        let mut shift = self.shift;
+        loop {
+            match chunk {
+                Chunk::Inner(children) => chunk = &children[(idx >> shift) & MASK],
+                Chunk::Leaf(values) => return values,
+            }
+            shift -= BITS;
+        }
+    }
+}
+
+pub struct VectorIter<'a> {
+    vector: &'a Vector,
+    leaf: slice::Iter<'a, Shared>,
+    // the index after the current leaf
+    next: usize,
+}
+
+impl<'a> Iterator for VectorIter<'a> {
+    type Item = &'a Shared;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(v) = self.leaf.next() {
+            return Some(v);
+        }
+        if self.next >= self.vector.len {
+            return None;
+        }
+        let leaf = self.vector.leaf(self.next);
+        self.next += leaf.len();
+        self.leaf = leaf.iter();
+        self.leaf.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn path(pointer: &str) -> Path {
+        Path::from_pointer(pointer).unwrap()
+    }
+
+    #[test]
+    fn with_path() {
+        let text = br#"{"a": {"b": [1, 2, 3], "c": "x"}, "d": {"e": null}}"#;
+        let doc = Shared::from_jzon(&Jzon::parse(text).unwrap());
+
+        let next = doc.with_path(&path("/a/b/1"), Shared::Integer(20)).unwrap();
+        assert!(matches!(doc.pointer("/a/b/1"), Some(Shared::Integer(2))));
+        assert!(matches!(next.pointer("/a/b/1"), Some(Shared::Integer(20))));
+        // the untouched subtree is the very same
+        match (doc.get("d"), next.get("d")) {
+            (Some(Shared::Object(a)), Some(Shared::Object(b))) => {
+                assert!(Arc::ptr_eq(&a.root, &b.root))
+            }
+            _ => panic!(),
+        }
+
+        let next = next
+            .with_path(&path("/a/b/3"), Shared::Bool(true))
+            .unwrap()
+            .with_path(&path("/d/f"), Shared::String(Arc::from("y")))
+            .unwrap()
+            .with_path(&path("/a/c"), Shared::Null)
+            .unwrap();
+        let expected = br#"{"a": {"b": [1, 20, 3, true], "c": null}, "d": {"e": null, "f": "y"}}"#;
+        assert_eq!(Jzon::parse(expected).unwrap(), next.to_jzon());
+        assert_eq!(Jzon::parse(text).unwrap(), doc.to_jzon());
+
+        assert!(doc.with_path(&path("/a/b/4"), Shared::Null).is_none());
+        assert!(doc.with_path(&path("/a/b/x"), Shared::Null).is_none());
+        assert!(doc.with_path(&path("/x/y"), Shared::Null).is_none());
+        assert!(doc.with_path(&path("/a/c/y"), Shared::Null).is_none());
+        let root = doc.with_path(&path(""), Shared::Integer(1)).unwrap();
+        assert!(matches!(root, Shared::Integer(1)));
+
+        // every thread with its own override
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let doc = doc.clone();
+                thread::spawn(move || {
+                    let doc = doc.with_path(&path("/d/e"), Shared::Integer(i)).unwrap();
+                    doc.pointer("/d/e").unwrap().to_jzon()
+                })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(Jzon::Integer(i as i64), handle.join().unwrap());
+        }
+    }
+
+    #[test]
+    fn tries() {
+        let mut vector = Vector::new();
+        let mut map = Map::new();
+        for i in 0..5000 {
+            vector.push(Shared::Integer(i));
+            map.insert(Arc::from(i.to_string()), Shared::Integer(i));
+        }
+        map.insert(Arc::from("17"), Shared::Null);
+        assert_eq!(5000, vector.len());
+        assert_eq!(5000, map.len());
+        assert_eq!(5000, map.iter().count());
+        assert!(vector
+            .iter()
+            .enumerate()
+            .all(|(i, v)| matches!(v, Shared::Integer(j) if *j == i as i64)));
+        assert!(matches!(map.get("4999"), Some(Shared::Integer(4999))));
+        assert!(matches!(map.get("17"), Some(Shared::Null)));
+        assert!(map.get("5000").is_none());
+        assert!(vector.get(5000).is_none());
+
+        let doc = Shared::Array(vector);
+        let next = doc.with_path(&path("/2500"), Shared::Null).unwrap();
+        assert!(matches!(next.at(2500), Some(Shared::Null)));
+        assert!(matches!(doc.at(2500), Some(Shared::Integer(2500))));
+        // only the leaf on the path was copied
+        assert!(std::ptr::eq(doc.at(0).unwrap(), next.at(0).unwrap()));
+        assert!(!std::ptr::eq(doc.at(2501).unwrap(), next.at(2501).unwrap()));
+
+        let content = std::fs::read("data/citm_catalog.json").unwrap();
+        let jz = Jzon::parse(&content).unwrap();
+        assert_eq!(jz, Shared::from_jzon(&jz).to_jzon());
+    }
+}
@@ -0,0 +1,472 @@
+use crate::jzon::{Jzon, ParseErr};
+use std::ops::Range;
+
+// A problem found while parsing tolerantly.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub err: ParseErr,
+    // bytes of the input to underline
+    pub span: Range<usize>,
+    // both start at 1, the column counts chars rather than bytes
+    pub line: usize,
+    pub column: usize,
+}
+
+use ParseErr::*;
+
+impl Jzon {
+    // Keeps going after syntax errors, resynchronizing at commas, braces and
+    // brackets. Whatever could not be parsed becomes `Jzon::Error`, and
+    // every problem is reported; valid input gives no diagnostics.
+    pub fn parse_tolerant(bytes: &[u8]) -> (Jzon, Vec<Diagnostic>) {
+        let mut parser = Parser {
+            bytes,
+            pos: 0,
+            closers: vec![],
+            diags: vec![],
+        };
+        let value = parser.value();
+        parser.space();
+        if parser.pos < bytes.len() {
+            let end = bytes.len() - parser.trailing_space();
+            parser.report(ExpectNoMore, parser.pos..end);
+        }
+
+        let diags = parser.diags;
+        let lines = line_starts(bytes);
+        let diags = diags
+            .into_iter()
+            .map(|(err, span)| {
+                let line = lines.partition_point(|start| *start <= span.start);
+                let before = &bytes[lines[line - 1]..span.start];
+                let column = String::from_utf8_lossy(before).chars().count() + 1;
+                Diagnostic {
+                    err,
+                    span,
+                    line,
+                    column,
+                }
+            })
+            .collect();
+        (value, diags)
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    // closing bytes of the containers being parsed, innermost last
+    closers: Vec<u8>,
+    diags: Vec<(ParseErr, Range<usize>)>,
+}
+
+impl Parser<'_> {
+    fn report(&mut self, err: ParseErr, span: Range<usize>) {
+        self.diags.push((err, span));
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn space(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn trailing_space(&self) -> usize {
+        self.bytes
+            .iter()
+            .rev()
+            .take_while(|b| b" \t\n\r".contains(b))
+            .count()
+    }
+
+    // end of the run of bytes that can't separate values, e.g. a bare word
+    fn word_end(&self) -> usize {
+        self.bytes[self.pos..]
+            .iter()
+            .position(|b| b" \t\n\r,:[]{}\"".contains(b))
+            .map_or(self.bytes.len(), |n| self.pos + n)
+    }
+
+    // a closer of an enclosing container ends this one without being eaten
+    fn closes_outer(&self, ch: u8) -> bool {
+        self.closers.iter().rev().skip(1).any(|c| *c == ch)
+    }
+
+    fn value(&mut self) -> Jzon {
+        self.space();
+        let start = self.pos;
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => Jzon::String(self.string()),
+            Some(b'-') | Some(b'0'..=b'9') => self.number(),
+            Some(b't') | Some(b'f') | Some(b'n') => self.literal(),
+            // left for the enclosing container to deal with
+            Some(b',') | Some(b']') | Some(b'}') => {
+                self.report(ExpectValue, start..start);
+                Jzon::Error
+            }
+            Some(_) => {
+                // at least one byte goes, so every loop makes progress
+                self.pos = self.word_end().max(start + 1);
+                self.report(ExpectPrefix, start..self.pos);
+                Jzon::Error
+            }
+            None => {
+                self.report(ExpectNoneEOF, start..start);
+                Jzon::Error
+            }
+        }
+    }
+
+    fn literal(&mut self) -> Jzon {
+        let start = self.pos;
+        self.pos = self.word_end();
+        match &self.bytes[start..self.pos] {
+            b"true" => Jzon::Bool(true),
+            b"false" => Jzon::Bool(false),
+            b"null" => Jzon::Null,
+            word => {
+                let err = if word[0] == b'n' {
+                    ExpectNull
+                } else {
+                    ExpectBool
+                };
+                self.report(err, start..self.pos);
+                Jzon::Error
+            }
+        }
+    }
+
+    fn number(&mut self) -> Jzon {
+        let start = self.pos;
+        match Jzon::parse_number(&self.bytes[start..]) {
+            Ok(state) => {
+                self.pos += state.consumed;
+                state.value
+            }
+            Err(_) => {
+                self.pos = self.word_end();
+                self.report(ExpectDigit, start..self.pos);
+                Jzon::Error
+            }
+        }
+    }
+
+    // A string left open ends with its line, which is where the closing
+    // quote was most likely forgotten.
+    fn string(&mut self) -> String {
+        let start = self.pos;
+        let mut value = vec![];
+        let mut encode_buf = [0; 4];
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b'\\') => match Jzon::parse_escaped(&self.bytes[self.pos..]) {
+                    Ok(state) => {
+                        let s = state.value.encode_utf8(&mut encode_buf);
+                        value.extend_from_slice(s.as_bytes());
+                        self.pos += state.consumed;
+                    }
+                    Err(err) => {
+                        let end = (self.pos + 2).min(self.bytes.len());
+                        self.report(err, self.pos..end);
+                        value.extend_from_slice("\u{FFFD}".as_bytes());
+                        self.pos = end;
+                    }
+                },
+                Some(b'\n') | Some(b'\r') | None => {
+                    self.report(ExpectNoneEOF, start..self.pos);
+                    break;
+                }
+                Some(ch @ 0x00..=0x1F) => {
+                    self.report(ExpectNoneControl, self.pos..self.pos + 1);
+                    value.push(ch);
+                    self.pos += 1;
+                }
+                Some(ch) => {
+                    value.push(ch);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        match String::from_utf8(value) {
+            Ok(value) => value,
+            Err(e) => {
+                self.report(ExpectCodePoint, start..self.pos);
+                String::from_utf8_lossy(e.as_bytes()).into_owned()
+            }
+        }
+    }
+
+    fn array(&mut self) -> Jzon {
+        let open = self.pos;
+        let mut vec = vec![];
+        // the last thing seen was a value rather than `[` or a comma
+        let mut after_value = false;
+        self.pos += 1;
+        self.closers.push(b']');
+        loop {
+            self.space();
+            let at = self.pos;
+            match self.peek() {
+                Some(b']') => {
+                    if !after_value && !vec.is_empty() {
+                        self.report(ExpectValue, at..at + 1);
+                    }
+                    self.pos += 1;
+                    break;
+                }
+                Some(b',') => {
+                    if !after_value {
+                        self.report(ExpectValue, at..at + 1);
+                        vec.push(Jzon::Error);
+                    }
+                    after_value = false;
+                    self.pos += 1;
+                }
+                Some(ch @ b'}') => {
+                    self.report(ExpectCommaBracket, at..at + 1);
+                    if self.closes_outer(ch) {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                Some(_) => {
+                    if after_value {
+                        self.report(ExpectCommaBracket, at..at);
+                    }
+                    vec.push(self.value());
+                    after_value = true;
+                }
+                None => {
+                    self.report(ExpectNoneEOF, open..open + 1);
+                    break;
+                }
+            }
+        }
+        self.closers.pop();
+        Jzon::Array(vec)
+    }
+
+    fn object(&mut self) -> Jzon {
+        let open = self.pos;
+        let mut members = vec![];
+        let mut after_value = false;
+        self.pos += 1;
+        self.closers.push(b'}');
+        loop {
+            self.space();
+            let at = self.pos;
+            match self.peek() {
+                Some(b'}') => {
+                    if !after_value && !members.is_empty() {
+                        self.report(ExpectPair, at..at + 1);
+                    }
+                    self.pos += 1;
+                    break;
+                }
+                Some(b',') => {
+                    if !after_value {
+                        self.report(ExpectPair, at..at + 1);
+                    }
+                    after_value = false;
+                    self.pos += 1;
+                }
+                Some(ch @ b']') => {
+                    self.report(ExpectCommaBrace, at..at + 1);
+                    if self.closes_outer(ch) {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                // a nested value where a key belongs is dropped, along
+                // with whatever it was given as
+                Some(b'{') | Some(b'[') => {
+                    self.report(ExpectPair, at..at + 1);
+                    self.value();
+                    self.space();
+                    if self.peek() == Some(b':') {
+                        self.pos += 1;
+                        self.value();
+                    }
+                    after_value = true;
+                }
+                Some(_) => {
+                    if after_value {
+                        self.report(ExpectCommaBrace, at..at);
+                    }
+                    let (key, value) = self.pair();
+                    members.push((key, value));
+                    after_value = true;
+                }
+                None => {
+                    self.report(ExpectNoneEOF, open..open + 1);
+                    break;
+                }
+            }
+        }
+        self.closers.pop();
+        Jzon::Object(members.into_iter().collect())
+    }
+
+    fn pair(&mut self) -> (String, Jzon) {
+        let start = self.pos;
+        let key = if self.peek() == Some(b'"') {
+            self.string()
+        } else {
+            // an unquoted key is taken as written
+            self.pos = self.word_end();
+            self.report(ExpectQuote, start..self.pos);
+            String::from_utf8_lossy(&self.bytes[start..self.pos]).into_owned()
+        };
+
+        self.space();
+        let at = self.pos;
+        match self.peek() {
+            Some(b':') => self.pos += 1,
+            Some(b',') | Some(b'}') | Some(b']') | None => {
+                self.report(ExpectColon, at..at);
+                return (key, Jzon::Error);
+            }
+            Some(_) => self.report(ExpectColon, at..at),
+        }
+
+        (key, self.value())
+    }
+}
+
+fn line_starts(bytes: &[u8]) -> Vec<usize> {
+    let newlines = bytes.iter().enumerate().filter(|(_, b)| **b == b'\n');
+    let mut starts = vec![0];
+    starts.extend(newlines.map(|(i, _)| i + 1));
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(text: &str) -> (Jzon, Vec<String>) {
+        let (jz, diags) = Jzon::parse_tolerant(text.as_bytes());
+        let diags = diags
+            .iter()
+            .map(|d| format!("{}:{} {:?} {:?}", d.line, d.column, d.err, d.span))
+            .collect();
+        (jz, diags)
+    }
+
+    #[test]
+    fn valid() {
+        let (jz, diags) = check(r#" {"a": [1, -2.5e3, "x\n", true, false, null], "b": {}} "#);
+        assert!(diags.is_empty());
+        assert_eq!(jz["a"][1], -2.5e3);
+        assert_eq!(jz["a"][2], "x\n");
+    }
+
+    #[test]
+    fn commas() {
+        let (jz, diags) = check("[1 2,,3,]");
+        assert_eq!(
+            vec![
+                "1:4 ExpectCommaBracket 3..3",
+                "1:6 ExpectValue 5..6",
+                "1:9 ExpectValue 8..9"
+            ],
+            diags
+        );
+        assert_eq!("[1,2,null,3]", jz.stringify());
+        assert!(matches!(jz[2], Jzon::Error));
+
+        let (jz, diags) = check("{\"a\": 1\n \"b\": 2,}");
+        assert_eq!(
+            vec!["2:2 ExpectCommaBrace 9..9", "2:9 ExpectPair 16..17"],
+            diags
+        );
+        assert_eq!(jz["b"], 2);
+    }
+
+    #[test]
+    fn strings() {
+        let (jz, diags) = check("[\"a\\qb\", \"open\n, \"é\tz\"]");
+        assert_eq!(
+            vec![
+                "1:4 ExpectEscaped 3..5",
+                "1:10 ExpectNoneEOF 9..14",
+                "2:5 ExpectNoneControl 20..21"
+            ],
+            diags
+        );
+        assert_eq!(jz[0], "a\u{FFFD}b");
+        assert_eq!(jz[1], "open");
+        assert_eq!(jz[2], "é\tz");
+    }
+
+    #[test]
+    fn values() {
+        let (jz, diags) = check(r#"{"a": tru, "b": nul, "c": --1, "d": @, "e": }"#);
+        assert_eq!(
+            vec![
+                "1:7 ExpectBool 6..9",
+                "1:17 ExpectNull 16..19",
+                "1:27 ExpectDigit 26..29",
+                "1:37 ExpectPrefix 36..37",
+                "1:45 ExpectValue 44..44"
+            ],
+            diags
+        );
+        if let Jzon::Object(map) = jz {
+            assert!(map.values().all(|v| matches!(v, Jzon::Error)));
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn keys() {
+        let (jz, diags) = check(r#"{a: 1, "b" 2, "c", [3]: 4}"#);
+        assert_eq!(
+            vec![
+                "1:2 ExpectQuote 1..2",
+                "1:12 ExpectColon 11..11",
+                "1:18 ExpectColon 17..17",
+                "1:20 ExpectPair 19..20"
+            ],
+            diags
+        );
+        assert_eq!(jz["a"], 1);
+        assert_eq!(jz["b"], 2);
+        assert!(matches!(jz["c"], Jzon::Error));
+    }
+
+    #[test]
+    fn brackets() {
+        let (jz, diags) = check(r#"{"a": [1, {"b": 2], "c": 3"#);
+        assert_eq!(
+            vec!["1:18 ExpectCommaBrace 17..18", "1:1 ExpectNoneEOF 0..1"],
+            diags
+        );
+        assert_eq!(jz["a"][1]["b"], 2);
+        assert_eq!(jz["c"], 3);
+
+        let (jz, diags) = check("[1}] x");
+        assert_eq!(
+            vec!["1:3 ExpectCommaBracket 2..3", "1:6 ExpectNoMore 5..6"],
+            diags
+        );
+        assert_eq!("[1]", jz.stringify());
+
+        let (jz, diags) = check("  ");
+        assert_eq!(vec!["1:3 ExpectNoneEOF 2..2"], diags);
+        assert!(matches!(jz, Jzon::Error));
+    }
+}
@@ -0,0 +1,360 @@
+use crate::jzon::{escape, Escaping, Jzon};
+use std::io;
+use std::result;
+
+// Writes JSON piece by piece, checking at runtime that the calls nest
+// properly, so large documents never have to exist as a `Jzon` tree. Every
+// call goes straight to the inner writer; give it a `BufWriter` if that is
+// unbuffered.
+
+#[derive(Debug)]
+pub enum WriterErr {
+    Io(io::Error),
+    // a value in an object without its key first
+    ExpectKey,
+    // a key outside an object, or a closing bracket right after a key
+    ExpectValue,
+    ExpectObject,
+    ExpectArray,
+    // a second value at the top level
+    ExpectNoMore,
+    // `finish` with brackets left open
+    ExpectClosed,
+    // NaN and the infinities have no JSON form
+    ExpectFinite,
+}
+
+impl From<io::Error> for WriterErr {
+    fn from(e: io::Error) -> Self {
+        WriterErr::Io(e)
+    }
+}
+
+type Result = result::Result<(), WriterErr>;
+
+use WriterErr::*;
+
+#[derive(Debug)]
+struct Scope {
+    object: bool,
+    // entries written so far
+    len: usize,
+    // a key was written and its value not yet
+    keyed: bool,
+}
+
+pub struct JzonWriter<W> {
+    inner: W,
+    stack: Vec<Scope>,
+    indent: Option<usize>,
+    escaping: Escaping,
+    // a complete value is at the top level
+    done: bool,
+}
+
+impl<W: io::Write> JzonWriter<W> {
+    // compact output, like `stringify`
+    pub fn new(inner: W) -> Self {
+        JzonWriter {
+            inner,
+            stack: vec![],
+            indent: None,
+            escaping: Escaping::default(),
+            done: false,
+        }
+    }
+
+    // one value per line, like `styled().indent(width)`
+    pub fn pretty(inner: W, width: usize) -> Self {
+        JzonWriter {
+            indent: Some(width),
+            ..JzonWriter::new(inner)
+        }
+    }
+
+    pub fn escaping(self, escaping: Escaping) -> Self {
+        JzonWriter { escaping, ..self }
+    }
+
+    pub fn begin_object(&mut self) -> Result {
+        self.begin_value()?;
+        self.inner.write_all(b"{")?;
+        self.stack.push(Scope {
+            object: true,
+            len: 0,
+            keyed: false,
+        });
+        Ok(())
+    }
+
+    pub fn end_object(&mut self) -> Result {
+        match self.stack.last() {
+            Some(Scope { keyed: true, .. }) => Err(ExpectValue),
+            Some(Scope { object: true, .. }) => self.end(b"}"),
+            _ => Err(ExpectObject),
+        }
+    }
+
+    pub fn begin_array(&mut self) -> Result {
+        self.begin_value()?;
+        self.inner.write_all(b"[")?;
+        self.stack.push(Scope {
+            object: false,
+            len: 0,
+            keyed: false,
+        });
+        Ok(())
+    }
+
+    pub fn end_array(&mut self) -> Result {
+        match self.stack.last() {
+            Some(Scope { object: false, .. }) => self.end(b"]"),
+            _ => Err(ExpectArray),
+        }
+    }
+
+    pub fn key(&mut self, key: &str) -> Result {
+        let scope = match self.stack.last_mut() {
+            Some(scope) if scope.object && !scope.keyed => scope,
+            _ => return Err(ExpectValue),
+        };
+        scope.keyed = true;
+        scope.len += 1;
+        let (len, depth) = (scope.len, self.stack.len());
+        self.separate(len, depth)?;
+        escape(key, self.escaping, |s| self.inner.write_all(s.as_bytes()))?;
+        match self.indent {
+            Some(_) => self.inner.write_all(b": ")?,
+            None => self.inner.write_all(b":")?,
+        }
+        Ok(())
+    }
+
+    // a whole tree at once, laid out like the rest of the output
+    pub fn value(&mut self, jz: &Jzon) -> Result {
+        match jz {
+            Jzon::Null | Jzon::Error => self.null(),
+            Jzon::Bool(v) => self.bool(*v),
+            Jzon::Integer(v) => self.integer(*v),
+            Jzon::Double(v) => self.double(*v),
+            Jzon::String(s) => self.string(s),
+            Jzon::Array(vec) => {
+                self.begin_array()?;
+                vec.iter().try_for_each(|v| self.value(v))?;
+                self.end_array()
+            }
+            Jzon::Object(map) => {
+                self.begin_object()?;
+                map.iter().try_for_each(|(k, v)| {
+                    self.key(k)?;
+                    self.value(v)
+                })?;
+                self.end_object()
+            }
+        }
+    }
+
+    pub fn string(&mut self, s: &str) -> Result {
+        self.begin_value()?;
+        escape(s, self.escaping, |s| self.inner.write_all(s.as_bytes()))?;
+        Ok(())
+    }
+
+    pub fn integer(&mut self, v: i64) -> Result {
+        self.begin_value()?;
+        write!(self.inner, "{}", v)?;
+        Ok(())
+    }
+
+    pub fn double(&mut self, v: f64) -> Result {
+        if !v.is_finite() {
+            return Err(ExpectFinite);
+        }
+        self.begin_value()?;
+        write!(self.inner, "{}", v)?;
+        Ok(())
+    }
+
+    pub fn bool(&mut self, v: bool) -> Result {
+        self.begin_value()?;
+        self.inner.write_all(if v { b"true" } else { b"false" })?;
+        Ok(())
+    }
+
+    pub fn null(&mut self) -> Result {
+        self.begin_value()?;
+        self.inner.write_all(b"null")?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result {
+        self.inner.flush()?;
+        Ok(())
+    }
+
+    // the inner writer, flushed, once exactly one value is complete
+    pub fn finish(mut self) -> result::Result<W, WriterErr> {
+        if !self.stack.is_empty() {
+            return Err(ExpectClosed);
+        }
+        if !self.done {
+            return Err(ExpectValue);
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    // checks a value may go here and writes what comes before it
+    fn begin_value(&mut self) -> Result {
+        let depth = self.stack.len();
+        match self.stack.last_mut() {
+            None if self.done => Err(ExpectNoMore),
+            None => {
+                self.done = true;
+                Ok(())
+            }
+            Some(scope) if scope.object => {
+                if !scope.keyed {
+                    return Err(ExpectKey);
+                }
+                scope.keyed = false;
+                Ok(())
+            }
+            Some(scope) => {
+                scope.len += 1;
+                let len = scope.len;
+                self.separate(len, depth)
+            }
+        }
+    }
+
+    // the comma and line break before the `len`th entry at `depth`
+    fn separate(&mut self, len: usize, depth: usize) -> Result {
+        if len > 1 {
+            self.inner.write_all(b",")?;
+        }
+        self.newline(depth)
+    }
+
+    fn newline(&mut self, depth: usize) -> Result {
+        if let Some(width) = self.indent {
+            write!(self.inner, "\n{:1$}", "", width * depth)?;
+        }
+        Ok(())
+    }
+
+    fn end(&mut self, bracket: &[u8]) -> Result {
+        let scope = self.stack.pop().unwrap();
+        if scope.len > 0 {
+            self.newline(self.stack.len())?;
+        }
+        self.inner.write_all(bracket)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(indent: Option<usize>, f: impl Fn(&mut JzonWriter<Vec<u8>>) -> Result) -> String {
+        let mut w = match indent {
+            Some(width) => JzonWriter::pretty(vec![], width),
+            None => JzonWriter::new(vec![]),
+        };
+        f(&mut w).unwrap();
+        String::from_utf8(w.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn write() {
+        let doc = |w: &mut JzonWriter<Vec<u8>>| {
+            w.begin_object()?;
+            w.key("a/b")?;
+            w.begin_array()?;
+            w.integer(1)?;
+            w.string("x\"\u{1}\n")?;
+            w.begin_object()?;
+            w.end_object()?;
+            w.end_array()?;
+            w.key("c")?;
+            w.value(&Jzon::parse(br#"{"d": [null, true, 1.5]}"#).unwrap())?;
+            w.end_object()
+        };
+        assert_eq!(
+            r#"{"a\/b":[1,"x\"\u0001\n",{}],"c":{"d":[null,true,1.5]}}"#,
+            written(None, doc)
+        );
+        let pretty = written(Some(2), doc);
+        assert_eq!(
+            "{\n  \"a\\/b\": [\n    1,\n    \"x\\\"\\u0001\\n\",\n    {}\n  ],\n  \"c\": {\n    \"d\": [\n      null,\n      true,\n      1.5\n    ]\n  }\n}",
+            pretty
+        );
+
+        let content = std::fs::read("data/canada.json").unwrap();
+        let jz = Jzon::parse(&content).unwrap();
+        assert_eq!(jz.stringify(), written(None, |w| w.value(&jz)));
+        assert_eq!(jz.beautify(), written(Some(2), |w| w.value(&jz)));
+    }
+
+    #[test]
+    fn nesting() {
+        fn fails(f: impl Fn(&mut JzonWriter<Vec<u8>>) -> Result) -> WriterErr {
+            let mut w = JzonWriter::new(vec![]);
+            match f(&mut w) {
+                Ok(()) => w.finish().err().unwrap(),
+                Err(err) => err,
+            }
+        }
+
+        assert!(matches!(fails(|w| w.key("a")), ExpectValue));
+        assert!(matches!(fails(|w| w.end_array()), ExpectArray));
+        assert!(matches!(
+            fails(|w| {
+                w.begin_object()?;
+                w.end_array()
+            }),
+            ExpectArray
+        ));
+        assert!(matches!(
+            fails(|w| {
+                w.begin_array()?;
+                w.end_object()
+            }),
+            ExpectObject
+        ));
+        assert!(matches!(
+            fails(|w| {
+                w.begin_object()?;
+                w.null()
+            }),
+            ExpectKey
+        ));
+        assert!(matches!(
+            fails(|w| {
+                w.begin_object()?;
+                w.key("a")?;
+                w.key("b")
+            }),
+            ExpectValue
+        ));
+        assert!(matches!(
+            fails(|w| {
+                w.begin_object()?;
+                w.key("a")?;
+                w.end_object()
+            }),
+            ExpectValue
+        ));
+        assert!(matches!(fails(|w| w.begin_array()), ExpectClosed));
+        assert!(matches!(fails(|_| Ok(())), ExpectValue));
+        assert!(matches!(
+            fails(|w| {
+                w.null()?;
+                w.null()
+            }),
+            ExpectNoMore
+        ));
+        assert!(matches!(fails(|w| w.double(f64::NAN)), ExpectFinite));
+    }
+}
@@ -0,0 +1,33 @@
+// The types `jzon_pp rust` generates for twitter.json are checked in as
+// generated, unformatted, so they get compiled, and must read the document
+// and write it back as is.
+#[rustfmt::skip]
+#[path = "generated/twitter.rs"]
+mod twitter;
+
+use jzon::codegen;
+use jzon::convert::{ConvertErr, FromJzon, ToJzon};
+use jzon::Jzon;
+use std::fs;
+
+fn twitter() -> Jzon {
+    let bytes = fs::read("data/twitter.json").unwrap();
+    Jzon::parse(&bytes).unwrap()
+}
+
+#[test]
+fn up_to_date() {
+    let code = codegen::rust_types(&Jzon::infer_schema(&[twitter()]), "Twitter");
+    let checked_in = fs::read_to_string("tests/generated/twitter.rs").unwrap();
+    assert!(
+        code == checked_in,
+        "regenerate with `jzon_pp rust --name Twitter data/twitter.json > tests/generated/twitter.rs`"
+    );
+}
+
+#[test]
+fn roundtrip() -> Result<(), ConvertErr> {
+    let jz = twitter();
+    assert_eq!(jz, twitter::Twitter::from_jzon(&jz)?.to_jzon());
+    Ok(())
+}
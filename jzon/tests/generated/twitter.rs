@@ -0,0 +1,2600 @@
+// Generated from sample documents by `jzon_pp rust`.
+use jzon::convert::{ConvertErr, FromJzon, ToJzon};
+use jzon::Jzon;
+use jzon::Map;
+
+#[derive(Debug)]
+pub struct Twitter {
+    pub search_metadata: SearchMetadata,
+    pub statuses: Vec<Status>,
+}
+
+impl FromJzon for Twitter {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Twitter {
+            search_metadata: jzon::convert::field(map, "search_metadata")?,
+            statuses: jzon::convert::field(map, "statuses")?,
+        })
+    }
+}
+
+impl ToJzon for Twitter {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("search_metadata".to_string(), self.search_metadata.to_jzon());
+        map.insert("statuses".to_string(), self.statuses.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchMetadata {
+    pub completed_in: f64,
+    pub count: i64,
+    pub max_id: i64,
+    pub max_id_str: String,
+    pub next_results: String,
+    pub query: String,
+    pub refresh_url: String,
+    pub since_id: i64,
+    pub since_id_str: String,
+}
+
+impl FromJzon for SearchMetadata {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(SearchMetadata {
+            completed_in: jzon::convert::field(map, "completed_in")?,
+            count: jzon::convert::field(map, "count")?,
+            max_id: jzon::convert::field(map, "max_id")?,
+            max_id_str: jzon::convert::field(map, "max_id_str")?,
+            next_results: jzon::convert::field(map, "next_results")?,
+            query: jzon::convert::field(map, "query")?,
+            refresh_url: jzon::convert::field(map, "refresh_url")?,
+            since_id: jzon::convert::field(map, "since_id")?,
+            since_id_str: jzon::convert::field(map, "since_id_str")?,
+        })
+    }
+}
+
+impl ToJzon for SearchMetadata {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("completed_in".to_string(), self.completed_in.to_jzon());
+        map.insert("count".to_string(), self.count.to_jzon());
+        map.insert("max_id".to_string(), self.max_id.to_jzon());
+        map.insert("max_id_str".to_string(), self.max_id_str.to_jzon());
+        map.insert("next_results".to_string(), self.next_results.to_jzon());
+        map.insert("query".to_string(), self.query.to_jzon());
+        map.insert("refresh_url".to_string(), self.refresh_url.to_jzon());
+        map.insert("since_id".to_string(), self.since_id.to_jzon());
+        map.insert("since_id_str".to_string(), self.since_id_str.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Status {
+    pub contributors: Jzon,
+    pub coordinates: Jzon,
+    pub created_at: String,
+    pub entities: Entities,
+    pub favorite_count: i64,
+    pub favorited: bool,
+    pub geo: Jzon,
+    pub id: i64,
+    pub id_str: String,
+    pub in_reply_to_screen_name: Option<String>,
+    pub in_reply_to_status_id: Option<i64>,
+    pub in_reply_to_status_id_str: Option<String>,
+    pub in_reply_to_user_id: Option<i64>,
+    pub in_reply_to_user_id_str: Option<String>,
+    pub lang: Lang,
+    pub metadata: Metadata,
+    pub place: Jzon,
+    pub possibly_sensitive: Option<bool>,
+    pub retweet_count: i64,
+    pub retweeted: bool,
+    pub retweeted_status: Option<RetweetedStatus>,
+    pub source: String,
+    pub text: String,
+    pub truncated: bool,
+    pub user: User2,
+}
+
+impl FromJzon for Status {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Status {
+            contributors: jzon::convert::field(map, "contributors")?,
+            coordinates: jzon::convert::field(map, "coordinates")?,
+            created_at: jzon::convert::field(map, "created_at")?,
+            entities: jzon::convert::field(map, "entities")?,
+            favorite_count: jzon::convert::field(map, "favorite_count")?,
+            favorited: jzon::convert::field(map, "favorited")?,
+            geo: jzon::convert::field(map, "geo")?,
+            id: jzon::convert::field(map, "id")?,
+            id_str: jzon::convert::field(map, "id_str")?,
+            in_reply_to_screen_name: jzon::convert::field(map, "in_reply_to_screen_name")?,
+            in_reply_to_status_id: jzon::convert::field(map, "in_reply_to_status_id")?,
+            in_reply_to_status_id_str: jzon::convert::field(map, "in_reply_to_status_id_str")?,
+            in_reply_to_user_id: jzon::convert::field(map, "in_reply_to_user_id")?,
+            in_reply_to_user_id_str: jzon::convert::field(map, "in_reply_to_user_id_str")?,
+            lang: jzon::convert::field(map, "lang")?,
+            metadata: jzon::convert::field(map, "metadata")?,
+            place: jzon::convert::field(map, "place")?,
+            possibly_sensitive: jzon::convert::optional(map, "possibly_sensitive")?,
+            retweet_count: jzon::convert::field(map, "retweet_count")?,
+            retweeted: jzon::convert::field(map, "retweeted")?,
+            retweeted_status: jzon::convert::optional(map, "retweeted_status")?,
+            source: jzon::convert::field(map, "source")?,
+            text: jzon::convert::field(map, "text")?,
+            truncated: jzon::convert::field(map, "truncated")?,
+            user: jzon::convert::field(map, "user")?,
+        })
+    }
+}
+
+impl ToJzon for Status {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("contributors".to_string(), self.contributors.to_jzon());
+        map.insert("coordinates".to_string(), self.coordinates.to_jzon());
+        map.insert("created_at".to_string(), self.created_at.to_jzon());
+        map.insert("entities".to_string(), self.entities.to_jzon());
+        map.insert("favorite_count".to_string(), self.favorite_count.to_jzon());
+        map.insert("favorited".to_string(), self.favorited.to_jzon());
+        map.insert("geo".to_string(), self.geo.to_jzon());
+        map.insert("id".to_string(), self.id.to_jzon());
+        map.insert("id_str".to_string(), self.id_str.to_jzon());
+        map.insert("in_reply_to_screen_name".to_string(), self.in_reply_to_screen_name.to_jzon());
+        map.insert("in_reply_to_status_id".to_string(), self.in_reply_to_status_id.to_jzon());
+        map.insert("in_reply_to_status_id_str".to_string(), self.in_reply_to_status_id_str.to_jzon());
+        map.insert("in_reply_to_user_id".to_string(), self.in_reply_to_user_id.to_jzon());
+        map.insert("in_reply_to_user_id_str".to_string(), self.in_reply_to_user_id_str.to_jzon());
+        map.insert("lang".to_string(), self.lang.to_jzon());
+        map.insert("metadata".to_string(), self.metadata.to_jzon());
+        map.insert("place".to_string(), self.place.to_jzon());
+        if let Some(v) = &self.possibly_sensitive {
+            map.insert("possibly_sensitive".to_string(), v.to_jzon());
+        }
+        map.insert("retweet_count".to_string(), self.retweet_count.to_jzon());
+        map.insert("retweeted".to_string(), self.retweeted.to_jzon());
+        if let Some(v) = &self.retweeted_status {
+            map.insert("retweeted_status".to_string(), v.to_jzon());
+        }
+        map.insert("source".to_string(), self.source.to_jzon());
+        map.insert("text".to_string(), self.text.to_jzon());
+        map.insert("truncated".to_string(), self.truncated.to_jzon());
+        map.insert("user".to_string(), self.user.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Entities {
+    pub hashtags: Vec<Hashtag>,
+    pub media: Option<Vec<MediaItem>>,
+    pub symbols: Vec<Jzon>,
+    pub urls: Vec<Url>,
+    pub user_mentions: Vec<UserMention>,
+}
+
+impl FromJzon for Entities {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Entities {
+            hashtags: jzon::convert::field(map, "hashtags")?,
+            media: jzon::convert::optional(map, "media")?,
+            symbols: jzon::convert::field(map, "symbols")?,
+            urls: jzon::convert::field(map, "urls")?,
+            user_mentions: jzon::convert::field(map, "user_mentions")?,
+        })
+    }
+}
+
+impl ToJzon for Entities {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("hashtags".to_string(), self.hashtags.to_jzon());
+        if let Some(v) = &self.media {
+            map.insert("media".to_string(), v.to_jzon());
+        }
+        map.insert("symbols".to_string(), self.symbols.to_jzon());
+        map.insert("urls".to_string(), self.urls.to_jzon());
+        map.insert("user_mentions".to_string(), self.user_mentions.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Hashtag {
+    pub indices: Vec<i64>,
+    pub text: String,
+}
+
+impl FromJzon for Hashtag {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Hashtag {
+            indices: jzon::convert::field(map, "indices")?,
+            text: jzon::convert::field(map, "text")?,
+        })
+    }
+}
+
+impl ToJzon for Hashtag {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("text".to_string(), self.text.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct MediaItem {
+    pub display_url: String,
+    pub expanded_url: String,
+    pub id: i64,
+    pub id_str: String,
+    pub indices: Vec<i64>,
+    pub media_url: String,
+    pub media_url_https: String,
+    pub sizes: Sizes,
+    pub source_status_id: Option<i64>,
+    pub source_status_id_str: Option<String>,
+    pub r#type: Type,
+    pub url: String,
+}
+
+impl FromJzon for MediaItem {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(MediaItem {
+            display_url: jzon::convert::field(map, "display_url")?,
+            expanded_url: jzon::convert::field(map, "expanded_url")?,
+            id: jzon::convert::field(map, "id")?,
+            id_str: jzon::convert::field(map, "id_str")?,
+            indices: jzon::convert::field(map, "indices")?,
+            media_url: jzon::convert::field(map, "media_url")?,
+            media_url_https: jzon::convert::field(map, "media_url_https")?,
+            sizes: jzon::convert::field(map, "sizes")?,
+            source_status_id: jzon::convert::optional(map, "source_status_id")?,
+            source_status_id_str: jzon::convert::optional(map, "source_status_id_str")?,
+            r#type: jzon::convert::field(map, "type")?,
+            url: jzon::convert::field(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for MediaItem {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("display_url".to_string(), self.display_url.to_jzon());
+        map.insert("expanded_url".to_string(), self.expanded_url.to_jzon());
+        map.insert("id".to_string(), self.id.to_jzon());
+        map.insert("id_str".to_string(), self.id_str.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("media_url".to_string(), self.media_url.to_jzon());
+        map.insert("media_url_https".to_string(), self.media_url_https.to_jzon());
+        map.insert("sizes".to_string(), self.sizes.to_jzon());
+        if let Some(v) = &self.source_status_id {
+            map.insert("source_status_id".to_string(), v.to_jzon());
+        }
+        if let Some(v) = &self.source_status_id_str {
+            map.insert("source_status_id_str".to_string(), v.to_jzon());
+        }
+        map.insert("type".to_string(), self.r#type.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Sizes {
+    pub large: Large,
+    pub medium: Medium,
+    pub small: Small,
+    pub thumb: Thumb,
+}
+
+impl FromJzon for Sizes {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Sizes {
+            large: jzon::convert::field(map, "large")?,
+            medium: jzon::convert::field(map, "medium")?,
+            small: jzon::convert::field(map, "small")?,
+            thumb: jzon::convert::field(map, "thumb")?,
+        })
+    }
+}
+
+impl ToJzon for Sizes {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("large".to_string(), self.large.to_jzon());
+        map.insert("medium".to_string(), self.medium.to_jzon());
+        map.insert("small".to_string(), self.small.to_jzon());
+        map.insert("thumb".to_string(), self.thumb.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Large {
+    pub h: i64,
+    pub resize: Resize,
+    pub w: i64,
+}
+
+impl FromJzon for Large {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Large {
+            h: jzon::convert::field(map, "h")?,
+            resize: jzon::convert::field(map, "resize")?,
+            w: jzon::convert::field(map, "w")?,
+        })
+    }
+}
+
+impl ToJzon for Large {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("h".to_string(), self.h.to_jzon());
+        map.insert("resize".to_string(), self.resize.to_jzon());
+        map.insert("w".to_string(), self.w.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resize {
+    Fit,
+}
+
+impl FromJzon for Resize {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "fit" => Ok(Resize::Fit),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Resize {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Resize::Fit => "fit",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Medium {
+    pub h: i64,
+    pub resize: Resize2,
+    pub w: i64,
+}
+
+impl FromJzon for Medium {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Medium {
+            h: jzon::convert::field(map, "h")?,
+            resize: jzon::convert::field(map, "resize")?,
+            w: jzon::convert::field(map, "w")?,
+        })
+    }
+}
+
+impl ToJzon for Medium {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("h".to_string(), self.h.to_jzon());
+        map.insert("resize".to_string(), self.resize.to_jzon());
+        map.insert("w".to_string(), self.w.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resize2 {
+    Fit,
+}
+
+impl FromJzon for Resize2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "fit" => Ok(Resize2::Fit),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Resize2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Resize2::Fit => "fit",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Small {
+    pub h: i64,
+    pub resize: Resize3,
+    pub w: i64,
+}
+
+impl FromJzon for Small {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Small {
+            h: jzon::convert::field(map, "h")?,
+            resize: jzon::convert::field(map, "resize")?,
+            w: jzon::convert::field(map, "w")?,
+        })
+    }
+}
+
+impl ToJzon for Small {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("h".to_string(), self.h.to_jzon());
+        map.insert("resize".to_string(), self.resize.to_jzon());
+        map.insert("w".to_string(), self.w.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resize3 {
+    Fit,
+}
+
+impl FromJzon for Resize3 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "fit" => Ok(Resize3::Fit),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Resize3 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Resize3::Fit => "fit",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Thumb {
+    pub h: i64,
+    pub resize: Resize4,
+    pub w: i64,
+}
+
+impl FromJzon for Thumb {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Thumb {
+            h: jzon::convert::field(map, "h")?,
+            resize: jzon::convert::field(map, "resize")?,
+            w: jzon::convert::field(map, "w")?,
+        })
+    }
+}
+
+impl ToJzon for Thumb {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("h".to_string(), self.h.to_jzon());
+        map.insert("resize".to_string(), self.resize.to_jzon());
+        map.insert("w".to_string(), self.w.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resize4 {
+    Crop,
+}
+
+impl FromJzon for Resize4 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "crop" => Ok(Resize4::Crop),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Resize4 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Resize4::Crop => "crop",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Type {
+    Photo,
+}
+
+impl FromJzon for Type {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "photo" => Ok(Type::Photo),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Type {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Type::Photo => "photo",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Url {
+    pub display_url: String,
+    pub expanded_url: String,
+    pub indices: Vec<i64>,
+    pub url: String,
+}
+
+impl FromJzon for Url {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Url {
+            display_url: jzon::convert::field(map, "display_url")?,
+            expanded_url: jzon::convert::field(map, "expanded_url")?,
+            indices: jzon::convert::field(map, "indices")?,
+            url: jzon::convert::field(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for Url {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("display_url".to_string(), self.display_url.to_jzon());
+        map.insert("expanded_url".to_string(), self.expanded_url.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct UserMention {
+    pub id: i64,
+    pub id_str: String,
+    pub indices: Vec<i64>,
+    pub name: String,
+    pub screen_name: String,
+}
+
+impl FromJzon for UserMention {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(UserMention {
+            id: jzon::convert::field(map, "id")?,
+            id_str: jzon::convert::field(map, "id_str")?,
+            indices: jzon::convert::field(map, "indices")?,
+            name: jzon::convert::field(map, "name")?,
+            screen_name: jzon::convert::field(map, "screen_name")?,
+        })
+    }
+}
+
+impl ToJzon for UserMention {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("id".to_string(), self.id.to_jzon());
+        map.insert("id_str".to_string(), self.id_str.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("name".to_string(), self.name.to_jzon());
+        map.insert("screen_name".to_string(), self.screen_name.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang {
+    Ja,
+    Zh,
+}
+
+impl FromJzon for Lang {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "ja" => Ok(Lang::Ja),
+            "zh" => Ok(Lang::Zh),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Lang {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Lang::Ja => "ja",
+            Lang::Zh => "zh",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Metadata {
+    pub iso_language_code: IsoLanguageCode,
+    pub result_type: ResultType,
+}
+
+impl FromJzon for Metadata {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Metadata {
+            iso_language_code: jzon::convert::field(map, "iso_language_code")?,
+            result_type: jzon::convert::field(map, "result_type")?,
+        })
+    }
+}
+
+impl ToJzon for Metadata {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("iso_language_code".to_string(), self.iso_language_code.to_jzon());
+        map.insert("result_type".to_string(), self.result_type.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IsoLanguageCode {
+    Ja,
+    Zh,
+}
+
+impl FromJzon for IsoLanguageCode {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "ja" => Ok(IsoLanguageCode::Ja),
+            "zh" => Ok(IsoLanguageCode::Zh),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for IsoLanguageCode {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            IsoLanguageCode::Ja => "ja",
+            IsoLanguageCode::Zh => "zh",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResultType {
+    Recent,
+}
+
+impl FromJzon for ResultType {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "recent" => Ok(ResultType::Recent),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ResultType {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ResultType::Recent => "recent",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct RetweetedStatus {
+    pub contributors: Jzon,
+    pub coordinates: Jzon,
+    pub created_at: String,
+    pub entities: Entities2,
+    pub favorite_count: i64,
+    pub favorited: bool,
+    pub geo: Jzon,
+    pub id: i64,
+    pub id_str: String,
+    pub in_reply_to_screen_name: Option<String>,
+    pub in_reply_to_status_id: Option<i64>,
+    pub in_reply_to_status_id_str: Option<String>,
+    pub in_reply_to_user_id: Option<i64>,
+    pub in_reply_to_user_id_str: Option<String>,
+    pub lang: Lang2,
+    pub metadata: Metadata2,
+    pub place: Jzon,
+    pub possibly_sensitive: Option<bool>,
+    pub retweet_count: i64,
+    pub retweeted: bool,
+    pub source: Source,
+    pub text: String,
+    pub truncated: bool,
+    pub user: User,
+}
+
+impl FromJzon for RetweetedStatus {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(RetweetedStatus {
+            contributors: jzon::convert::field(map, "contributors")?,
+            coordinates: jzon::convert::field(map, "coordinates")?,
+            created_at: jzon::convert::field(map, "created_at")?,
+            entities: jzon::convert::field(map, "entities")?,
+            favorite_count: jzon::convert::field(map, "favorite_count")?,
+            favorited: jzon::convert::field(map, "favorited")?,
+            geo: jzon::convert::field(map, "geo")?,
+            id: jzon::convert::field(map, "id")?,
+            id_str: jzon::convert::field(map, "id_str")?,
+            in_reply_to_screen_name: jzon::convert::field(map, "in_reply_to_screen_name")?,
+            in_reply_to_status_id: jzon::convert::field(map, "in_reply_to_status_id")?,
+            in_reply_to_status_id_str: jzon::convert::field(map, "in_reply_to_status_id_str")?,
+            in_reply_to_user_id: jzon::convert::field(map, "in_reply_to_user_id")?,
+            in_reply_to_user_id_str: jzon::convert::field(map, "in_reply_to_user_id_str")?,
+            lang: jzon::convert::field(map, "lang")?,
+            metadata: jzon::convert::field(map, "metadata")?,
+            place: jzon::convert::field(map, "place")?,
+            possibly_sensitive: jzon::convert::optional(map, "possibly_sensitive")?,
+            retweet_count: jzon::convert::field(map, "retweet_count")?,
+            retweeted: jzon::convert::field(map, "retweeted")?,
+            source: jzon::convert::field(map, "source")?,
+            text: jzon::convert::field(map, "text")?,
+            truncated: jzon::convert::field(map, "truncated")?,
+            user: jzon::convert::field(map, "user")?,
+        })
+    }
+}
+
+impl ToJzon for RetweetedStatus {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("contributors".to_string(), self.contributors.to_jzon());
+        map.insert("coordinates".to_string(), self.coordinates.to_jzon());
+        map.insert("created_at".to_string(), self.created_at.to_jzon());
+        map.insert("entities".to_string(), self.entities.to_jzon());
+        map.insert("favorite_count".to_string(), self.favorite_count.to_jzon());
+        map.insert("favorited".to_string(), self.favorited.to_jzon());
+        map.insert("geo".to_string(), self.geo.to_jzon());
+        map.insert("id".to_string(), self.id.to_jzon());
+        map.insert("id_str".to_string(), self.id_str.to_jzon());
+        map.insert("in_reply_to_screen_name".to_string(), self.in_reply_to_screen_name.to_jzon());
+        map.insert("in_reply_to_status_id".to_string(), self.in_reply_to_status_id.to_jzon());
+        map.insert("in_reply_to_status_id_str".to_string(), self.in_reply_to_status_id_str.to_jzon());
+        map.insert("in_reply_to_user_id".to_string(), self.in_reply_to_user_id.to_jzon());
+        map.insert("in_reply_to_user_id_str".to_string(), self.in_reply_to_user_id_str.to_jzon());
+        map.insert("lang".to_string(), self.lang.to_jzon());
+        map.insert("metadata".to_string(), self.metadata.to_jzon());
+        map.insert("place".to_string(), self.place.to_jzon());
+        if let Some(v) = &self.possibly_sensitive {
+            map.insert("possibly_sensitive".to_string(), v.to_jzon());
+        }
+        map.insert("retweet_count".to_string(), self.retweet_count.to_jzon());
+        map.insert("retweeted".to_string(), self.retweeted.to_jzon());
+        map.insert("source".to_string(), self.source.to_jzon());
+        map.insert("text".to_string(), self.text.to_jzon());
+        map.insert("truncated".to_string(), self.truncated.to_jzon());
+        map.insert("user".to_string(), self.user.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Entities2 {
+    pub hashtags: Vec<Hashtag2>,
+    pub media: Option<Vec<MediaItem2>>,
+    pub symbols: Vec<Jzon>,
+    pub urls: Vec<Url2>,
+    pub user_mentions: Vec<UserMention2>,
+}
+
+impl FromJzon for Entities2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Entities2 {
+            hashtags: jzon::convert::field(map, "hashtags")?,
+            media: jzon::convert::optional(map, "media")?,
+            symbols: jzon::convert::field(map, "symbols")?,
+            urls: jzon::convert::field(map, "urls")?,
+            user_mentions: jzon::convert::field(map, "user_mentions")?,
+        })
+    }
+}
+
+impl ToJzon for Entities2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("hashtags".to_string(), self.hashtags.to_jzon());
+        if let Some(v) = &self.media {
+            map.insert("media".to_string(), v.to_jzon());
+        }
+        map.insert("symbols".to_string(), self.symbols.to_jzon());
+        map.insert("urls".to_string(), self.urls.to_jzon());
+        map.insert("user_mentions".to_string(), self.user_mentions.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Hashtag2 {
+    pub indices: Vec<i64>,
+    pub text: String,
+}
+
+impl FromJzon for Hashtag2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Hashtag2 {
+            indices: jzon::convert::field(map, "indices")?,
+            text: jzon::convert::field(map, "text")?,
+        })
+    }
+}
+
+impl ToJzon for Hashtag2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("text".to_string(), self.text.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct MediaItem2 {
+    pub display_url: String,
+    pub expanded_url: String,
+    pub id: i64,
+    pub id_str: String,
+    pub indices: Vec<i64>,
+    pub media_url: String,
+    pub media_url_https: String,
+    pub sizes: Sizes2,
+    pub source_status_id: Option<i64>,
+    pub source_status_id_str: Option<String>,
+    pub r#type: Type2,
+    pub url: String,
+}
+
+impl FromJzon for MediaItem2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(MediaItem2 {
+            display_url: jzon::convert::field(map, "display_url")?,
+            expanded_url: jzon::convert::field(map, "expanded_url")?,
+            id: jzon::convert::field(map, "id")?,
+            id_str: jzon::convert::field(map, "id_str")?,
+            indices: jzon::convert::field(map, "indices")?,
+            media_url: jzon::convert::field(map, "media_url")?,
+            media_url_https: jzon::convert::field(map, "media_url_https")?,
+            sizes: jzon::convert::field(map, "sizes")?,
+            source_status_id: jzon::convert::optional(map, "source_status_id")?,
+            source_status_id_str: jzon::convert::optional(map, "source_status_id_str")?,
+            r#type: jzon::convert::field(map, "type")?,
+            url: jzon::convert::field(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for MediaItem2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("display_url".to_string(), self.display_url.to_jzon());
+        map.insert("expanded_url".to_string(), self.expanded_url.to_jzon());
+        map.insert("id".to_string(), self.id.to_jzon());
+        map.insert("id_str".to_string(), self.id_str.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("media_url".to_string(), self.media_url.to_jzon());
+        map.insert("media_url_https".to_string(), self.media_url_https.to_jzon());
+        map.insert("sizes".to_string(), self.sizes.to_jzon());
+        if let Some(v) = &self.source_status_id {
+            map.insert("source_status_id".to_string(), v.to_jzon());
+        }
+        if let Some(v) = &self.source_status_id_str {
+            map.insert("source_status_id_str".to_string(), v.to_jzon());
+        }
+        map.insert("type".to_string(), self.r#type.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Sizes2 {
+    pub large: Large2,
+    pub medium: Medium2,
+    pub small: Small2,
+    pub thumb: Thumb2,
+}
+
+impl FromJzon for Sizes2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Sizes2 {
+            large: jzon::convert::field(map, "large")?,
+            medium: jzon::convert::field(map, "medium")?,
+            small: jzon::convert::field(map, "small")?,
+            thumb: jzon::convert::field(map, "thumb")?,
+        })
+    }
+}
+
+impl ToJzon for Sizes2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("large".to_string(), self.large.to_jzon());
+        map.insert("medium".to_string(), self.medium.to_jzon());
+        map.insert("small".to_string(), self.small.to_jzon());
+        map.insert("thumb".to_string(), self.thumb.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Large2 {
+    pub h: i64,
+    pub resize: Resize5,
+    pub w: i64,
+}
+
+impl FromJzon for Large2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Large2 {
+            h: jzon::convert::field(map, "h")?,
+            resize: jzon::convert::field(map, "resize")?,
+            w: jzon::convert::field(map, "w")?,
+        })
+    }
+}
+
+impl ToJzon for Large2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("h".to_string(), self.h.to_jzon());
+        map.insert("resize".to_string(), self.resize.to_jzon());
+        map.insert("w".to_string(), self.w.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resize5 {
+    Fit,
+}
+
+impl FromJzon for Resize5 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "fit" => Ok(Resize5::Fit),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Resize5 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Resize5::Fit => "fit",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Medium2 {
+    pub h: i64,
+    pub resize: Resize6,
+    pub w: i64,
+}
+
+impl FromJzon for Medium2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Medium2 {
+            h: jzon::convert::field(map, "h")?,
+            resize: jzon::convert::field(map, "resize")?,
+            w: jzon::convert::field(map, "w")?,
+        })
+    }
+}
+
+impl ToJzon for Medium2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("h".to_string(), self.h.to_jzon());
+        map.insert("resize".to_string(), self.resize.to_jzon());
+        map.insert("w".to_string(), self.w.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resize6 {
+    Fit,
+}
+
+impl FromJzon for Resize6 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "fit" => Ok(Resize6::Fit),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Resize6 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Resize6::Fit => "fit",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Small2 {
+    pub h: i64,
+    pub resize: Resize7,
+    pub w: i64,
+}
+
+impl FromJzon for Small2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Small2 {
+            h: jzon::convert::field(map, "h")?,
+            resize: jzon::convert::field(map, "resize")?,
+            w: jzon::convert::field(map, "w")?,
+        })
+    }
+}
+
+impl ToJzon for Small2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("h".to_string(), self.h.to_jzon());
+        map.insert("resize".to_string(), self.resize.to_jzon());
+        map.insert("w".to_string(), self.w.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resize7 {
+    Fit,
+}
+
+impl FromJzon for Resize7 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "fit" => Ok(Resize7::Fit),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Resize7 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Resize7::Fit => "fit",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Thumb2 {
+    pub h: i64,
+    pub resize: Resize8,
+    pub w: i64,
+}
+
+impl FromJzon for Thumb2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Thumb2 {
+            h: jzon::convert::field(map, "h")?,
+            resize: jzon::convert::field(map, "resize")?,
+            w: jzon::convert::field(map, "w")?,
+        })
+    }
+}
+
+impl ToJzon for Thumb2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("h".to_string(), self.h.to_jzon());
+        map.insert("resize".to_string(), self.resize.to_jzon());
+        map.insert("w".to_string(), self.w.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resize8 {
+    Crop,
+}
+
+impl FromJzon for Resize8 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "crop" => Ok(Resize8::Crop),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Resize8 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Resize8::Crop => "crop",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Type2 {
+    Photo,
+}
+
+impl FromJzon for Type2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "photo" => Ok(Type2::Photo),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Type2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Type2::Photo => "photo",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Url2 {
+    pub display_url: String,
+    pub expanded_url: String,
+    pub indices: Vec<i64>,
+    pub url: String,
+}
+
+impl FromJzon for Url2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Url2 {
+            display_url: jzon::convert::field(map, "display_url")?,
+            expanded_url: jzon::convert::field(map, "expanded_url")?,
+            indices: jzon::convert::field(map, "indices")?,
+            url: jzon::convert::field(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for Url2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("display_url".to_string(), self.display_url.to_jzon());
+        map.insert("expanded_url".to_string(), self.expanded_url.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct UserMention2 {
+    pub id: i64,
+    pub id_str: String,
+    pub indices: Vec<i64>,
+    pub name: String,
+    pub screen_name: String,
+}
+
+impl FromJzon for UserMention2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(UserMention2 {
+            id: jzon::convert::field(map, "id")?,
+            id_str: jzon::convert::field(map, "id_str")?,
+            indices: jzon::convert::field(map, "indices")?,
+            name: jzon::convert::field(map, "name")?,
+            screen_name: jzon::convert::field(map, "screen_name")?,
+        })
+    }
+}
+
+impl ToJzon for UserMention2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("id".to_string(), self.id.to_jzon());
+        map.insert("id_str".to_string(), self.id_str.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("name".to_string(), self.name.to_jzon());
+        map.insert("screen_name".to_string(), self.screen_name.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang2 {
+    Ja,
+    Zh,
+}
+
+impl FromJzon for Lang2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "ja" => Ok(Lang2::Ja),
+            "zh" => Ok(Lang2::Zh),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Lang2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Lang2::Ja => "ja",
+            Lang2::Zh => "zh",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Metadata2 {
+    pub iso_language_code: IsoLanguageCode2,
+    pub result_type: ResultType2,
+}
+
+impl FromJzon for Metadata2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Metadata2 {
+            iso_language_code: jzon::convert::field(map, "iso_language_code")?,
+            result_type: jzon::convert::field(map, "result_type")?,
+        })
+    }
+}
+
+impl ToJzon for Metadata2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("iso_language_code".to_string(), self.iso_language_code.to_jzon());
+        map.insert("result_type".to_string(), self.result_type.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IsoLanguageCode2 {
+    Ja,
+    Zh,
+}
+
+impl FromJzon for IsoLanguageCode2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "ja" => Ok(IsoLanguageCode2::Ja),
+            "zh" => Ok(IsoLanguageCode2::Zh),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for IsoLanguageCode2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            IsoLanguageCode2::Ja => "ja",
+            IsoLanguageCode2::Zh => "zh",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResultType2 {
+    Recent,
+}
+
+impl FromJzon for ResultType2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "recent" => Ok(ResultType2::Recent),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ResultType2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ResultType2::Recent => "recent",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    AHrefHttpJanetterNetRelNofollowJanetterA,
+    AHrefHttpTwittbotNetRelNofollowTwittbotNetA,
+    AHrefHttpTwitterComRelNofollowTwitterWebClientA,
+    AHrefHttpTwitterComDownloadAndroidRelNofollowTwitterForAndroidA,
+    AHrefHttpTwitterComDownloadIphoneRelNofollowTwitterForIPhoneA,
+    AHrefHttpsAboutTwitterComProductsTweetdeckRelNofollowTweetDeckA,
+    AHrefHttpsTwitterComShiawaseomamoriRelNofollow幸せのお守りA,
+}
+
+impl FromJzon for Source {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "<a href=\"http://janetter.net/\" rel=\"nofollow\">Janetter</a>" => Ok(Source::AHrefHttpJanetterNetRelNofollowJanetterA),
+            "<a href=\"http://twittbot.net/\" rel=\"nofollow\">twittbot.net</a>" => Ok(Source::AHrefHttpTwittbotNetRelNofollowTwittbotNetA),
+            "<a href=\"http://twitter.com\" rel=\"nofollow\">Twitter Web Client</a>" => Ok(Source::AHrefHttpTwitterComRelNofollowTwitterWebClientA),
+            "<a href=\"http://twitter.com/download/android\" rel=\"nofollow\">Twitter for Android</a>" => Ok(Source::AHrefHttpTwitterComDownloadAndroidRelNofollowTwitterForAndroidA),
+            "<a href=\"http://twitter.com/download/iphone\" rel=\"nofollow\">Twitter for iPhone</a>" => Ok(Source::AHrefHttpTwitterComDownloadIphoneRelNofollowTwitterForIPhoneA),
+            "<a href=\"https://about.twitter.com/products/tweetdeck\" rel=\"nofollow\">TweetDeck</a>" => Ok(Source::AHrefHttpsAboutTwitterComProductsTweetdeckRelNofollowTweetDeckA),
+            "<a href=\"https://twitter.com/shiawaseomamori\" rel=\"nofollow\">幸せの☆お守り</a>" => Ok(Source::AHrefHttpsTwitterComShiawaseomamoriRelNofollow幸せのお守りA),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Source {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Source::AHrefHttpJanetterNetRelNofollowJanetterA => "<a href=\"http://janetter.net/\" rel=\"nofollow\">Janetter</a>",
+            Source::AHrefHttpTwittbotNetRelNofollowTwittbotNetA => "<a href=\"http://twittbot.net/\" rel=\"nofollow\">twittbot.net</a>",
+            Source::AHrefHttpTwitterComRelNofollowTwitterWebClientA => "<a href=\"http://twitter.com\" rel=\"nofollow\">Twitter Web Client</a>",
+            Source::AHrefHttpTwitterComDownloadAndroidRelNofollowTwitterForAndroidA => "<a href=\"http://twitter.com/download/android\" rel=\"nofollow\">Twitter for Android</a>",
+            Source::AHrefHttpTwitterComDownloadIphoneRelNofollowTwitterForIPhoneA => "<a href=\"http://twitter.com/download/iphone\" rel=\"nofollow\">Twitter for iPhone</a>",
+            Source::AHrefHttpsAboutTwitterComProductsTweetdeckRelNofollowTweetDeckA => "<a href=\"https://about.twitter.com/products/tweetdeck\" rel=\"nofollow\">TweetDeck</a>",
+            Source::AHrefHttpsTwitterComShiawaseomamoriRelNofollow幸せのお守りA => "<a href=\"https://twitter.com/shiawaseomamori\" rel=\"nofollow\">幸せの☆お守り</a>",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct User {
+    pub contributors_enabled: bool,
+    pub created_at: String,
+    pub default_profile: bool,
+    pub default_profile_image: bool,
+    pub description: String,
+    pub entities: Entities3,
+    pub favourites_count: i64,
+    pub follow_request_sent: bool,
+    pub followers_count: i64,
+    pub following: bool,
+    pub friends_count: i64,
+    pub geo_enabled: bool,
+    pub id: i64,
+    pub id_str: String,
+    pub is_translation_enabled: bool,
+    pub is_translator: bool,
+    pub lang: Lang3,
+    pub listed_count: i64,
+    pub location: String,
+    pub name: String,
+    pub notifications: bool,
+    pub profile_background_color: ProfileBackgroundColor,
+    pub profile_background_image_url: String,
+    pub profile_background_image_url_https: String,
+    pub profile_background_tile: bool,
+    pub profile_banner_url: Option<String>,
+    pub profile_image_url: String,
+    pub profile_image_url_https: String,
+    pub profile_link_color: ProfileLinkColor,
+    pub profile_sidebar_border_color: ProfileSidebarBorderColor,
+    pub profile_sidebar_fill_color: ProfileSidebarFillColor,
+    pub profile_text_color: ProfileTextColor,
+    pub profile_use_background_image: bool,
+    pub protected: bool,
+    pub screen_name: String,
+    pub statuses_count: i64,
+    pub time_zone: Option<TimeZone>,
+    pub url: Option<String>,
+    pub utc_offset: Option<i64>,
+    pub verified: bool,
+}
+
+impl FromJzon for User {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(User {
+            contributors_enabled: jzon::convert::field(map, "contributors_enabled")?,
+            created_at: jzon::convert::field(map, "created_at")?,
+            default_profile: jzon::convert::field(map, "default_profile")?,
+            default_profile_image: jzon::convert::field(map, "default_profile_image")?,
+            description: jzon::convert::field(map, "description")?,
+            entities: jzon::convert::field(map, "entities")?,
+            favourites_count: jzon::convert::field(map, "favourites_count")?,
+            follow_request_sent: jzon::convert::field(map, "follow_request_sent")?,
+            followers_count: jzon::convert::field(map, "followers_count")?,
+            following: jzon::convert::field(map, "following")?,
+            friends_count: jzon::convert::field(map, "friends_count")?,
+            geo_enabled: jzon::convert::field(map, "geo_enabled")?,
+            id: jzon::convert::field(map, "id")?,
+            id_str: jzon::convert::field(map, "id_str")?,
+            is_translation_enabled: jzon::convert::field(map, "is_translation_enabled")?,
+            is_translator: jzon::convert::field(map, "is_translator")?,
+            lang: jzon::convert::field(map, "lang")?,
+            listed_count: jzon::convert::field(map, "listed_count")?,
+            location: jzon::convert::field(map, "location")?,
+            name: jzon::convert::field(map, "name")?,
+            notifications: jzon::convert::field(map, "notifications")?,
+            profile_background_color: jzon::convert::field(map, "profile_background_color")?,
+            profile_background_image_url: jzon::convert::field(map, "profile_background_image_url")?,
+            profile_background_image_url_https: jzon::convert::field(map, "profile_background_image_url_https")?,
+            profile_background_tile: jzon::convert::field(map, "profile_background_tile")?,
+            profile_banner_url: jzon::convert::optional(map, "profile_banner_url")?,
+            profile_image_url: jzon::convert::field(map, "profile_image_url")?,
+            profile_image_url_https: jzon::convert::field(map, "profile_image_url_https")?,
+            profile_link_color: jzon::convert::field(map, "profile_link_color")?,
+            profile_sidebar_border_color: jzon::convert::field(map, "profile_sidebar_border_color")?,
+            profile_sidebar_fill_color: jzon::convert::field(map, "profile_sidebar_fill_color")?,
+            profile_text_color: jzon::convert::field(map, "profile_text_color")?,
+            profile_use_background_image: jzon::convert::field(map, "profile_use_background_image")?,
+            protected: jzon::convert::field(map, "protected")?,
+            screen_name: jzon::convert::field(map, "screen_name")?,
+            statuses_count: jzon::convert::field(map, "statuses_count")?,
+            time_zone: jzon::convert::field(map, "time_zone")?,
+            url: jzon::convert::field(map, "url")?,
+            utc_offset: jzon::convert::field(map, "utc_offset")?,
+            verified: jzon::convert::field(map, "verified")?,
+        })
+    }
+}
+
+impl ToJzon for User {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("contributors_enabled".to_string(), self.contributors_enabled.to_jzon());
+        map.insert("created_at".to_string(), self.created_at.to_jzon());
+        map.insert("default_profile".to_string(), self.default_profile.to_jzon());
+        map.insert("default_profile_image".to_string(), self.default_profile_image.to_jzon());
+        map.insert("description".to_string(), self.description.to_jzon());
+        map.insert("entities".to_string(), self.entities.to_jzon());
+        map.insert("favourites_count".to_string(), self.favourites_count.to_jzon());
+        map.insert("follow_request_sent".to_string(), self.follow_request_sent.to_jzon());
+        map.insert("followers_count".to_string(), self.followers_count.to_jzon());
+        map.insert("following".to_string(), self.following.to_jzon());
+        map.insert("friends_count".to_string(), self.friends_count.to_jzon());
+        map.insert("geo_enabled".to_string(), self.geo_enabled.to_jzon());
+        map.insert("id".to_string(), self.id.to_jzon());
+        map.insert("id_str".to_string(), self.id_str.to_jzon());
+        map.insert("is_translation_enabled".to_string(), self.is_translation_enabled.to_jzon());
+        map.insert("is_translator".to_string(), self.is_translator.to_jzon());
+        map.insert("lang".to_string(), self.lang.to_jzon());
+        map.insert("listed_count".to_string(), self.listed_count.to_jzon());
+        map.insert("location".to_string(), self.location.to_jzon());
+        map.insert("name".to_string(), self.name.to_jzon());
+        map.insert("notifications".to_string(), self.notifications.to_jzon());
+        map.insert("profile_background_color".to_string(), self.profile_background_color.to_jzon());
+        map.insert("profile_background_image_url".to_string(), self.profile_background_image_url.to_jzon());
+        map.insert("profile_background_image_url_https".to_string(), self.profile_background_image_url_https.to_jzon());
+        map.insert("profile_background_tile".to_string(), self.profile_background_tile.to_jzon());
+        if let Some(v) = &self.profile_banner_url {
+            map.insert("profile_banner_url".to_string(), v.to_jzon());
+        }
+        map.insert("profile_image_url".to_string(), self.profile_image_url.to_jzon());
+        map.insert("profile_image_url_https".to_string(), self.profile_image_url_https.to_jzon());
+        map.insert("profile_link_color".to_string(), self.profile_link_color.to_jzon());
+        map.insert("profile_sidebar_border_color".to_string(), self.profile_sidebar_border_color.to_jzon());
+        map.insert("profile_sidebar_fill_color".to_string(), self.profile_sidebar_fill_color.to_jzon());
+        map.insert("profile_text_color".to_string(), self.profile_text_color.to_jzon());
+        map.insert("profile_use_background_image".to_string(), self.profile_use_background_image.to_jzon());
+        map.insert("protected".to_string(), self.protected.to_jzon());
+        map.insert("screen_name".to_string(), self.screen_name.to_jzon());
+        map.insert("statuses_count".to_string(), self.statuses_count.to_jzon());
+        map.insert("time_zone".to_string(), self.time_zone.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        map.insert("utc_offset".to_string(), self.utc_offset.to_jzon());
+        map.insert("verified".to_string(), self.verified.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Entities3 {
+    pub description: Description,
+    pub url: Option<Url4>,
+}
+
+impl FromJzon for Entities3 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Entities3 {
+            description: jzon::convert::field(map, "description")?,
+            url: jzon::convert::optional(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for Entities3 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("description".to_string(), self.description.to_jzon());
+        if let Some(v) = &self.url {
+            map.insert("url".to_string(), v.to_jzon());
+        }
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Description {
+    pub urls: Vec<Url3>,
+}
+
+impl FromJzon for Description {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Description {
+            urls: jzon::convert::field(map, "urls")?,
+        })
+    }
+}
+
+impl ToJzon for Description {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("urls".to_string(), self.urls.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Url3 {
+    pub display_url: String,
+    pub expanded_url: String,
+    pub indices: Vec<i64>,
+    pub url: String,
+}
+
+impl FromJzon for Url3 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Url3 {
+            display_url: jzon::convert::field(map, "display_url")?,
+            expanded_url: jzon::convert::field(map, "expanded_url")?,
+            indices: jzon::convert::field(map, "indices")?,
+            url: jzon::convert::field(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for Url3 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("display_url".to_string(), self.display_url.to_jzon());
+        map.insert("expanded_url".to_string(), self.expanded_url.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Url4 {
+    pub urls: Vec<Url5>,
+}
+
+impl FromJzon for Url4 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Url4 {
+            urls: jzon::convert::field(map, "urls")?,
+        })
+    }
+}
+
+impl ToJzon for Url4 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("urls".to_string(), self.urls.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Url5 {
+    pub display_url: String,
+    pub expanded_url: String,
+    pub indices: Vec<i64>,
+    pub url: String,
+}
+
+impl FromJzon for Url5 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Url5 {
+            display_url: jzon::convert::field(map, "display_url")?,
+            expanded_url: jzon::convert::field(map, "expanded_url")?,
+            indices: jzon::convert::field(map, "indices")?,
+            url: jzon::convert::field(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for Url5 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("display_url".to_string(), self.display_url.to_jzon());
+        map.insert("expanded_url".to_string(), self.expanded_url.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang3 {
+    En,
+    Ja,
+}
+
+impl FromJzon for Lang3 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "en" => Ok(Lang3::En),
+            "ja" => Ok(Lang3::Ja),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Lang3 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Lang3::En => "en",
+            Lang3::Ja => "ja",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileBackgroundColor {
+    V131516,
+    V352726,
+    C0Deed,
+    Edece9,
+    Ffffff,
+}
+
+impl FromJzon for ProfileBackgroundColor {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "131516" => Ok(ProfileBackgroundColor::V131516),
+            "352726" => Ok(ProfileBackgroundColor::V352726),
+            "C0DEED" => Ok(ProfileBackgroundColor::C0Deed),
+            "EDECE9" => Ok(ProfileBackgroundColor::Edece9),
+            "FFFFFF" => Ok(ProfileBackgroundColor::Ffffff),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ProfileBackgroundColor {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ProfileBackgroundColor::V131516 => "131516",
+            ProfileBackgroundColor::V352726 => "352726",
+            ProfileBackgroundColor::C0Deed => "C0DEED",
+            ProfileBackgroundColor::Edece9 => "EDECE9",
+            ProfileBackgroundColor::Ffffff => "FFFFFF",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileLinkColor {
+    V0084B4,
+    V088253,
+    D02B55,
+    Ed1313,
+    Ff2424,
+}
+
+impl FromJzon for ProfileLinkColor {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "0084B4" => Ok(ProfileLinkColor::V0084B4),
+            "088253" => Ok(ProfileLinkColor::V088253),
+            "D02B55" => Ok(ProfileLinkColor::D02B55),
+            "ED1313" => Ok(ProfileLinkColor::Ed1313),
+            "FF2424" => Ok(ProfileLinkColor::Ff2424),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ProfileLinkColor {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ProfileLinkColor::V0084B4 => "0084B4",
+            ProfileLinkColor::V088253 => "088253",
+            ProfileLinkColor::D02B55 => "D02B55",
+            ProfileLinkColor::Ed1313 => "ED1313",
+            ProfileLinkColor::Ff2424 => "FF2424",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileSidebarBorderColor {
+    V829D5E,
+    C0Deed,
+    D3D2Cf,
+    Eeeeee,
+    Ffffff,
+}
+
+impl FromJzon for ProfileSidebarBorderColor {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "829D5E" => Ok(ProfileSidebarBorderColor::V829D5E),
+            "C0DEED" => Ok(ProfileSidebarBorderColor::C0Deed),
+            "D3D2CF" => Ok(ProfileSidebarBorderColor::D3D2Cf),
+            "EEEEEE" => Ok(ProfileSidebarBorderColor::Eeeeee),
+            "FFFFFF" => Ok(ProfileSidebarBorderColor::Ffffff),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ProfileSidebarBorderColor {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ProfileSidebarBorderColor::V829D5E => "829D5E",
+            ProfileSidebarBorderColor::C0Deed => "C0DEED",
+            ProfileSidebarBorderColor::D3D2Cf => "D3D2CF",
+            ProfileSidebarBorderColor::Eeeeee => "EEEEEE",
+            ProfileSidebarBorderColor::Ffffff => "FFFFFF",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileSidebarFillColor {
+    V99Cc33,
+    Ddeef6,
+    E0Ff92,
+    E3E2De,
+    Efefef,
+}
+
+impl FromJzon for ProfileSidebarFillColor {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "99CC33" => Ok(ProfileSidebarFillColor::V99Cc33),
+            "DDEEF6" => Ok(ProfileSidebarFillColor::Ddeef6),
+            "E0FF92" => Ok(ProfileSidebarFillColor::E0Ff92),
+            "E3E2DE" => Ok(ProfileSidebarFillColor::E3E2De),
+            "EFEFEF" => Ok(ProfileSidebarFillColor::Efefef),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ProfileSidebarFillColor {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ProfileSidebarFillColor::V99Cc33 => "99CC33",
+            ProfileSidebarFillColor::Ddeef6 => "DDEEF6",
+            ProfileSidebarFillColor::E0Ff92 => "E0FF92",
+            ProfileSidebarFillColor::E3E2De => "E3E2DE",
+            ProfileSidebarFillColor::Efefef => "EFEFEF",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileTextColor {
+    V000000,
+    V333333,
+    V3E4415,
+    V634047,
+}
+
+impl FromJzon for ProfileTextColor {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "000000" => Ok(ProfileTextColor::V000000),
+            "333333" => Ok(ProfileTextColor::V333333),
+            "3E4415" => Ok(ProfileTextColor::V3E4415),
+            "634047" => Ok(ProfileTextColor::V634047),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ProfileTextColor {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ProfileTextColor::V000000 => "000000",
+            ProfileTextColor::V333333 => "333333",
+            ProfileTextColor::V3E4415 => "3E4415",
+            ProfileTextColor::V634047 => "634047",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeZone {
+    Hawaii,
+    Irkutsk,
+    Singapore,
+    Tokyo,
+}
+
+impl FromJzon for TimeZone {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "Hawaii" => Ok(TimeZone::Hawaii),
+            "Irkutsk" => Ok(TimeZone::Irkutsk),
+            "Singapore" => Ok(TimeZone::Singapore),
+            "Tokyo" => Ok(TimeZone::Tokyo),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for TimeZone {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            TimeZone::Hawaii => "Hawaii",
+            TimeZone::Irkutsk => "Irkutsk",
+            TimeZone::Singapore => "Singapore",
+            TimeZone::Tokyo => "Tokyo",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct User2 {
+    pub contributors_enabled: bool,
+    pub created_at: String,
+    pub default_profile: bool,
+    pub default_profile_image: bool,
+    pub description: String,
+    pub entities: Entities4,
+    pub favourites_count: i64,
+    pub follow_request_sent: bool,
+    pub followers_count: i64,
+    pub following: bool,
+    pub friends_count: i64,
+    pub geo_enabled: bool,
+    pub id: i64,
+    pub id_str: String,
+    pub is_translation_enabled: bool,
+    pub is_translator: bool,
+    pub lang: Lang4,
+    pub listed_count: i64,
+    pub location: String,
+    pub name: String,
+    pub notifications: bool,
+    pub profile_background_color: String,
+    pub profile_background_image_url: String,
+    pub profile_background_image_url_https: String,
+    pub profile_background_tile: bool,
+    pub profile_banner_url: Option<String>,
+    pub profile_image_url: String,
+    pub profile_image_url_https: String,
+    pub profile_link_color: String,
+    pub profile_sidebar_border_color: ProfileSidebarBorderColor2,
+    pub profile_sidebar_fill_color: ProfileSidebarFillColor2,
+    pub profile_text_color: ProfileTextColor2,
+    pub profile_use_background_image: bool,
+    pub protected: bool,
+    pub screen_name: String,
+    pub statuses_count: i64,
+    pub time_zone: Option<TimeZone2>,
+    pub url: Option<String>,
+    pub utc_offset: Option<i64>,
+    pub verified: bool,
+}
+
+impl FromJzon for User2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(User2 {
+            contributors_enabled: jzon::convert::field(map, "contributors_enabled")?,
+            created_at: jzon::convert::field(map, "created_at")?,
+            default_profile: jzon::convert::field(map, "default_profile")?,
+            default_profile_image: jzon::convert::field(map, "default_profile_image")?,
+            description: jzon::convert::field(map, "description")?,
+            entities: jzon::convert::field(map, "entities")?,
+            favourites_count: jzon::convert::field(map, "favourites_count")?,
+            follow_request_sent: jzon::convert::field(map, "follow_request_sent")?,
+            followers_count: jzon::convert::field(map, "followers_count")?,
+            following: jzon::convert::field(map, "following")?,
+            friends_count: jzon::convert::field(map, "friends_count")?,
+            geo_enabled: jzon::convert::field(map, "geo_enabled")?,
+            id: jzon::convert::field(map, "id")?,
+            id_str: jzon::convert::field(map, "id_str")?,
+            is_translation_enabled: jzon::convert::field(map, "is_translation_enabled")?,
+            is_translator: jzon::convert::field(map, "is_translator")?,
+            lang: jzon::convert::field(map, "lang")?,
+            listed_count: jzon::convert::field(map, "listed_count")?,
+            location: jzon::convert::field(map, "location")?,
+            name: jzon::convert::field(map, "name")?,
+            notifications: jzon::convert::field(map, "notifications")?,
+            profile_background_color: jzon::convert::field(map, "profile_background_color")?,
+            profile_background_image_url: jzon::convert::field(map, "profile_background_image_url")?,
+            profile_background_image_url_https: jzon::convert::field(map, "profile_background_image_url_https")?,
+            profile_background_tile: jzon::convert::field(map, "profile_background_tile")?,
+            profile_banner_url: jzon::convert::optional(map, "profile_banner_url")?,
+            profile_image_url: jzon::convert::field(map, "profile_image_url")?,
+            profile_image_url_https: jzon::convert::field(map, "profile_image_url_https")?,
+            profile_link_color: jzon::convert::field(map, "profile_link_color")?,
+            profile_sidebar_border_color: jzon::convert::field(map, "profile_sidebar_border_color")?,
+            profile_sidebar_fill_color: jzon::convert::field(map, "profile_sidebar_fill_color")?,
+            profile_text_color: jzon::convert::field(map, "profile_text_color")?,
+            profile_use_background_image: jzon::convert::field(map, "profile_use_background_image")?,
+            protected: jzon::convert::field(map, "protected")?,
+            screen_name: jzon::convert::field(map, "screen_name")?,
+            statuses_count: jzon::convert::field(map, "statuses_count")?,
+            time_zone: jzon::convert::field(map, "time_zone")?,
+            url: jzon::convert::field(map, "url")?,
+            utc_offset: jzon::convert::field(map, "utc_offset")?,
+            verified: jzon::convert::field(map, "verified")?,
+        })
+    }
+}
+
+impl ToJzon for User2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("contributors_enabled".to_string(), self.contributors_enabled.to_jzon());
+        map.insert("created_at".to_string(), self.created_at.to_jzon());
+        map.insert("default_profile".to_string(), self.default_profile.to_jzon());
+        map.insert("default_profile_image".to_string(), self.default_profile_image.to_jzon());
+        map.insert("description".to_string(), self.description.to_jzon());
+        map.insert("entities".to_string(), self.entities.to_jzon());
+        map.insert("favourites_count".to_string(), self.favourites_count.to_jzon());
+        map.insert("follow_request_sent".to_string(), self.follow_request_sent.to_jzon());
+        map.insert("followers_count".to_string(), self.followers_count.to_jzon());
+        map.insert("following".to_string(), self.following.to_jzon());
+        map.insert("friends_count".to_string(), self.friends_count.to_jzon());
+        map.insert("geo_enabled".to_string(), self.geo_enabled.to_jzon());
+        map.insert("id".to_string(), self.id.to_jzon());
+        map.insert("id_str".to_string(), self.id_str.to_jzon());
+        map.insert("is_translation_enabled".to_string(), self.is_translation_enabled.to_jzon());
+        map.insert("is_translator".to_string(), self.is_translator.to_jzon());
+        map.insert("lang".to_string(), self.lang.to_jzon());
+        map.insert("listed_count".to_string(), self.listed_count.to_jzon());
+        map.insert("location".to_string(), self.location.to_jzon());
+        map.insert("name".to_string(), self.name.to_jzon());
+        map.insert("notifications".to_string(), self.notifications.to_jzon());
+        map.insert("profile_background_color".to_string(), self.profile_background_color.to_jzon());
+        map.insert("profile_background_image_url".to_string(), self.profile_background_image_url.to_jzon());
+        map.insert("profile_background_image_url_https".to_string(), self.profile_background_image_url_https.to_jzon());
+        map.insert("profile_background_tile".to_string(), self.profile_background_tile.to_jzon());
+        if let Some(v) = &self.profile_banner_url {
+            map.insert("profile_banner_url".to_string(), v.to_jzon());
+        }
+        map.insert("profile_image_url".to_string(), self.profile_image_url.to_jzon());
+        map.insert("profile_image_url_https".to_string(), self.profile_image_url_https.to_jzon());
+        map.insert("profile_link_color".to_string(), self.profile_link_color.to_jzon());
+        map.insert("profile_sidebar_border_color".to_string(), self.profile_sidebar_border_color.to_jzon());
+        map.insert("profile_sidebar_fill_color".to_string(), self.profile_sidebar_fill_color.to_jzon());
+        map.insert("profile_text_color".to_string(), self.profile_text_color.to_jzon());
+        map.insert("profile_use_background_image".to_string(), self.profile_use_background_image.to_jzon());
+        map.insert("protected".to_string(), self.protected.to_jzon());
+        map.insert("screen_name".to_string(), self.screen_name.to_jzon());
+        map.insert("statuses_count".to_string(), self.statuses_count.to_jzon());
+        map.insert("time_zone".to_string(), self.time_zone.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        map.insert("utc_offset".to_string(), self.utc_offset.to_jzon());
+        map.insert("verified".to_string(), self.verified.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Entities4 {
+    pub description: Description2,
+    pub url: Option<Url7>,
+}
+
+impl FromJzon for Entities4 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Entities4 {
+            description: jzon::convert::field(map, "description")?,
+            url: jzon::convert::optional(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for Entities4 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("description".to_string(), self.description.to_jzon());
+        if let Some(v) = &self.url {
+            map.insert("url".to_string(), v.to_jzon());
+        }
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Description2 {
+    pub urls: Vec<Url6>,
+}
+
+impl FromJzon for Description2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Description2 {
+            urls: jzon::convert::field(map, "urls")?,
+        })
+    }
+}
+
+impl ToJzon for Description2 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("urls".to_string(), self.urls.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Url6 {
+    pub display_url: String,
+    pub expanded_url: String,
+    pub indices: Vec<i64>,
+    pub url: String,
+}
+
+impl FromJzon for Url6 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Url6 {
+            display_url: jzon::convert::field(map, "display_url")?,
+            expanded_url: jzon::convert::field(map, "expanded_url")?,
+            indices: jzon::convert::field(map, "indices")?,
+            url: jzon::convert::field(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for Url6 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("display_url".to_string(), self.display_url.to_jzon());
+        map.insert("expanded_url".to_string(), self.expanded_url.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Url7 {
+    pub urls: Vec<Url8>,
+}
+
+impl FromJzon for Url7 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Url7 {
+            urls: jzon::convert::field(map, "urls")?,
+        })
+    }
+}
+
+impl ToJzon for Url7 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("urls".to_string(), self.urls.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug)]
+pub struct Url8 {
+    pub display_url: String,
+    pub expanded_url: String,
+    pub indices: Vec<i64>,
+    pub url: String,
+}
+
+impl FromJzon for Url8 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let map = match jz {
+            Jzon::Object(map) => map,
+            _ => return Err(ConvertErr::ExpectObject),
+        };
+        Ok(Url8 {
+            display_url: jzon::convert::field(map, "display_url")?,
+            expanded_url: jzon::convert::field(map, "expanded_url")?,
+            indices: jzon::convert::field(map, "indices")?,
+            url: jzon::convert::field(map, "url")?,
+        })
+    }
+}
+
+impl ToJzon for Url8 {
+    fn to_jzon(&self) -> Jzon {
+        let mut map = Map::new();
+        map.insert("display_url".to_string(), self.display_url.to_jzon());
+        map.insert("expanded_url".to_string(), self.expanded_url.to_jzon());
+        map.insert("indices".to_string(), self.indices.to_jzon());
+        map.insert("url".to_string(), self.url.to_jzon());
+        Jzon::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang4 {
+    En,
+    Es,
+    It,
+    Ja,
+    ZhCn,
+}
+
+impl FromJzon for Lang4 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "en" => Ok(Lang4::En),
+            "es" => Ok(Lang4::Es),
+            "it" => Ok(Lang4::It),
+            "ja" => Ok(Lang4::Ja),
+            "zh-cn" => Ok(Lang4::ZhCn),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for Lang4 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            Lang4::En => "en",
+            Lang4::Es => "es",
+            Lang4::It => "it",
+            Lang4::Ja => "ja",
+            Lang4::ZhCn => "zh-cn",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileSidebarBorderColor2 {
+    V000000,
+    V181A1E,
+    V5Ed4Dc,
+    V829D5E,
+    V86A4A6,
+    C0Deed,
+    Eeeeee,
+    Ffffff,
+}
+
+impl FromJzon for ProfileSidebarBorderColor2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "000000" => Ok(ProfileSidebarBorderColor2::V000000),
+            "181A1E" => Ok(ProfileSidebarBorderColor2::V181A1E),
+            "5ED4DC" => Ok(ProfileSidebarBorderColor2::V5Ed4Dc),
+            "829D5E" => Ok(ProfileSidebarBorderColor2::V829D5E),
+            "86A4A6" => Ok(ProfileSidebarBorderColor2::V86A4A6),
+            "C0DEED" => Ok(ProfileSidebarBorderColor2::C0Deed),
+            "EEEEEE" => Ok(ProfileSidebarBorderColor2::Eeeeee),
+            "FFFFFF" => Ok(ProfileSidebarBorderColor2::Ffffff),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ProfileSidebarBorderColor2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ProfileSidebarBorderColor2::V000000 => "000000",
+            ProfileSidebarBorderColor2::V181A1E => "181A1E",
+            ProfileSidebarBorderColor2::V5Ed4Dc => "5ED4DC",
+            ProfileSidebarBorderColor2::V829D5E => "829D5E",
+            ProfileSidebarBorderColor2::V86A4A6 => "86A4A6",
+            ProfileSidebarBorderColor2::C0Deed => "C0DEED",
+            ProfileSidebarBorderColor2::Eeeeee => "EEEEEE",
+            ProfileSidebarBorderColor2::Ffffff => "FFFFFF",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileSidebarFillColor2 {
+    V252429,
+    V95E8Ec,
+    V99Cc33,
+    A0C5C7,
+    Ddeef6,
+    Efefef,
+    F065A8,
+    F6Ffd1,
+}
+
+impl FromJzon for ProfileSidebarFillColor2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "252429" => Ok(ProfileSidebarFillColor2::V252429),
+            "95E8EC" => Ok(ProfileSidebarFillColor2::V95E8Ec),
+            "99CC33" => Ok(ProfileSidebarFillColor2::V99Cc33),
+            "A0C5C7" => Ok(ProfileSidebarFillColor2::A0C5C7),
+            "DDEEF6" => Ok(ProfileSidebarFillColor2::Ddeef6),
+            "EFEFEF" => Ok(ProfileSidebarFillColor2::Efefef),
+            "F065A8" => Ok(ProfileSidebarFillColor2::F065A8),
+            "F6FFD1" => Ok(ProfileSidebarFillColor2::F6Ffd1),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ProfileSidebarFillColor2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ProfileSidebarFillColor2::V252429 => "252429",
+            ProfileSidebarFillColor2::V95E8Ec => "95E8EC",
+            ProfileSidebarFillColor2::V99Cc33 => "99CC33",
+            ProfileSidebarFillColor2::A0C5C7 => "A0C5C7",
+            ProfileSidebarFillColor2::Ddeef6 => "DDEEF6",
+            ProfileSidebarFillColor2::Efefef => "EFEFEF",
+            ProfileSidebarFillColor2::F065A8 => "F065A8",
+            ProfileSidebarFillColor2::F6Ffd1 => "F6FFD1",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileTextColor2 {
+    V080808,
+    V333333,
+    V3C3940,
+    V3E4415,
+    V666666,
+}
+
+impl FromJzon for ProfileTextColor2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "080808" => Ok(ProfileTextColor2::V080808),
+            "333333" => Ok(ProfileTextColor2::V333333),
+            "3C3940" => Ok(ProfileTextColor2::V3C3940),
+            "3E4415" => Ok(ProfileTextColor2::V3E4415),
+            "666666" => Ok(ProfileTextColor2::V666666),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for ProfileTextColor2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            ProfileTextColor2::V080808 => "080808",
+            ProfileTextColor2::V333333 => "333333",
+            ProfileTextColor2::V3C3940 => "3C3940",
+            ProfileTextColor2::V3E4415 => "3E4415",
+            ProfileTextColor2::V666666 => "666666",
+        };
+        Jzon::String(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeZone2 {
+    Alaska,
+    Amsterdam,
+    Hawaii,
+    Irkutsk,
+    Osaka,
+    Seoul,
+    Tokyo,
+}
+
+impl FromJzon for TimeZone2 {
+    fn from_jzon(jz: &Jzon) -> Result<Self, ConvertErr> {
+        let s = match jz {
+            Jzon::String(s) => s,
+            _ => return Err(ConvertErr::ExpectString),
+        };
+        match s.as_str() {
+            "Alaska" => Ok(TimeZone2::Alaska),
+            "Amsterdam" => Ok(TimeZone2::Amsterdam),
+            "Hawaii" => Ok(TimeZone2::Hawaii),
+            "Irkutsk" => Ok(TimeZone2::Irkutsk),
+            "Osaka" => Ok(TimeZone2::Osaka),
+            "Seoul" => Ok(TimeZone2::Seoul),
+            "Tokyo" => Ok(TimeZone2::Tokyo),
+            _ => Err(ConvertErr::ExpectVariant),
+        }
+    }
+}
+
+impl ToJzon for TimeZone2 {
+    fn to_jzon(&self) -> Jzon {
+        let s = match self {
+            TimeZone2::Alaska => "Alaska",
+            TimeZone2::Amsterdam => "Amsterdam",
+            TimeZone2::Hawaii => "Hawaii",
+            TimeZone2::Irkutsk => "Irkutsk",
+            TimeZone2::Osaka => "Osaka",
+            TimeZone2::Seoul => "Seoul",
+            TimeZone2::Tokyo => "Tokyo",
+        };
+        Jzon::String(s.to_string())
+    }
+}